@@ -0,0 +1 @@
+ALTER TABLE entity_effects ADD COLUMN elapsed_ticks INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+ALTER TABLE rooms ADD COLUMN up_json TEXT;
+ALTER TABLE rooms ADD COLUMN down_json TEXT;
+ALTER TABLE rooms ADD COLUMN northeast_json TEXT;
+ALTER TABLE rooms ADD COLUMN northwest_json TEXT;
+ALTER TABLE rooms ADD COLUMN southeast_json TEXT;
+ALTER TABLE rooms ADD COLUMN southwest_json TEXT;
+ALTER TABLE rooms ADD COLUMN exits_json TEXT;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS player_channels (
+    player_id INTEGER NOT NULL,
+    channel TEXT NOT NULL,
+    joined INTEGER NOT NULL,
+    PRIMARY KEY (player_id, channel),
+    FOREIGN KEY (player_id) REFERENCES players(id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS mail (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    sender_id INTEGER NOT NULL,
+    recipient_id INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    sent_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    read INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (sender_id) REFERENCES players(id) ON DELETE CASCADE,
+    FOREIGN KEY (recipient_id) REFERENCES players(id) ON DELETE CASCADE
+);
+CREATE INDEX idx_mail_recipient_id ON mail(recipient_id);
@@ -0,0 +1 @@
+ALTER TABLE entities ADD COLUMN holder_id INTEGER;
@@ -0,0 +1 @@
+ALTER TABLE entities ADD COLUMN equipped INTEGER NOT NULL DEFAULT 0;
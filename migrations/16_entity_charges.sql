@@ -0,0 +1 @@
+ALTER TABLE entities ADD COLUMN charges INTEGER;
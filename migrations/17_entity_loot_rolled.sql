@@ -0,0 +1 @@
+ALTER TABLE entities ADD COLUMN loot_rolled INTEGER NOT NULL DEFAULT 0;
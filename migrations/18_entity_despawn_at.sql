@@ -0,0 +1 @@
+ALTER TABLE entities ADD COLUMN despawn_at INTEGER;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS dialog_roll_failures (
+    player_id INTEGER NOT NULL,
+    npc TEXT NOT NULL,
+    path TEXT NOT NULL,
+    PRIMARY KEY (player_id, npc, path),
+    FOREIGN KEY (player_id) REFERENCES players(id) ON DELETE CASCADE
+);
@@ -1,3 +1,4 @@
+pub mod agent;
 pub mod component;
 pub mod config;
 pub mod engagement;
@@ -10,12 +11,14 @@ pub mod map;
 pub mod messaging;
 pub mod player;
 
+pub use component::ActiveEffect;
 pub use component::Attribute;
 pub use component::AttributeCategory;
 pub use component::AttributeDefinition;
 pub use component::AttributeType;
 pub use component::Check;
 pub use component::CheckedDescription;
+pub use component::Comparison;
 pub use component::Description;
 pub use component::Direction;
 pub use component::Effect;
@@ -24,8 +27,12 @@ pub use component::EffectType;
 pub use component::Interaction;
 pub use component::Location;
 pub use component::Movement;
+pub use component::Regeneration;
+pub use component::SkillCheck;
 pub use component::TriggerInfo;
+pub use config::AgentConfig;
 pub use config::AttributeConfig;
+pub use config::BattleConfig;
 pub use config::DialogLine;
 pub use config::EntityConfig;
 pub use config::EntityTypeConfig;
@@ -47,6 +54,8 @@ pub use engagement::TurnAction;
 pub use engagement::TurnOrder;
 pub use entity::Entity;
 pub use entity::EntityType;
+pub use entity_ai::AgentConversationContext;
+pub use entity_ai::AgentConversationState;
 pub use entity_ai::ConversationContext;
 pub use entity_ai::EntityAI;
 pub use entity_ai::SimpleConversationState;
@@ -0,0 +1,14 @@
+pub mod provider;
+pub mod rig_provider;
+
+use std::sync::Arc;
+
+use crate::game::config::AgentConfig;
+
+pub use provider::{AgentError, AgentProvider, ChatMessage, ChatRole, ReplyStream};
+pub use rig_provider::RigAgentProvider;
+
+/// Build the agent provider described by the mud config.
+pub fn build_provider(config: &AgentConfig) -> Result<Arc<dyn AgentProvider>, AgentError> {
+    Ok(Arc::new(RigAgentProvider::new(config)?))
+}
@@ -0,0 +1,52 @@
+use std::pin::Pin;
+
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AgentError {
+    #[error("provider error: {0}")]
+    Provider(String),
+    #[error("stream error: {0}")]
+    Stream(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::User,
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+/// Text chunks of an agent's reply, in the order they were generated.
+pub type ReplyStream = Pin<Box<dyn Stream<Item = Result<String, AgentError>> + Send>>;
+
+/// A source of agent replies. Implementations wrap an LLM provider so agent personas don't
+/// depend on any one backend.
+pub trait AgentProvider: Send + Sync {
+    /// Stream the reply to `prompt` from an agent with the given persona (system prompt) and
+    /// prior chat history.
+    fn stream_reply(&self, persona: &str, history: &[ChatMessage], prompt: &str) -> ReplyStream;
+}
@@ -0,0 +1,146 @@
+use futures_util::StreamExt;
+use rig::agent::{Agent, MultiTurnStreamItem};
+use rig::client::{CompletionClient, Nothing};
+use rig::completion::{CompletionModel, GetTokenUsage, Message};
+use rig::providers::{ollama, openai};
+use rig::streaming::{StreamedAssistantContent, StreamingChat};
+
+use crate::game::config::{AgentConfig, AgentProviderKind};
+
+use super::provider::{AgentError, AgentProvider, ChatMessage, ChatRole, ReplyStream};
+
+enum Backend {
+    OpenAi(openai::CompletionsClient),
+    Ollama(ollama::Client),
+}
+
+/// [`AgentProvider`] backed by rig-core. OpenAI uses the chat completions API so any
+/// OpenAI-compatible endpoint can be targeted through `base_url`.
+pub struct RigAgentProvider {
+    backend: Backend,
+    model: String,
+}
+
+impl RigAgentProvider {
+    pub fn new(config: &AgentConfig) -> Result<Self, AgentError> {
+        let backend = match config.provider {
+            AgentProviderKind::OpenAi => {
+                let mut builder = openai::CompletionsClient::builder().api_key(config.api_key());
+                if let Some(base_url) = &config.base_url {
+                    builder = builder.base_url(base_url);
+                }
+                Backend::OpenAi(
+                    builder
+                        .build()
+                        .map_err(|e| AgentError::Provider(e.to_string()))?,
+                )
+            }
+            AgentProviderKind::Ollama => {
+                let mut builder = ollama::Client::builder().api_key(Nothing);
+                if let Some(base_url) = &config.base_url {
+                    builder = builder.base_url(base_url);
+                }
+                Backend::Ollama(
+                    builder
+                        .build()
+                        .map_err(|e| AgentError::Provider(e.to_string()))?,
+                )
+            }
+        };
+        Ok(Self {
+            backend,
+            model: config.model.clone(),
+        })
+    }
+}
+
+impl AgentProvider for RigAgentProvider {
+    fn stream_reply(&self, persona: &str, history: &[ChatMessage], prompt: &str) -> ReplyStream {
+        let history: Vec<Message> = history.iter().map(to_rig_message).collect();
+        let prompt = prompt.to_string();
+        match &self.backend {
+            Backend::OpenAi(client) => {
+                let agent = client.agent(&self.model).preamble(persona).build();
+                stream_agent(agent, history, prompt)
+            }
+            Backend::Ollama(client) => {
+                let agent = client.agent(&self.model).preamble(persona).build();
+                stream_agent(agent, history, prompt)
+            }
+        }
+    }
+}
+
+fn stream_agent<M>(agent: Agent<M>, history: Vec<Message>, prompt: String) -> ReplyStream
+where
+    M: CompletionModel + 'static,
+    M::StreamingResponse: GetTokenUsage + Send,
+{
+    let stream =
+        futures_util::stream::once(async move { agent.stream_chat(prompt, history).await })
+            .flatten()
+            .filter_map(|item| async move {
+                match item {
+                    Ok(MultiTurnStreamItem::StreamAssistantItem(
+                        StreamedAssistantContent::Text(text),
+                    )) => Some(Ok(text.text)),
+                    Ok(_) => None,
+                    Err(e) => Some(Err(AgentError::Stream(e.to_string()))),
+                }
+            });
+    Box::pin(stream)
+}
+
+fn to_rig_message(message: &ChatMessage) -> Message {
+    match message.role {
+        ChatRole::User => Message::user(&message.content),
+        ChatRole::Assistant => Message::assistant(&message.content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::response::IntoResponse;
+    use axum::routing::post;
+
+    /// Minimal OpenAI-compatible endpoint that streams a fixed reply.
+    async fn chat_completions() -> impl IntoResponse {
+        let chunk = |content: &str| {
+            format!(
+                "data: {{\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":0,\"model\":\"stub\",\"choices\":[{{\"index\":0,\"delta\":{{\"role\":\"assistant\",\"content\":\"{content}\"}},\"finish_reason\":null}}]}}\n\n"
+            )
+        };
+        let body = format!(
+            "{}{}data: {{\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":0,\"model\":\"stub\",\"choices\":[{{\"index\":0,\"delta\":{{}},\"finish_reason\":\"stop\"}}]}}\n\ndata: [DONE]\n\n",
+            chunk("Welcome, "),
+            chunk("traveller."),
+        );
+        ([("content-type", "text/event-stream")], body)
+    }
+
+    #[tokio::test]
+    async fn streams_reply_from_openai_compatible_endpoint() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route("/v1/chat/completions", post(chat_completions));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = AgentConfig {
+            provider: AgentProviderKind::OpenAi,
+            model: "stub".to_string(),
+            base_url: Some(format!("http://{addr}/v1")),
+            api_key_env: None,
+            max_history: 20,
+        };
+        let provider = RigAgentProvider::new(&config).unwrap();
+        let history = vec![ChatMessage::user("Hi"), ChatMessage::assistant("Hello.")];
+        let chunks: Vec<String> = provider
+            .stream_reply("You are an innkeeper.", &history, "Got a room?")
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        assert_eq!(chunks.concat(), "Welcome, traveller.");
+    }
+}
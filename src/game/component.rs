@@ -6,13 +6,15 @@ pub mod effect;
 pub mod interaction;
 pub mod location;
 
-pub use attribute::Attribute;
+pub use attribute::{Attribute, AttributeModifier};
 pub use attribute_definition::AttributeCategory;
 pub use attribute_definition::AttributeDefinition;
 pub use attribute_definition::AttributeType;
-pub use check::Check;
+pub use attribute_definition::Regeneration;
+pub use check::{Check, CheckOutcome, CheckRoll, Comparison, RollMode, SkillCheck};
 pub use description::CheckedDescription;
 pub use description::Description;
+pub use effect::ActiveEffect;
 pub use effect::Effect;
 pub use effect::EffectDescription;
 pub use effect::EffectType;
@@ -20,4 +22,5 @@ pub use effect::TriggerInfo;
 pub use interaction::Direction;
 pub use interaction::Interaction;
 pub use interaction::Movement;
+pub use interaction::{CustomVerb, ExitChange};
 pub use location::Location;
@@ -1,11 +1,21 @@
 use serde::{Deserialize, Serialize};
 
+/// A bonus or penalty to an attribute from another entity, such as worn equipment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeModifier {
+    pub source_id: i64,
+    pub value: i64,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attribute {
     pub definition_id: String,
     pub min_value: i64,
     pub max_value: i64,
     pub current_value: i64,
+    /// Kept apart from `current_value` so removing a modifier always restores the base value.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<AttributeModifier>,
 }
 
 impl Attribute {
@@ -15,6 +25,70 @@ impl Attribute {
             min_value,
             max_value,
             current_value,
+            modifiers: Vec::new(),
         }
     }
+
+    /// Current value with every modifier applied, never below `min_value`.
+    pub fn effective_value(&self) -> i64 {
+        self.modifiers
+            .iter()
+            .fold(self.current_value, |value, m| value.saturating_add(m.value))
+            .max(self.min_value)
+    }
+
+    /// Replace any modifier from `source_id` with `value`.
+    pub fn set_modifier(&mut self, source_id: i64, value: i64) {
+        self.remove_modifier(source_id);
+        self.modifiers.push(AttributeModifier { source_id, value });
+    }
+
+    /// Remove the modifier from `source_id`. Returns true if there was one.
+    pub fn remove_modifier(&mut self, source_id: i64) -> bool {
+        let before = self.modifiers.len();
+        self.modifiers.retain(|m| m.source_id != source_id);
+        self.modifiers.len() != before
+    }
+
+    /// Add `delta` to the current value, clamped to `min_value..=max_value`.
+    /// Returns true if the current value changed.
+    pub fn adjust(&mut self, delta: i64) -> bool {
+        let new_value = self
+            .current_value
+            .saturating_add(delta)
+            .clamp(self.min_value, self.max_value);
+        let changed = new_value != self.current_value;
+        self.current_value = new_value;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjust_clamps_to_range() {
+        let mut attr = Attribute::new("hp".to_string(), 0, 100, 90);
+        assert!(attr.adjust(25));
+        assert_eq!(attr.current_value, 100);
+        assert!(attr.adjust(-150));
+        assert_eq!(attr.current_value, 0);
+        assert!(!attr.adjust(-1));
+    }
+
+    #[test]
+    fn modifiers_leave_base_value_alone() {
+        let mut attr = Attribute::new("strength".to_string(), 0, 20, 10);
+        attr.set_modifier(7, 2);
+        attr.set_modifier(7, 3);
+        attr.set_modifier(8, -20);
+        assert_eq!(attr.current_value, 10);
+        assert_eq!(attr.effective_value(), 0);
+        assert!(attr.remove_modifier(8));
+        assert_eq!(attr.effective_value(), 13);
+        assert!(attr.remove_modifier(7));
+        assert!(!attr.remove_modifier(7));
+        assert_eq!(attr.effective_value(), 10);
+    }
 }
@@ -10,7 +10,7 @@ pub enum AttributeCategory {
     General,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum AttributeType {
     #[serde(rename = "hp")]
     HP,
@@ -24,6 +24,13 @@ pub enum AttributeType {
     Stat,
 }
 
+/// Passive recovery for an attribute: `amount` is added every `interval_ms`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Regeneration {
+    pub amount: i64,
+    pub interval_ms: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttributeDefinition {
     pub id: String,
@@ -33,6 +40,8 @@ pub struct AttributeDefinition {
     pub max_value: i64,
     pub attribute_type: AttributeType,
     pub attribute_category: AttributeCategory,
+    #[serde(default)]
+    pub regeneration: Option<Regeneration>,
 }
 
 #[cfg(test)]
@@ -49,6 +58,7 @@ mod tests {
             max_value: 100,
             attribute_type: AttributeType::HP,
             attribute_category: AttributeCategory::Life,
+            regeneration: None,
         };
         let json = serde_json::to_string(&def).unwrap();
         let restored: AttributeDefinition = serde_json::from_str(&json).unwrap();
@@ -81,6 +91,7 @@ mod tests {
             max_value: 20,
             attribute_type: AttributeType::Stat,
             attribute_category: AttributeCategory::General,
+            regeneration: None,
         };
         let json = serde_json::to_string(&def).unwrap();
         let restored: AttributeDefinition = serde_json::from_str(&json).unwrap();
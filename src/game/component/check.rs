@@ -1,18 +1,328 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+use super::attribute::Attribute;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Comparison {
+    #[default]
+    #[serde(rename = ">=")]
+    AtLeast,
+    #[serde(rename = "<=")]
+    AtMost,
+    #[serde(rename = "==")]
+    Equal,
+}
+
+impl Comparison {
+    pub fn compare(self, value: i64, expected: i64) -> bool {
+        match self {
+            Comparison::AtLeast => value >= expected,
+            Comparison::AtMost => value <= expected,
+            Comparison::Equal => value == expected,
+        }
+    }
+}
+
+/// A condition on one of an entity's attributes, e.g. `wisdom >= 15`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Check {
-    pub id: i64,
-    pub attribute_id: i64,
+    /// Id of the attribute definition from `attributes.toml`.
+    pub attribute_id: String,
+    #[serde(default)]
+    pub comparison: Comparison,
     pub expected_value: i64,
 }
 
 impl Check {
-    pub fn new(id: i64, attribute_id: i64, expected_value: i64) -> Self {
+    pub fn new(attribute_id: String, comparison: Comparison, expected_value: i64) -> Self {
         Self {
-            id,
             attribute_id,
+            comparison,
             expected_value,
         }
     }
+
+    /// Returns true if the attribute's effective value (including equipment modifiers) satisfies
+    /// the check. Entities without the attribute never pass.
+    pub fn passes(&self, attributes: &HashMap<String, Attribute>) -> bool {
+        attributes.get(&self.attribute_id).is_some_and(|a| {
+            self.comparison
+                .compare(a.effective_value(), self.expected_value)
+        })
+    }
+}
+
+/// Modifier a stat adds to a d20 roll: +0 at 10 and 11, one more for every two points above
+/// and one less for every two below.
+pub fn stat_modifier(value: i64) -> i64 {
+    (value - 10).div_euclid(2)
+}
+
+/// Whether a roll takes the better or worse of two d20s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollMode {
+    #[default]
+    Normal,
+    Advantage,
+    Disadvantage,
+}
+
+/// A d20 roll plus a stat modifier against a difficulty class, e.g. `strength` DC 12. A natural
+/// 20 always succeeds and a natural 1 always fails.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillCheck {
+    /// Id of the stat whose modifier is added. Entities without it roll with no modifier.
+    pub attribute_id: String,
+    pub difficulty: i64,
+    #[serde(default)]
+    pub mode: RollMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckOutcome {
+    CriticalSuccess,
+    Success,
+    Failure,
+    CriticalFailure,
+}
+
+/// The result of rolling a [`SkillCheck`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckRoll {
+    /// Every d20 thrown: one normally, two with advantage or disadvantage.
+    pub dice: Vec<i64>,
+    /// The die that counts.
+    pub natural: i64,
+    pub modifier: i64,
+    pub difficulty: i64,
+    pub outcome: CheckOutcome,
+}
+
+impl SkillCheck {
+    pub fn new(attribute_id: impl Into<String>, difficulty: i64, mode: RollMode) -> Self {
+        Self {
+            attribute_id: attribute_id.into(),
+            difficulty,
+            mode,
+        }
+    }
+
+    /// Roll the check for an entity with `attributes`.
+    pub fn roll(
+        &self,
+        attributes: &HashMap<String, Attribute>,
+        rng: &mut fastrand::Rng,
+    ) -> CheckRoll {
+        let modifier = attributes
+            .get(&self.attribute_id)
+            .map_or(0, |a| stat_modifier(a.effective_value()));
+        self.roll_with_modifier(modifier, rng)
+    }
+
+    /// Roll the check with a modifier worked out by the caller.
+    pub fn roll_with_modifier(&self, modifier: i64, rng: &mut fastrand::Rng) -> CheckRoll {
+        let dice = match self.mode {
+            RollMode::Normal => vec![rng.i64(1..=20)],
+            RollMode::Advantage | RollMode::Disadvantage => vec![rng.i64(1..=20), rng.i64(1..=20)],
+        };
+        let natural = match self.mode {
+            RollMode::Disadvantage => dice.iter().copied().min(),
+            _ => dice.iter().copied().max(),
+        }
+        .unwrap_or(1);
+        let outcome = match natural {
+            20 => CheckOutcome::CriticalSuccess,
+            1 => CheckOutcome::CriticalFailure,
+            _ if natural + modifier >= self.difficulty => CheckOutcome::Success,
+            _ => CheckOutcome::Failure,
+        };
+        CheckRoll {
+            dice,
+            natural,
+            modifier,
+            difficulty: self.difficulty,
+            outcome,
+        }
+    }
+}
+
+impl CheckRoll {
+    pub fn total(&self) -> i64 {
+        self.natural + self.modifier
+    }
+
+    pub fn succeeded(&self) -> bool {
+        matches!(
+            self.outcome,
+            CheckOutcome::CriticalSuccess | CheckOutcome::Success
+        )
+    }
+
+    pub fn is_critical(&self) -> bool {
+        matches!(
+            self.outcome,
+            CheckOutcome::CriticalSuccess | CheckOutcome::CriticalFailure
+        )
+    }
+
+    /// The roll as told to the player who made it, e.g.
+    /// `You roll 14 + 2 = 16 against 15. Success!`
+    pub fn narrate(&self) -> String {
+        let die = match self.dice.as_slice() {
+            [first, second] => format!("{} (from {first} and {second})", self.natural),
+            _ => self.natural.to_string(),
+        };
+        let sum = match self.modifier {
+            0 => String::new(),
+            m if m > 0 => format!(" + {m} = {}", self.total()),
+            m => format!(" - {} = {}", -m, self.total()),
+        };
+        let verdict = match self.outcome {
+            CheckOutcome::CriticalSuccess => "A natural 20, critical success!",
+            CheckOutcome::Success => "Success!",
+            CheckOutcome::Failure => "Failure.",
+            CheckOutcome::CriticalFailure => "A natural 1, critical failure!",
+        };
+        format!("You roll {die}{sum} against {}. {verdict}", self.difficulty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(wisdom: i64) -> HashMap<String, Attribute> {
+        HashMap::from([(
+            "wisdom".to_string(),
+            Attribute::new("wisdom".to_string(), 0, 20, wisdom),
+        )])
+    }
+
+    #[test]
+    fn passes_compares_current_value() {
+        let at_least = Check::new("wisdom".to_string(), Comparison::AtLeast, 15);
+        assert!(at_least.passes(&attributes(15)));
+        assert!(!at_least.passes(&attributes(14)));
+
+        let at_most = Check::new("wisdom".to_string(), Comparison::AtMost, 5);
+        assert!(at_most.passes(&attributes(5)));
+        assert!(!at_most.passes(&attributes(6)));
+
+        let equal = Check::new("wisdom".to_string(), Comparison::Equal, 10);
+        assert!(equal.passes(&attributes(10)));
+        assert!(!equal.passes(&attributes(11)));
+    }
+
+    #[test]
+    fn missing_attribute_fails() {
+        let check = Check::new("strength".to_string(), Comparison::AtLeast, 0);
+        assert!(!check.passes(&attributes(10)));
+    }
+
+    #[test]
+    fn stat_modifier_rounds_down() {
+        assert_eq!(stat_modifier(10), 0);
+        assert_eq!(stat_modifier(11), 0);
+        assert_eq!(stat_modifier(14), 2);
+        assert_eq!(stat_modifier(9), -1);
+        assert_eq!(stat_modifier(1), -5);
+    }
+
+    #[test]
+    fn roll_adds_stat_modifier() {
+        let check = SkillCheck::new("wisdom", 15, RollMode::Normal);
+        let roll = check.roll(&attributes(14), &mut fastrand::Rng::with_seed(7));
+        assert_eq!(roll.dice.len(), 1);
+        assert_eq!(roll.modifier, 2);
+        assert_eq!(roll.total(), roll.natural + 2);
+        if !roll.is_critical() {
+            assert_eq!(roll.succeeded(), roll.total() >= 15);
+        }
+    }
+
+    #[test]
+    fn seeded_rolls_repeat() {
+        let check = SkillCheck::new("wisdom", 10, RollMode::Normal);
+        let rolls = |seed| {
+            let mut rng = fastrand::Rng::with_seed(seed);
+            (0..10)
+                .map(|_| check.roll(&attributes(10), &mut rng).natural)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rolls(42), rolls(42));
+    }
+
+    #[test]
+    fn advantage_keeps_higher_die_and_disadvantage_lower() {
+        let mut rng = fastrand::Rng::with_seed(3);
+        for _ in 0..20 {
+            let roll =
+                SkillCheck::new("wisdom", 10, RollMode::Advantage).roll_with_modifier(0, &mut rng);
+            assert_eq!(roll.natural, roll.dice[0].max(roll.dice[1]));
+            let roll = SkillCheck::new("wisdom", 10, RollMode::Disadvantage)
+                .roll_with_modifier(0, &mut rng);
+            assert_eq!(roll.natural, roll.dice[0].min(roll.dice[1]));
+        }
+    }
+
+    #[test]
+    fn natural_twenty_and_one_ignore_difficulty() {
+        let mut rng = fastrand::Rng::with_seed(1);
+        let impossible = SkillCheck::new("wisdom", 100, RollMode::Normal);
+        let trivial = SkillCheck::new("wisdom", -100, RollMode::Normal);
+        for _ in 0..200 {
+            let roll = impossible.roll_with_modifier(0, &mut rng);
+            assert_eq!(roll.succeeded(), roll.natural == 20);
+            let roll = trivial.roll_with_modifier(0, &mut rng);
+            assert_eq!(roll.succeeded(), roll.natural != 1);
+        }
+    }
+
+    #[test]
+    fn narrate_shows_the_sum() {
+        let roll = CheckRoll {
+            dice: vec![14],
+            natural: 14,
+            modifier: 2,
+            difficulty: 15,
+            outcome: CheckOutcome::Success,
+        };
+        assert_eq!(roll.narrate(), "You roll 14 + 2 = 16 against 15. Success!");
+        let roll = CheckRoll {
+            dice: vec![3, 12],
+            natural: 3,
+            modifier: -1,
+            difficulty: 15,
+            outcome: CheckOutcome::Failure,
+        };
+        assert_eq!(
+            roll.narrate(),
+            "You roll 3 (from 3 and 12) - 1 = 2 against 15. Failure."
+        );
+    }
+
+    #[test]
+    fn parses_operator_from_toml() {
+        let check: Check = toml::from_str(
+            r#"
+attribute_id = "wisdom"
+comparison = "<="
+expected_value = 3
+"#,
+        )
+        .unwrap();
+        assert_eq!(check.comparison, Comparison::AtMost);
+
+        let check: Check = toml::from_str(
+            r#"
+attribute_id = "wisdom"
+expected_value = 3
+"#,
+        )
+        .unwrap();
+        assert_eq!(check.comparison, Comparison::AtLeast);
+    }
 }
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::attribute::Attribute;
 use super::check::Check;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,4 +31,56 @@ impl Description {
             checked: Vec::new(),
         }
     }
+
+    /// The text seen by an entity with the given attributes: the standard description
+    /// followed by every checked description whose check passes, one per line.
+    pub fn render(&self, attributes: &HashMap<String, Attribute>) -> Option<String> {
+        let lines: Vec<&str> = self
+            .standard
+            .as_deref()
+            .into_iter()
+            .chain(
+                self.checked
+                    .iter()
+                    .filter(|c| c.check.passes(attributes))
+                    .map(|c| c.description.as_str()),
+            )
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::Comparison;
+
+    #[test]
+    fn render_appends_passing_checks() {
+        let mut description = Description::new(Some("A dusty library.".to_string()));
+        description.checked.push(CheckedDescription::new(
+            Check::new("wisdom".to_string(), Comparison::AtLeast, 15),
+            "One of the books looks out of place.".to_string(),
+        ));
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            "wisdom".to_string(),
+            Attribute::new("wisdom".to_string(), 0, 20, 10),
+        );
+        assert_eq!(
+            description.render(&attributes).as_deref(),
+            Some("A dusty library.")
+        );
+
+        attributes.get_mut("wisdom").unwrap().current_value = 16;
+        assert_eq!(
+            description.render(&attributes).as_deref(),
+            Some("A dusty library.\nOne of the books looks out of place.")
+        );
+    }
+
+    #[test]
+    fn render_empty_description_is_none() {
+        assert!(Description::default().render(&HashMap::new()).is_none());
+    }
 }
@@ -1,4 +1,5 @@
 use crate::game::component::location::Location;
+use crate::game::map::Navigation;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -12,6 +13,31 @@ pub enum TriggerInfo {
     Once,
 }
 
+impl TriggerInfo {
+    /// Returns true if an effect that has been active for `elapsed` ticks should be applied
+    /// on this tick. `OverTime` effects fire every `rate` ticks starting at `start` and
+    /// stop firing once `end` has passed.
+    pub fn fires_at(&self, elapsed: u64) -> bool {
+        match self {
+            TriggerInfo::Once => elapsed == 0,
+            TriggerInfo::OverTime { start, end, rate } => {
+                elapsed >= *start
+                    && end.is_none_or(|end| elapsed <= end)
+                    && (elapsed - start).is_multiple_of((*rate).max(1))
+            }
+        }
+    }
+
+    /// Returns true if an effect that has been active for `elapsed` ticks is finished after
+    /// this tick is processed. `OverTime` effects without an `end` never expire.
+    pub fn expires_at(&self, elapsed: u64) -> bool {
+        match self {
+            TriggerInfo::Once => true,
+            TriggerInfo::OverTime { end, .. } => end.is_some_and(|end| elapsed >= end),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum EffectType {
@@ -19,9 +45,22 @@ pub enum EffectType {
         attribute_id: String,
         value: i64,
     },
+    /// Create an entity from the `entity_id` config at `location`, or where the effect's entity
+    /// is. With a lifetime the new entity fades away once it has passed.
     EntitySpawn {
         entity_id: String,
         location: Option<Location>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lifetime_ms: Option<u64>,
+    },
+    /// Move the entity to another room. Missing world and dungeon ids keep the current ones.
+    Warp {
+        destination: Navigation,
+    },
+    /// Award experience, e.g. for finishing a quest. Counts toward levelling like XP from
+    /// battles and dialog.
+    Experience {
+        amount: i64,
     },
 }
 
@@ -40,6 +79,15 @@ pub struct Effect {
     pub description: EffectDescription,
 }
 
+/// An [`Effect`] attached to an entity at runtime. `id` is the `entity_effects` row and
+/// `elapsed_ticks` counts game ticks since the effect was attached.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActiveEffect {
+    pub id: i64,
+    pub effect: Effect,
+    pub elapsed_ticks: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,6 +122,7 @@ mod tests {
             effect_type: EffectType::EntitySpawn {
                 entity_id: "goblin".to_string(),
                 location: None,
+                lifetime_ms: Some(60_000),
             },
             trigger_info: TriggerInfo::Once,
             description: EffectDescription::default(),
@@ -82,4 +131,59 @@ mod tests {
         let restored: Effect = serde_json::from_str(&json).unwrap();
         assert_eq!(effect, restored);
     }
+
+    #[test]
+    fn warp_parses_from_toml() {
+        let effect: Effect = toml::from_str(
+            r#"
+name = "recall"
+effect_type = { type = "warp", destination = { dungeon_id = "town", room_id = "square" } }
+trigger_info = { type = "once" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            effect.effect_type,
+            EffectType::Warp {
+                destination: Navigation {
+                    world_id: None,
+                    dungeon_id: Some("town".to_string()),
+                    room_id: Some("square".to_string()),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn once_fires_only_on_first_tick_and_expires() {
+        let trigger = TriggerInfo::Once;
+        assert!(trigger.fires_at(0));
+        assert!(!trigger.fires_at(1));
+        assert!(trigger.expires_at(0));
+    }
+
+    #[test]
+    fn over_time_fires_at_rate_between_start_and_end() {
+        let trigger = TriggerInfo::OverTime {
+            start: 2,
+            end: Some(8),
+            rate: 3,
+        };
+        let fired: Vec<u64> = (0..12).filter(|t| trigger.fires_at(*t)).collect();
+        assert_eq!(fired, vec![2, 5, 8]);
+        assert!(!trigger.expires_at(7));
+        assert!(trigger.expires_at(8));
+    }
+
+    #[test]
+    fn over_time_without_end_never_expires() {
+        let trigger = TriggerInfo::OverTime {
+            start: 0,
+            end: None,
+            rate: 0,
+        };
+        assert!(trigger.fires_at(0));
+        assert!(trigger.fires_at(1000));
+        assert!(!trigger.expires_at(1000));
+    }
 }
@@ -1,8 +1,10 @@
 pub mod direction;
 pub mod movement;
+pub mod verb;
 
 pub use direction::Direction;
 pub use movement::Movement;
+pub use verb::{CustomVerb, ExitChange};
 
 use serde::{Deserialize, Serialize};
 
@@ -16,4 +18,59 @@ pub enum Interaction {
     Movement(Movement),
     EngagementAction(TurnAction),
     StartConversation,
+    Attack(String),
+    Flee,
+    Say(String),
+    Emote(String),
+    Whisper {
+        target: String,
+        content: String,
+    },
+    Shout(String),
+    ChannelPost {
+        channel: String,
+        content: String,
+    },
+    ChannelJoin(String),
+    ChannelLeave(String),
+    Channels,
+    Tell {
+        target: String,
+        content: String,
+    },
+    Reply(String),
+    Mail {
+        target: String,
+        content: String,
+    },
+    ReadMail,
+    Get(String),
+    Drop(String),
+    Give {
+        item: String,
+        target: String,
+    },
+    Inventory,
+    Wear(String),
+    Remove(String),
+    Equipment,
+    /// Show the player's attributes and active effects.
+    Stats,
+    /// Look for hidden entities in the room.
+    Search,
+    /// Use a carried item, or run `verb` on an entity that declares it, e.g. `read sign`.
+    Use {
+        verb: String,
+        item: String,
+        target: Option<String>,
+    },
+    Open(String),
+    LookIn(String),
+    GetFrom {
+        item: String,
+        container: String,
+    },
+    /// A verb an entity offers, kept in its `interactions`. Players trigger it by name with
+    /// `Use`, e.g. `pull lever`, rather than sending it themselves.
+    Verb(CustomVerb),
 }
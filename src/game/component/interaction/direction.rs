@@ -9,6 +9,56 @@ pub enum Direction {
     South,
     East,
     West,
+    Up,
+    Down,
+    Northeast,
+    Northwest,
+    Southeast,
+    Southwest,
+}
+
+impl Direction {
+    /// Parse a direction from its full name or short form (`n`, `ne`, `u`, ...).
+    pub fn parse(word: &str) -> Option<Direction> {
+        match word.to_lowercase().as_str() {
+            "n" | "north" => Some(Direction::North),
+            "s" | "south" => Some(Direction::South),
+            "e" | "east" => Some(Direction::East),
+            "w" | "west" => Some(Direction::West),
+            "u" | "up" => Some(Direction::Up),
+            "d" | "down" => Some(Direction::Down),
+            "ne" | "northeast" => Some(Direction::Northeast),
+            "nw" | "northwest" => Some(Direction::Northwest),
+            "se" | "southeast" => Some(Direction::Southeast),
+            "sw" | "southwest" => Some(Direction::Southwest),
+            _ => None,
+        }
+    }
+
+    /// Where someone who moved in this direction appears to arrive from, e.g. moving north
+    /// arrives "from the south".
+    pub fn arrival(&self) -> String {
+        match self {
+            Direction::Up => "from below".to_string(),
+            Direction::Down => "from above".to_string(),
+            other => format!("from the {}", other.opposite()),
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Northeast => Direction::Southwest,
+            Direction::Northwest => Direction::Southeast,
+            Direction::Southeast => Direction::Northwest,
+            Direction::Southwest => Direction::Northeast,
+        }
+    }
 }
 
 impl fmt::Display for Direction {
@@ -18,6 +68,32 @@ impl fmt::Display for Direction {
             Direction::South => write!(f, "south"),
             Direction::East => write!(f, "east"),
             Direction::West => write!(f, "west"),
+            Direction::Up => write!(f, "up"),
+            Direction::Down => write!(f, "down"),
+            Direction::Northeast => write!(f, "northeast"),
+            Direction::Northwest => write!(f, "northwest"),
+            Direction::Southeast => write!(f, "southeast"),
+            Direction::Southwest => write!(f, "southwest"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_short_and_long_forms() {
+        assert_eq!(Direction::parse("NE"), Some(Direction::Northeast));
+        assert_eq!(Direction::parse("down"), Some(Direction::Down));
+        assert_eq!(Direction::parse("u"), Some(Direction::Up));
+        assert_eq!(Direction::parse("tavern"), None);
+    }
+
+    #[test]
+    fn arrival_uses_opposite_direction() {
+        assert_eq!(Direction::North.arrival(), "from the south");
+        assert_eq!(Direction::Southeast.arrival(), "from the northwest");
+        assert_eq!(Direction::Up.arrival(), "from below");
+    }
+}
@@ -8,4 +8,6 @@ use crate::game::map::Navigation;
 pub enum Movement {
     TryDirection(Direction),
     Warp(Navigation),
+    /// Go into something by name, e.g. a portal in the room.
+    Enter(String),
 }
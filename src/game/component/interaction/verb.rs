@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::component::check::{Check, SkillCheck};
+use crate::game::component::effect::Effect;
+use crate::game::map::Navigation;
+
+/// Opens, moves or closes an exit of the room the entity is in. Leaving out `to` closes it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExitChange {
+    /// A direction such as `east`, or the name of a named exit.
+    pub exit: String,
+    #[serde(default)]
+    pub to: Option<Navigation>,
+}
+
+/// A custom verb players can use on the entity, e.g. `pull lever`. When the check passes the
+/// player sees `response`, the room sees `room_response` (with `{actor}` replaced by the
+/// player's name), `effects` are attached to the player and `exits` are changed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomVerb {
+    pub verb: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub response: String,
+    #[serde(default)]
+    pub room_response: Option<String>,
+    /// Condition on the player's attributes. `failure` is shown when it doesn't pass.
+    #[serde(default)]
+    pub check: Option<Check>,
+    /// Dice roll the player has to pass after `check`, e.g. strength against 12. `failure` is
+    /// shown when it doesn't.
+    #[serde(default)]
+    pub roll: Option<SkillCheck>,
+    #[serde(default)]
+    pub failure: Option<String>,
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    #[serde(default)]
+    pub exits: Vec<ExitChange>,
+}
+
+impl CustomVerb {
+    pub fn matches(&self, verb: &str) -> bool {
+        self.verb.eq_ignore_ascii_case(verb)
+            || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(verb))
+    }
+}
@@ -1,16 +1,27 @@
+pub mod agent_config;
 pub mod attribute_config;
+pub mod battle_config;
+pub mod channel_config;
 mod dialog_parser;
 pub mod entity_config;
 pub mod game_loop_config;
+pub mod loot_config;
 pub mod map_config;
 pub mod map_loader;
 pub mod mud_config;
+pub mod player_config;
 
-pub use attribute_config::AttributeConfig;
+pub use agent_config::{AgentConfig, AgentProviderKind};
+pub use attribute_config::{AttributeConfig, LevelIncrease, LevellingConfig};
+pub use battle_config::{BattleConfig, HitRoll};
+pub use channel_config::{ChannelConfig, find_channel};
 pub use entity_config::{
-    DialogLine, EntityConfig, EntityTypeConfig, PersonaConfig, PlayerResponse, load_entity_configs,
+    ConsumableConfig, DialogLine, EntityConfig, EntityTypeConfig, EquipmentConfig,
+    EquipmentModifier, PersonaConfig, PlayerResponse, RespawnConfig, load_entity_configs,
 };
 pub use game_loop_config::GameLoopConfig;
+pub use loot_config::{LootEntry, LootTable, load_loot_tables};
 pub use map_config::load_map;
 pub use map_loader::{load_entities_into_db, load_map_into_db, should_auto_load};
-pub use mud_config::{MudConfig, SpawnConfig};
+pub use mud_config::{MudConfig, SpawnConfig, StatusConfig};
+pub use player_config::{PlayerAttribute, PlayerTemplate};
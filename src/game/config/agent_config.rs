@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentProviderKind {
+    /// Any OpenAI compatible chat completions endpoint.
+    #[serde(rename = "openai")]
+    OpenAi,
+    Ollama,
+}
+
+fn default_max_history() -> usize {
+    20
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentConfig {
+    pub provider: AgentProviderKind,
+    pub model: String,
+    /// Overrides the provider's default endpoint, e.g. `http://localhost:8080/v1`.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Environment variable holding the provider's API key.
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// Maximum number of chat messages kept per conversation.
+    #[serde(default = "default_max_history")]
+    pub max_history: usize,
+}
+
+impl AgentConfig {
+    /// Read the API key from `api_key_env`. Local endpoints often don't need one, so a missing
+    /// variable yields an empty key.
+    pub fn api_key(&self) -> String {
+        self.api_key_env
+            .as_deref()
+            .and_then(|name| std::env::var(name).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_openai_compatible_config() {
+        let toml = r#"
+provider = "openai"
+model = "gpt-4o-mini"
+base_url = "http://localhost:8080/v1"
+"#;
+        let config: AgentConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.provider, AgentProviderKind::OpenAi);
+        assert_eq!(config.model, "gpt-4o-mini");
+        assert_eq!(config.base_url.as_deref(), Some("http://localhost:8080/v1"));
+        assert_eq!(config.max_history, 20);
+        assert_eq!(config.api_key(), "");
+    }
+
+    #[test]
+    fn parses_ollama_config() {
+        let toml = r#"
+provider = "ollama"
+model = "llama3"
+max_history = 6
+"#;
+        let config: AgentConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.provider, AgentProviderKind::Ollama);
+        assert_eq!(config.max_history, 6);
+        assert!(config.base_url.is_none());
+    }
+}
@@ -3,11 +3,61 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 use crate::game::component::AttributeDefinition;
-use crate::game::component::attribute_definition::{AttributeCategory, AttributeType};
+use crate::game::component::attribute_definition::{
+    AttributeCategory, AttributeType, Regeneration,
+};
+
+/// What gaining a level does to one attribute: `value` is added to its current value and
+/// `max_value` to its maximum, e.g. one more strength or ten more maximum hit points.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelIncrease {
+    pub attribute_id: String,
+    #[serde(default)]
+    pub value: i64,
+    #[serde(default)]
+    pub max_value: i64,
+}
+
+/// The XP curve. `thresholds` holds the total XP needed for each level after the first, so
+/// `[100, 300]` means level 2 at 100 XP and level 3 at 300. Every level gained applies
+/// `increases` once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevellingConfig {
+    pub thresholds: Vec<i64>,
+    #[serde(default)]
+    pub increases: Vec<LevelIncrease>,
+}
+
+impl LevellingConfig {
+    pub fn default_config() -> Self {
+        let increase = |id: &str, value: i64, max_value: i64| LevelIncrease {
+            attribute_id: id.to_string(),
+            value,
+            max_value,
+        };
+        Self {
+            thresholds: vec![100, 300, 600, 1_000, 1_500, 2_100, 2_800, 3_600, 4_500],
+            increases: vec![
+                increase("hp", 10, 10),
+                increase("mp", 5, 5),
+                increase("strength", 1, 0),
+                increase("constitution", 1, 0),
+            ],
+        }
+    }
+
+    /// The level `xp` total XP is worth.
+    pub fn level_for(&self, xp: i64) -> i64 {
+        1 + self.thresholds.iter().filter(|&&t| xp >= t).count() as i64
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttributeConfig {
     pub attributes: Vec<AttributeDefinition>,
+    /// How XP turns into levels. Without it players never level up.
+    #[serde(default)]
+    pub levelling: Option<LevellingConfig>,
 }
 
 impl AttributeConfig {
@@ -17,6 +67,17 @@ impl AttributeConfig {
         Ok(config)
     }
 
+    pub fn find(&self, id: &str) -> Option<&AttributeDefinition> {
+        self.attributes.iter().find(|a| a.id == id)
+    }
+
+    /// The first attribute of the given type, e.g. the one tracking XP.
+    pub fn find_type(&self, attribute_type: AttributeType) -> Option<&AttributeDefinition> {
+        self.attributes
+            .iter()
+            .find(|a| a.attribute_type == attribute_type)
+    }
+
     pub fn default_config() -> Self {
         Self {
             attributes: vec![
@@ -28,6 +89,10 @@ impl AttributeConfig {
                     max_value: 999,
                     attribute_type: AttributeType::HP,
                     attribute_category: AttributeCategory::Life,
+                    regeneration: Some(Regeneration {
+                        amount: 1,
+                        interval_ms: 5_000,
+                    }),
                 },
                 AttributeDefinition {
                     id: "mp".to_string(),
@@ -38,6 +103,10 @@ impl AttributeConfig {
                     max_value: 999,
                     attribute_type: AttributeType::MP,
                     attribute_category: AttributeCategory::General,
+                    regeneration: Some(Regeneration {
+                        amount: 1,
+                        interval_ms: 10_000,
+                    }),
                 },
                 AttributeDefinition {
                     id: "level".to_string(),
@@ -47,6 +116,7 @@ impl AttributeConfig {
                     max_value: 100,
                     attribute_type: AttributeType::Level,
                     attribute_category: AttributeCategory::General,
+                    regeneration: None,
                 },
                 AttributeDefinition {
                     id: "xp".to_string(),
@@ -56,6 +126,7 @@ impl AttributeConfig {
                     max_value: i64::MAX,
                     attribute_type: AttributeType::XP,
                     attribute_category: AttributeCategory::General,
+                    regeneration: None,
                 },
                 AttributeDefinition {
                     id: "strength".to_string(),
@@ -65,6 +136,7 @@ impl AttributeConfig {
                     max_value: 20,
                     attribute_type: AttributeType::Stat,
                     attribute_category: AttributeCategory::General,
+                    regeneration: None,
                 },
                 AttributeDefinition {
                     id: "dexterity".to_string(),
@@ -74,6 +146,7 @@ impl AttributeConfig {
                     max_value: 20,
                     attribute_type: AttributeType::Stat,
                     attribute_category: AttributeCategory::General,
+                    regeneration: None,
                 },
                 AttributeDefinition {
                     id: "constitution".to_string(),
@@ -83,6 +156,7 @@ impl AttributeConfig {
                     max_value: 20,
                     attribute_type: AttributeType::Stat,
                     attribute_category: AttributeCategory::General,
+                    regeneration: None,
                 },
                 AttributeDefinition {
                     id: "intelligence".to_string(),
@@ -92,6 +166,7 @@ impl AttributeConfig {
                     max_value: 20,
                     attribute_type: AttributeType::Stat,
                     attribute_category: AttributeCategory::General,
+                    regeneration: None,
                 },
                 AttributeDefinition {
                     id: "wisdom".to_string(),
@@ -101,6 +176,7 @@ impl AttributeConfig {
                     max_value: 20,
                     attribute_type: AttributeType::Stat,
                     attribute_category: AttributeCategory::General,
+                    regeneration: None,
                 },
                 AttributeDefinition {
                     id: "charisma".to_string(),
@@ -111,8 +187,10 @@ impl AttributeConfig {
                     max_value: 20,
                     attribute_type: AttributeType::Stat,
                     attribute_category: AttributeCategory::General,
+                    regeneration: None,
                 },
             ],
+            levelling: Some(LevellingConfig::default_config()),
         }
     }
 }
@@ -168,4 +246,53 @@ attribute_category = "general"
         assert_eq!(config.attributes[0].id, "test_hp");
         assert_eq!(config.attributes[1].id, "test_stat");
     }
+
+    #[test]
+    fn load_parses_regeneration() {
+        let toml = r#"
+[[attributes]]
+id = "hp"
+title = "HP"
+description = "Hit points."
+min_value = 0
+max_value = 100
+attribute_type = "hp"
+attribute_category = "life"
+
+[attributes.regeneration]
+amount = 2
+interval_ms = 3000
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml.as_bytes()).unwrap();
+        let config = AttributeConfig::load(file.path()).unwrap();
+        let hp = config.find("hp").unwrap();
+        assert_eq!(
+            hp.regeneration,
+            Some(Regeneration {
+                amount: 2,
+                interval_ms: 3000,
+            })
+        );
+        assert!(config.find("missing").is_none());
+        assert!(config.levelling.is_none());
+    }
+
+    #[test]
+    fn load_parses_levelling() {
+        let toml = r#"
+attributes = []
+
+[levelling]
+thresholds = [100, 300]
+increases = [{ attribute_id = "hp", value = 10, max_value = 10 }]
+"#;
+        let config: AttributeConfig = toml::from_str(toml).unwrap();
+        let levelling = config.levelling.unwrap();
+        assert_eq!(levelling.increases[0].max_value, 10);
+        assert_eq!(levelling.level_for(0), 1);
+        assert_eq!(levelling.level_for(100), 2);
+        assert_eq!(levelling.level_for(299), 2);
+        assert_eq!(levelling.level_for(5_000), 3);
+    }
 }
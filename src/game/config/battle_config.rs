@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::component::effect::{Effect, EffectDescription, EffectType, TriggerInfo};
+use crate::game::component::{RollMode, SkillCheck};
+
+fn default_attack_effect() -> String {
+    "attack".to_string()
+}
+
+/// A d20 roll made before every battle effect lands. The attacker adds their `attribute_id`
+/// modifier and has to reach `difficulty` plus the target's `defense_attribute_id` modifier.
+/// A critical hit doubles an immediate attribute update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HitRoll {
+    pub attribute_id: String,
+    #[serde(default)]
+    pub defense_attribute_id: Option<String>,
+    pub difficulty: i64,
+}
+
+impl HitRoll {
+    /// The check an attacker has to pass against a target whose defense modifier is
+    /// `defense_modifier`.
+    pub fn check(&self, defense_modifier: i64) -> SkillCheck {
+        SkillCheck::new(
+            self.attribute_id.clone(),
+            self.difficulty + defense_modifier,
+            RollMode::Normal,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleConfig {
+    /// Name of the effect used by the `attack` command.
+    #[serde(default = "default_attack_effect")]
+    pub attack_effect: String,
+    /// Name of the effect applied when a combatant's turn times out. `None` skips the turn.
+    #[serde(default)]
+    pub default_action: Option<String>,
+    /// Effects that can be used in battle, referenced by name from `TurnAction::ApplyEffect`.
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    /// Roll to hit before effects land. Without it every effect hits.
+    #[serde(default)]
+    pub hit_roll: Option<HitRoll>,
+}
+
+impl BattleConfig {
+    pub fn default_config() -> Self {
+        Self {
+            attack_effect: default_attack_effect(),
+            default_action: Some(default_attack_effect()),
+            effects: vec![Effect {
+                name: default_attack_effect(),
+                effect_type: EffectType::AttributeUpdate {
+                    attribute_id: "hp".to_string(),
+                    value: -5,
+                },
+                trigger_info: TriggerInfo::Once,
+                description: EffectDescription::default(),
+            }],
+            hit_roll: None,
+        }
+    }
+
+    pub fn find_effect(&self, name: &str) -> Option<&Effect> {
+        self.effects.iter().find(|e| e.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_has_attack_effect() {
+        let config = BattleConfig::default_config();
+        assert_eq!(config.attack_effect, "attack");
+        assert_eq!(config.default_action.as_deref(), Some("attack"));
+        assert!(config.find_effect("attack").is_some());
+        assert!(config.find_effect("fireball").is_none());
+    }
+
+    #[test]
+    fn parses_effects_from_toml() {
+        let toml = r#"
+default_action = "punch"
+
+[[effects]]
+name = "punch"
+effect_type = { type = "attribute_update", attribute_id = "hp", value = -2 }
+trigger_info = { type = "once" }
+"#;
+        let config: BattleConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.attack_effect, "attack");
+        assert_eq!(config.default_action.as_deref(), Some("punch"));
+        assert_eq!(config.effects.len(), 1);
+        assert_eq!(config.effects[0].name, "punch");
+        assert!(config.hit_roll.is_none());
+    }
+
+    #[test]
+    fn hit_roll_adds_defense_to_difficulty() {
+        let toml = r#"
+hit_roll = { attribute_id = "strength", defense_attribute_id = "dexterity", difficulty = 10 }
+"#;
+        let config: BattleConfig = toml::from_str(toml).unwrap();
+        let hit_roll = config.hit_roll.unwrap();
+        assert_eq!(hit_roll.defense_attribute_id.as_deref(), Some("dexterity"));
+        let check = hit_roll.check(2);
+        assert_eq!(check.attribute_id, "strength");
+        assert_eq!(check.difficulty, 12);
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// A server-wide chat channel players can join, leave and post to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelConfig {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Whether players are subscribed until they leave the channel.
+    #[serde(default)]
+    pub auto_join: bool,
+}
+
+impl ChannelConfig {
+    pub fn default_config() -> Vec<Self> {
+        vec![
+            Self::new("ooc", "Out-of-character chat for everyone.", true),
+            Self::new("newbie", "Questions and help for new players.", true),
+            Self::new("trade", "Buying, selling and swapping.", false),
+        ]
+    }
+
+    fn new(name: &str, description: &str, auto_join: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            auto_join,
+        }
+    }
+}
+
+/// Find a channel by name, ignoring case.
+pub fn find_channel<'a>(channels: &'a [ChannelConfig], name: &str) -> Option<&'a ChannelConfig> {
+    channels
+        .iter()
+        .find(|c| c.name.eq_ignore_ascii_case(name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_channel_ignores_case() {
+        let channels = ChannelConfig::default_config();
+        assert_eq!(find_channel(&channels, "OOC").unwrap().name, "ooc");
+        assert!(find_channel(&channels, "pvp").is_none());
+    }
+}
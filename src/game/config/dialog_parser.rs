@@ -1,3 +1,4 @@
+use crate::game::component::{RollMode, SkillCheck};
 use crate::game::config::entity_config::{DialogLine, PlayerResponse};
 use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
 use std::error::Error;
@@ -10,6 +11,9 @@ use std::error::Error;
 /// - Text after `# H1` (before next heading) → NPC reply
 /// - `## H2` within an H1 section → sub-player choices
 /// - `**alt-N**` bold-only paragraphs act as alternate separators
+/// - An `**xp-N**` paragraph in an NPC reply awards N experience for choosing its response
+/// - A `**roll-STAT-DC**` paragraph (optionally ending `-advantage` or `-disadvantage`) makes
+///   its response need a dice roll; the rest of the paragraph is what the NPC says on a failure
 pub fn parse_dialog_markdown(content: &str) -> Result<DialogLine, Box<dyn Error>> {
     let parser = Parser::new(content);
     let events: Vec<Event> = parser.collect();
@@ -26,6 +30,10 @@ enum Block {
     Alt,
     /// A heading at the given depth (1 = H1, 2 = H2, …) with its text.
     Heading(u8, String),
+    /// An XP award paragraph (`**xp-N**`).
+    Xp(i64),
+    /// A roll paragraph (`**roll-STAT-DC**`) and the NPC's line when it fails.
+    Roll(SkillCheck, String),
 }
 
 /// Collect the event stream into high-level blocks.
@@ -40,7 +48,18 @@ fn collect_blocks(events: &[Event]) -> Vec<Block> {
     while i < events.len() {
         match &events[i] {
             Event::Start(Tag::Paragraph) => {
-                if is_alt_start(events, i) {
+                if let Some(check) = roll_marker(events, i) {
+                    let (failure, consumed) = read_alt_paragraph(events, i);
+                    i += consumed;
+                    blocks.push(Block::Roll(check, failure));
+                } else if let Some(xp) = xp_marker(events, i) {
+                    let (text, consumed) = read_alt_paragraph(events, i);
+                    i += consumed;
+                    blocks.push(Block::Xp(xp));
+                    if !text.is_empty() {
+                        blocks.push(Block::Text(text));
+                    }
+                } else if is_alt_start(events, i) {
                     let (alt_text, consumed) = read_alt_paragraph(events, i);
                     i += consumed;
                     blocks.push(Block::Alt);
@@ -84,6 +103,38 @@ fn is_alt_start(events: &[Event], start: usize) -> bool {
     }
 }
 
+/// Returns the amount when a paragraph starts with a bold `**xp-N**` marker.
+fn xp_marker(events: &[Event], start: usize) -> Option<i64> {
+    if !matches!(events.get(start + 1), Some(Event::Start(Tag::Strong))) {
+        return None;
+    }
+    match events.get(start + 2) {
+        Some(Event::Text(t)) => t.strip_prefix("xp-")?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Returns the check when a paragraph starts with a bold `**roll-STAT-DC**` marker, e.g.
+/// `**roll-charisma-12-advantage**`.
+fn roll_marker(events: &[Event], start: usize) -> Option<SkillCheck> {
+    if !matches!(events.get(start + 1), Some(Event::Start(Tag::Strong))) {
+        return None;
+    }
+    let Some(Event::Text(t)) = events.get(start + 2) else {
+        return None;
+    };
+    let mut parts = t.strip_prefix("roll-")?.trim().split('-');
+    let attribute_id = parts.next().filter(|id| !id.is_empty())?;
+    let difficulty = parts.next()?.parse().ok()?;
+    let mode = match parts.next() {
+        None => RollMode::Normal,
+        Some("advantage") => RollMode::Advantage,
+        Some("disadvantage") => RollMode::Disadvantage,
+        Some(_) => return None,
+    };
+    Some(SkillCheck::new(attribute_id, difficulty, mode))
+}
+
 /// Read all inline events inside a paragraph, returning the joined text and
 /// how many events were consumed (including the opening/closing tags).
 fn read_paragraph(events: &[Event], start: usize) -> (String, usize) {
@@ -240,6 +291,8 @@ fn build_dialog_from_blocks(
             .iter()
             .position(|b| matches!(b, Block::Heading(d, _) if *d == sub_depth));
 
+        let mut xp = 0;
+        let mut roll = None;
         let reply = if body_blocks.is_empty() {
             None
         } else {
@@ -249,6 +302,17 @@ fn build_dialog_from_blocks(
                 (&body_blocks[..], &[][..])
             };
 
+            xp = npc_prefix
+                .iter()
+                .map(|b| match b {
+                    Block::Xp(amount) => *amount,
+                    _ => 0,
+                })
+                .sum();
+            roll = npc_prefix.iter().find_map(|b| match b {
+                Block::Roll(check, failure) => Some((check.clone(), failure.clone())),
+                _ => None,
+            });
             let npc_text = build_text_with_alts(npc_prefix);
             let owned_sub: Vec<Block> = sub_blocks.iter().map(|b| (*b).clone()).collect();
             let mut sub_dialog = build_dialog_from_blocks(&owned_sub, sub_depth)?;
@@ -270,9 +334,16 @@ fn build_dialog_from_blocks(
             }
         };
 
+        let (roll, roll_failure) = match roll {
+            Some((check, failure)) => (Some(check), Some(failure).filter(|f| !f.is_empty())),
+            None => (None, None),
+        };
         responses.push(PlayerResponse {
             text: choice_text,
             reply,
+            xp,
+            roll,
+            roll_failure,
         });
     }
 
@@ -307,7 +378,7 @@ fn build_text_with_alts(prefix: &[&Block]) -> TextWithAlts {
                 groups.push(current.clone());
                 current = Vec::new();
             }
-            Block::Heading(_, _) => {} // shouldn't appear here, skip
+            Block::Heading(_, _) | Block::Xp(_) | Block::Roll(_, _) => {} // not part of the text
         }
     }
     if !current.is_empty() || groups.is_empty() {
@@ -388,6 +459,51 @@ That'll be 5 gold.
         assert!(reply.responses.is_empty());
     }
 
+    #[test]
+    fn xp_marker_awards_its_response() {
+        let md = "\
+Welcome!
+
+# I found your ring.
+
+**xp-50**
+Thank you, friend!
+
+# Nothing.
+";
+        let dialog = parse_dialog_markdown(md).unwrap();
+        assert_eq!(dialog.responses[0].xp, 50);
+        let reply = dialog.responses[0].reply.as_ref().unwrap();
+        assert_eq!(reply.text, "Thank you, friend!");
+        assert!(reply.alts.is_empty());
+        assert_eq!(dialog.responses[1].xp, 0);
+    }
+
+    #[test]
+    fn roll_marker_adds_check_and_failure_line() {
+        let md = "\
+What'll it be?
+
+# Knock a few coins off?
+
+**roll-charisma-12-advantage**
+Nice try.
+
+Fine, three gold then.
+";
+        let dialog = parse_dialog_markdown(md).unwrap();
+        let response = &dialog.responses[0];
+        assert_eq!(
+            response.roll,
+            Some(SkillCheck::new("charisma", 12, RollMode::Advantage))
+        );
+        assert_eq!(response.roll_failure.as_deref(), Some("Nice try."));
+        assert_eq!(
+            response.reply.as_ref().unwrap().text,
+            "Fine, three gold then."
+        );
+    }
+
     #[test]
     fn multiple_top_level_choices() {
         let md = "\
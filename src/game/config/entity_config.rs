@@ -1,5 +1,9 @@
+use crate::game::component::attribute::Attribute;
+use crate::game::component::check::SkillCheck;
 use crate::game::component::effect::Effect;
+use crate::game::component::interaction::{CustomVerb, Interaction};
 use crate::game::config::dialog_parser::parse_dialog_markdown;
+use crate::game::map::Navigation;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error;
@@ -22,6 +26,9 @@ pub enum PersonaConfig {
     Agent {
         #[serde(default = "default_agent_type")]
         agent_type: String,
+        /// System prompt for the agent. Loaded from `persona_file` when that is set.
+        #[serde(default)]
+        persona: Option<String>,
         persona_file: Option<String>,
     },
     Standard {
@@ -45,6 +52,15 @@ pub struct DialogLine {
 pub struct PlayerResponse {
     pub text: String,
     pub reply: Option<Box<DialogLine>>,
+    /// Experience the player gains for choosing this response.
+    #[serde(default)]
+    pub xp: i64,
+    /// Dice roll the player has to pass for this response to work. On a failure the NPC says
+    /// `roll_failure` and the same options are offered again, but this one stays locked.
+    #[serde(default)]
+    pub roll: Option<SkillCheck>,
+    #[serde(default)]
+    pub roll_failure: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +71,66 @@ pub struct StartingAttribute {
     pub current_value: i64,
 }
 
+/// A change to the wearer's attribute while an item is worn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquipmentModifier {
+    pub attribute_id: String,
+    pub value: i64,
+}
+
+/// Makes an object wearable or wieldable in a slot such as `head` or `main_hand`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquipmentConfig {
+    pub slot: String,
+    #[serde(default)]
+    pub modifiers: Vec<EquipmentModifier>,
+}
+
+fn default_charges() -> i64 {
+    1
+}
+
+/// Makes an object usable with `use`, applying its effects to the user or a target. The object
+/// is used up once its charges run out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsumableConfig {
+    #[serde(default = "default_charges")]
+    pub charges: i64,
+    pub on_use: Vec<Effect>,
+}
+
+/// How a config entity goes home on world updates after it has wandered off, been killed or
+/// been looted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RespawnConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How long the entity stays disturbed before it resets.
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// Restore the starting attributes, bringing dead characters back to life.
+    #[serde(default = "default_true")]
+    pub reset_attributes: bool,
+    /// Wait until no players are in the room the entity is in or returns to.
+    #[serde(default = "default_true")]
+    pub skip_occupied: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl RespawnConfig {
+    pub fn default_config() -> Self {
+        Self {
+            enabled: true,
+            delay_ms: 0,
+            reset_attributes: true,
+            skip_occupied: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntityConfig {
     pub id: Option<String>,
@@ -66,19 +142,81 @@ pub struct EntityConfig {
     pub attributes: Vec<StartingAttribute>,
     #[serde(default)]
     pub entity_effects: Vec<Effect>,
+    /// Names of battle effects this entity picks from on its turn.
+    #[serde(default)]
+    pub battle_actions: Vec<String>,
+    /// Makes the entity a portal: entering it warps to this destination.
+    #[serde(default)]
+    pub portal: Option<Navigation>,
+    /// Lets the object be worn with `wear`, applying its modifiers to the wearer.
+    #[serde(default)]
+    pub equipment: Option<EquipmentConfig>,
+    /// Lets the object be used, drunk or read for its effects.
+    #[serde(default)]
+    pub consumable: Option<ConsumableConfig>,
+    /// Lets the object hold other entities that can be taken out with `get <item> from`.
+    #[serde(default)]
+    pub container: bool,
+    /// Loot table rolled into a container when it's first opened, or into a character's corpse
+    /// when it dies.
+    #[serde(default)]
+    pub loot: Option<String>,
+    /// Experience shared by the players who defeat this character in battle.
+    #[serde(default)]
+    pub xp: i64,
+    /// Keeps the entity out of sight until a player passes this check with `search`.
+    #[serde(default)]
+    pub hidden: Option<SkillCheck>,
+    /// Custom verbs players can use on the entity. They become the entity's interactions.
+    #[serde(default)]
+    pub verbs: Vec<CustomVerb>,
+    #[serde(default = "RespawnConfig::default_config")]
+    pub respawn: RespawnConfig,
+}
+
+impl EntityConfig {
+    /// The interactions entities made from this config offer players.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.verbs.iter().cloned().map(Interaction::Verb).collect()
+    }
+
+    /// The entity's attributes as configured, before anything has happened to it.
+    pub fn starting_attributes(&self) -> HashMap<String, Attribute> {
+        self.attributes
+            .iter()
+            .map(|sa| {
+                let attribute = Attribute::new(
+                    sa.definition_id.clone(),
+                    sa.min_value,
+                    sa.max_value,
+                    sa.current_value,
+                );
+                (sa.definition_id.clone(), attribute)
+            })
+            .collect()
+    }
 }
 
 pub fn load_entity_config(path: &Path) -> Result<EntityConfig, Box<dyn Error>> {
     let content = std::fs::read_to_string(path)?;
     let mut config: EntityConfig = toml::from_str(&content)?;
-    if let Some(PersonaConfig::Standard {
-        dialog_file: Some(ref dialog_path),
-        ref mut dialog_tree,
-    }) = config.persona
-    {
-        let md_path = path.parent().unwrap_or(Path::new(".")).join(dialog_path);
-        let md_content = std::fs::read_to_string(&md_path)?;
-        *dialog_tree = Some(parse_dialog_markdown(&md_content)?);
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    match config.persona {
+        Some(PersonaConfig::Standard {
+            dialog_file: Some(ref dialog_path),
+            ref mut dialog_tree,
+        }) => {
+            let md_content = std::fs::read_to_string(base_dir.join(dialog_path))?;
+            *dialog_tree = Some(parse_dialog_markdown(&md_content)?);
+        }
+        Some(PersonaConfig::Agent {
+            persona_file: Some(ref persona_path),
+            ref mut persona,
+            ..
+        }) => {
+            *persona = Some(std::fs::read_to_string(base_dir.join(persona_path))?);
+        }
+        _ => {}
     }
     Ok(config)
 }
@@ -147,6 +285,8 @@ current_value = 100
         let config: EntityConfig = toml::from_str(toml).unwrap();
         assert!(matches!(config.entity_type, EntityTypeConfig::Character));
         assert_eq!(config.attributes.len(), 1);
+        assert!(config.respawn.enabled);
+        assert_eq!(config.starting_attributes()["hp"].current_value, 100);
         assert_eq!(config.attributes[0].definition_id, "hp");
         if let Some(PersonaConfig::Standard {
             dialog_tree: Some(tree),
@@ -267,6 +407,35 @@ entity_type = "object"
         assert!(configs.contains_key("entities/innkeeper"));
     }
 
+    #[test]
+    fn load_entity_config_reads_persona_file() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "entities/sage.toml",
+            r#"
+entity_type = "character"
+
+[persona]
+type = "agent"
+persona_file = "sage.md"
+"#,
+        );
+        write_file(tmp.path(), "entities/sage.md", "You are a wise old sage.");
+        let config = load_entity_config(&tmp.path().join("entities/sage.toml")).unwrap();
+        if let Some(PersonaConfig::Agent {
+            agent_type,
+            persona,
+            ..
+        }) = &config.persona
+        {
+            assert_eq!(agent_type, "default");
+            assert_eq!(persona.as_deref(), Some("You are a wise old sage."));
+        } else {
+            panic!("expected Agent persona");
+        }
+    }
+
     #[test]
     fn load_entity_configs_uses_id_field_when_present() {
         let tmp = TempDir::new().unwrap();
@@ -281,4 +450,65 @@ entity_type = "character"
         let configs = load_entity_configs(tmp.path()).unwrap();
         assert!(configs.contains_key("custom_id"));
     }
+
+    #[test]
+    fn consumable_config_parses_on_use_effects() {
+        let toml = r#"
+entity_type = "object"
+
+[consumable]
+charges = 2
+
+[[consumable.on_use]]
+name = "healing_draught"
+effect_type = { type = "attribute_update", attribute_id = "hp", value = 10 }
+trigger_info = { type = "once" }
+"#;
+        let config: EntityConfig = toml::from_str(toml).unwrap();
+        let consumable = config.consumable.unwrap();
+        assert_eq!(consumable.charges, 2);
+        assert_eq!(consumable.on_use.len(), 1);
+        assert_eq!(consumable.on_use[0].name, "healing_draught");
+    }
+
+    #[test]
+    fn verbs_parse_checks_and_exit_changes() {
+        let toml = r#"
+entity_type = "object"
+
+[[verbs]]
+verb = "pull"
+aliases = ["yank"]
+response = "You pull the lever."
+room_response = "{actor} pulls the lever."
+check = { attribute_id = "strength", expected_value = 12 }
+failure = "The lever won't budge."
+exits = [{ exit = "east", to = { room_id = "vault" } }]
+"#;
+        let config: EntityConfig = toml::from_str(toml).unwrap();
+        let verb = &config.verbs[0];
+        assert!(verb.matches("YANK"));
+        assert!(!verb.matches("push"));
+        assert_eq!(verb.check.as_ref().unwrap().expected_value, 12);
+        assert_eq!(verb.exits[0].exit, "east");
+        assert_eq!(
+            verb.exits[0].to.as_ref().unwrap().room_id.as_deref(),
+            Some("vault")
+        );
+    }
+
+    #[test]
+    fn respawn_section_overrides_defaults() {
+        let toml = r#"
+entity_type = "character"
+
+[respawn]
+delay_ms = 60000
+skip_occupied = false
+"#;
+        let config: EntityConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.respawn.delay_ms, 60_000);
+        assert!(config.respawn.reset_attributes);
+        assert!(!config.respawn.skip_occupied);
+    }
 }
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+fn default_rolls() -> u32 {
+    1
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// One possible drop. Entries without an entity roll nothing, which makes empty results as
+/// likely as their weight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LootEntry {
+    #[serde(default)]
+    pub entity: Option<String>,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+/// A weighted table of entity configs, rolled `rolls` times when a container is first opened or
+/// a character with the table dies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LootTable {
+    #[serde(default = "default_rolls")]
+    pub rolls: u32,
+    #[serde(default)]
+    pub entries: Vec<LootEntry>,
+}
+
+impl LootTable {
+    /// Roll the table, returning the entity config id of each drop.
+    pub fn roll(&self) -> Vec<String> {
+        let total: u32 = self.entries.iter().map(|e| e.weight).sum();
+        if total == 0 {
+            return Vec::new();
+        }
+        (0..self.rolls)
+            .filter_map(|_| {
+                let mut pick = fastrand::u32(..total);
+                let entry = self.entries.iter().find(|e| {
+                    if pick < e.weight {
+                        true
+                    } else {
+                        pick -= e.weight;
+                        false
+                    }
+                })?;
+                entry.entity.clone()
+            })
+            .collect()
+    }
+}
+
+/// Load every `loot/*.toml` under `config_dir`, keyed by path without extension, e.g.
+/// `loot/chest`.
+pub fn load_loot_tables(config_dir: &Path) -> Result<HashMap<String, LootTable>, Box<dyn Error>> {
+    let mut tables = HashMap::new();
+    let loot_dir = config_dir.join("loot");
+    if !loot_dir.exists() {
+        return Ok(tables);
+    }
+    for entry in walkdir::WalkDir::new(&loot_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("toml"))
+    {
+        let path = entry.path();
+        let table: LootTable = toml::from_str(&std::fs::read_to_string(path)?)?;
+        let id = path.strip_prefix(config_dir)?.with_extension("");
+        tables.insert(id.to_string_lossy().to_string(), table);
+    }
+    Ok(tables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn roll_picks_from_weighted_entries() {
+        let table: LootTable = toml::from_str(
+            r#"
+rolls = 3
+
+[[entries]]
+entity = "entities/potion"
+weight = 2
+
+[[entries]]
+weight = 0
+"#,
+        )
+        .unwrap();
+        assert_eq!(table.roll(), vec!["entities/potion"; 3]);
+    }
+
+    #[test]
+    fn roll_without_weight_drops_nothing() {
+        let table = LootTable {
+            rolls: 2,
+            entries: vec![],
+        };
+        assert!(table.roll().is_empty());
+    }
+
+    #[test]
+    fn load_loot_tables_keys_by_path() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("loot")).unwrap();
+        fs::write(
+            dir.path().join("loot/chest.toml"),
+            "[[entries]]\nentity = \"entities/potion\"\n",
+        )
+        .unwrap();
+        let tables = load_loot_tables(dir.path()).unwrap();
+        assert_eq!(tables["loot/chest"].rolls, 1);
+        assert_eq!(tables["loot/chest"].entries[0].weight, 1);
+    }
+}
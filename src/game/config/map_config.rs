@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::path::Path;
 
@@ -12,6 +13,14 @@ pub struct RoomConfig {
     pub south: Option<Navigation>,
     pub east: Option<Navigation>,
     pub west: Option<Navigation>,
+    pub up: Option<Navigation>,
+    pub down: Option<Navigation>,
+    pub northeast: Option<Navigation>,
+    pub northwest: Option<Navigation>,
+    pub southeast: Option<Navigation>,
+    pub southwest: Option<Navigation>,
+    #[serde(default)]
+    pub exits: BTreeMap<String, Navigation>,
     #[serde(default)]
     pub entities: Vec<String>,
 }
@@ -94,6 +103,13 @@ pub fn load_map(config_dir: Option<&Path>) -> Result<Universe, Box<dyn Error>> {
                     south: config.south,
                     east: config.east,
                     west: config.west,
+                    up: config.up,
+                    down: config.down,
+                    northeast: config.northeast,
+                    northwest: config.northwest,
+                    southeast: config.southeast,
+                    southwest: config.southwest,
+                    exits: config.exits,
                     entities: config.entities,
                 };
                 dungeon.rooms.insert(room_name, room);
@@ -257,4 +273,30 @@ room_id = "tavern"
             Some("tavern")
         );
     }
+
+    #[test]
+    fn load_map_handles_extended_and_named_exits() {
+        let tmp = TempDir::new().unwrap();
+        make_dir(tmp.path(), "maps/w1/d1");
+        write_file(
+            tmp.path(),
+            "maps/w1/d1/room.toml",
+            r#"
+[description]
+standard = "Main room."
+[up]
+room_id = "attic"
+[southwest]
+room_id = "garden"
+[exits.tavern]
+room_id = "tavern"
+"#,
+        );
+
+        let universe = load_map(Some(tmp.path())).unwrap();
+        let room = &universe.worlds["w1"].dungeons["d1"].rooms["room"];
+        assert_eq!(room.up.as_ref().unwrap().room_id.as_deref(), Some("attic"));
+        assert!(room.southwest.is_some());
+        assert_eq!(room.exits["tavern"].room_id.as_deref(), Some("tavern"));
+    }
 }
@@ -6,7 +6,8 @@ use crate::game::component::Attribute;
 use crate::game::config::entity_config::EntityTypeConfig;
 use crate::game::{EntityConfig, EntityType, Location, Universe};
 use crate::persistence::{
-    dungeon_repo, entity_effect_repo, entity_repo, room_repo, server_state_repo, world_repo,
+    dungeon_repo, entity_effect_repo, entity_repo, interaction_repo, room_repo, server_state_repo,
+    world_repo,
 };
 
 const LAST_MAP_LOAD_KEY: &str = "last_map_load_date";
@@ -132,6 +133,7 @@ pub async fn load_entities_into_db(
                     if !config.attributes.is_empty() {
                         sync_entity_attributes(pool, entity_id, config).await?;
                     }
+                    sync_entity_interactions(pool, entity_id, config).await?;
                 }
             }
         }
@@ -139,6 +141,20 @@ pub async fn load_entities_into_db(
     Ok(())
 }
 
+/// Replace the entity's stored interactions with the ones its config declares, so edits to
+/// verbs reach entities that already exist.
+async fn sync_entity_interactions(
+    pool: &SqlitePool,
+    entity_id: i64,
+    config: &EntityConfig,
+) -> Result<(), Box<dyn Error>> {
+    interaction_repo::delete_by_entity(pool, entity_id).await?;
+    for interaction in config.interactions() {
+        interaction_repo::insert(pool, entity_id, &interaction).await?;
+    }
+    Ok(())
+}
+
 async fn sync_entity_attributes(
     pool: &SqlitePool,
     entity_id: i64,
@@ -150,19 +166,19 @@ async fn sync_entity_attributes(
         .attributes
         .iter()
         .map(|sa| {
-            let current_value = db_attrs
-                .get(&sa.definition_id)
+            let existing = db_attrs.get(&sa.definition_id);
+            let current_value = existing
                 .map(|a| a.current_value.clamp(sa.min_value, sa.max_value))
                 .unwrap_or(sa.current_value);
-            (
+            let mut attribute = Attribute::new(
                 sa.definition_id.clone(),
-                Attribute::new(
-                    sa.definition_id.clone(),
-                    sa.min_value,
-                    sa.max_value,
-                    current_value,
-                ),
-            )
+                sa.min_value,
+                sa.max_value,
+                current_value,
+            );
+            // Modifiers come from equipment, not config, so they survive a reload.
+            attribute.modifiers = existing.map(|a| a.modifiers.clone()).unwrap_or_default();
+            (sa.definition_id.clone(), attribute)
         })
         .collect();
     entity_repo::update_attributes(pool, entity_id, &attrs).await?;
@@ -172,6 +188,8 @@ async fn sync_entity_attributes(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::component::{CustomVerb, Interaction};
+    use crate::game::config::RespawnConfig;
     use crate::game::{Description, Dungeon, EntityConfig, Room, World};
     use crate::persistence::database::Database;
 
@@ -290,6 +308,16 @@ mod tests {
             persona: None,
             attributes: vec![],
             entity_effects: vec![],
+            battle_actions: vec![],
+            portal: None,
+            equipment: None,
+            consumable: None,
+            container: false,
+            loot: None,
+            xp: 0,
+            hidden: None,
+            verbs: Vec::new(),
+            respawn: RespawnConfig::default_config(),
         };
         let mut map = HashMap::new();
         map.insert("entities/innkeeper".to_string(), config);
@@ -320,6 +348,16 @@ mod tests {
                 },
             ],
             entity_effects: vec![],
+            battle_actions: vec![],
+            portal: None,
+            equipment: None,
+            consumable: None,
+            container: false,
+            loot: None,
+            xp: 0,
+            hidden: None,
+            verbs: Vec::new(),
+            respawn: RespawnConfig::default_config(),
         };
         let mut map = HashMap::new();
         map.insert("entities/innkeeper".to_string(), config);
@@ -380,6 +418,25 @@ mod tests {
         assert_eq!(entities.len(), 1);
     }
 
+    #[tokio::test]
+    async fn load_entities_stores_config_verbs_as_interactions() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let mut configs = make_entity_configs();
+        let verb: CustomVerb = toml::from_str("verb = \"ring\"\nresponse = \"Ding!\"").unwrap();
+        configs.get_mut("entities/innkeeper").unwrap().verbs = vec![verb.clone()];
+        load_innkeeper(&db, &configs).await;
+        load_innkeeper(&db, &configs).await;
+
+        let entity_id = entity_repo::find_by_location(db.pool(), &innkeeper_location())
+            .await
+            .unwrap()[0]
+            .id;
+        let interactions = interaction_repo::find_by_entity(db.pool(), entity_id)
+            .await
+            .unwrap();
+        assert_eq!(interactions, vec![Interaction::Verb(verb)]);
+    }
+
     #[tokio::test]
     async fn load_entities_populates_starting_attributes() {
         let db = Database::connect_in_memory().await.unwrap();
@@ -450,6 +507,16 @@ mod tests {
                     },
                 ],
                 entity_effects: vec![],
+                battle_actions: vec![],
+                portal: None,
+                equipment: None,
+                consumable: None,
+                container: false,
+                loot: None,
+                xp: 0,
+                hidden: None,
+                verbs: Vec::new(),
+                respawn: RespawnConfig::default_config(),
             },
         );
         let universe = make_universe_with_entity();
@@ -2,7 +2,12 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::game::component::Location;
+use crate::game::config::agent_config::AgentConfig;
+use crate::game::config::battle_config::BattleConfig;
+use crate::game::config::channel_config::ChannelConfig;
 use crate::game::config::game_loop_config::GameLoopConfig;
+use crate::game::config::player_config::PlayerTemplate;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpawnConfig {
@@ -19,12 +24,53 @@ impl SpawnConfig {
             room_id: "default".to_string(),
         }
     }
+
+    pub fn location(&self) -> Location {
+        Location {
+            world_id: self.world_id.clone(),
+            dungeon_id: self.dungeon_id.clone(),
+            room_id: self.room_id.clone(),
+        }
+    }
+}
+
+/// Attributes shown on every player's status bar, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusConfig {
+    pub attributes: Vec<String>,
+}
+
+impl StatusConfig {
+    pub fn default_config() -> Self {
+        Self {
+            attributes: vec!["hp".to_string(), "mp".to_string()],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MudConfig {
     pub game_loop: GameLoopConfig,
     pub spawn: SpawnConfig,
+    #[serde(default = "BattleConfig::default_config")]
+    pub battle: BattleConfig,
+    /// Server-wide chat channels.
+    #[serde(default = "ChannelConfig::default_config")]
+    pub channels: Vec<ChannelConfig>,
+    /// Starting attributes for new players.
+    #[serde(default = "PlayerTemplate::default_config")]
+    pub player: PlayerTemplate,
+    #[serde(default = "StatusConfig::default_config")]
+    pub status: StatusConfig,
+    /// Seed for dice rolls, to make skill checks repeatable. Random when missing.
+    #[serde(default)]
+    pub rng_seed: Option<u64>,
+    /// Bearer token required by the `/admin` routes. They are disabled when this is missing.
+    #[serde(default)]
+    pub admin_token: Option<String>,
+    /// LLM provider used by agent personas. Agents can't talk when this is missing.
+    #[serde(default)]
+    pub agent: Option<AgentConfig>,
 }
 
 impl MudConfig {
@@ -38,6 +84,13 @@ impl MudConfig {
         Self {
             game_loop: GameLoopConfig::default_config(),
             spawn: SpawnConfig::default_config(),
+            battle: BattleConfig::default_config(),
+            channels: ChannelConfig::default_config(),
+            player: PlayerTemplate::default_config(),
+            status: StatusConfig::default_config(),
+            rng_seed: None,
+            admin_token: None,
+            agent: None,
         }
     }
 }
@@ -81,5 +134,61 @@ room_id = "square"
         assert_eq!(config.spawn.world_id, "overworld");
         assert_eq!(config.spawn.dungeon_id, "town");
         assert_eq!(config.spawn.room_id, "square");
+        assert_eq!(config.battle.attack_effect, "attack");
+        assert_eq!(config.channels.len(), 3);
+        assert_eq!(config.player.stat_points, 0);
+        assert_eq!(config.status.attributes, ["hp", "mp"]);
+        assert!(config.agent.is_none());
+    }
+
+    #[test]
+    fn load_parses_channels() {
+        let toml = r#"
+[game_loop]
+tick_rate_ms = 500
+max_engage_ms = 15000
+world_update_ms = 300000
+
+[spawn]
+world_id = "overworld"
+dungeon_id = "town"
+room_id = "square"
+
+[[channels]]
+name = "guild"
+auto_join = true
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml.as_bytes()).unwrap();
+        let config = MudConfig::load(file.path()).unwrap();
+        assert_eq!(config.channels.len(), 1);
+        assert_eq!(config.channels[0].name, "guild");
+        assert!(config.channels[0].auto_join);
+    }
+
+    #[test]
+    fn load_parses_agent_section() {
+        let toml = r#"
+[game_loop]
+tick_rate_ms = 500
+max_engage_ms = 15000
+world_update_ms = 300000
+
+[spawn]
+world_id = "overworld"
+dungeon_id = "town"
+room_id = "square"
+
+[agent]
+provider = "openai"
+model = "local-model"
+base_url = "http://localhost:8080/v1"
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml.as_bytes()).unwrap();
+        let config = MudConfig::load(file.path()).unwrap();
+        let agent = config.agent.unwrap();
+        assert_eq!(agent.model, "local-model");
+        assert_eq!(agent.base_url.as_deref(), Some("http://localhost:8080/v1"));
     }
 }
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::component::attribute_definition::{AttributeCategory, AttributeType};
+use crate::game::component::{Attribute, AttributeDefinition};
+use crate::game::config::AttributeConfig;
+
+/// Starting value for one attribute. `max_value` caps it below the definition's maximum, e.g.
+/// a new player's hit points.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerAttribute {
+    pub attribute_id: String,
+    pub value: i64,
+    #[serde(default)]
+    pub max_value: Option<i64>,
+}
+
+/// How new players start out. Attributes without an entry start at their definition's minimum,
+/// except life attributes, which start full. `stat_points` are extra points a new player can add
+/// to their stat attributes; points they don't allocate are spread evenly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerTemplate {
+    #[serde(default)]
+    pub attributes: Vec<PlayerAttribute>,
+    #[serde(default)]
+    pub stat_points: i64,
+}
+
+impl PlayerTemplate {
+    pub fn default_config() -> Self {
+        let attribute = |id: &str, value: i64, max_value: Option<i64>| PlayerAttribute {
+            attribute_id: id.to_string(),
+            value,
+            max_value,
+        };
+        Self {
+            attributes: vec![
+                attribute("hp", 50, Some(50)),
+                attribute("mp", 20, Some(20)),
+                attribute("level", 1, None),
+                attribute("xp", 0, None),
+                attribute("strength", 10, None),
+                attribute("dexterity", 10, None),
+                attribute("constitution", 10, None),
+                attribute("intelligence", 10, None),
+                attribute("wisdom", 10, None),
+                attribute("charisma", 10, None),
+            ],
+            stat_points: 0,
+        }
+    }
+
+    /// Attributes for a new player, with `allocation` stat points added on top. Fails if the
+    /// allocation spends more points than the template gives, or on anything but stats.
+    pub fn starting_attributes(
+        &self,
+        config: &AttributeConfig,
+        allocation: &HashMap<String, i64>,
+    ) -> Result<HashMap<String, Attribute>, String> {
+        let mut attributes: HashMap<String, Attribute> = config
+            .attributes
+            .iter()
+            .map(|definition| (definition.id.clone(), self.starting_attribute(definition)))
+            .collect();
+
+        let mut remaining = self.stat_points;
+        for (id, points) in allocation {
+            let is_stat = config
+                .find(id)
+                .is_some_and(|d| matches!(d.attribute_type, AttributeType::Stat));
+            if !is_stat {
+                return Err(format!("{id} isn't a stat"));
+            }
+            if *points < 0 {
+                return Err(format!("Can't take points away from {id}"));
+            }
+            remaining -= points;
+            if remaining < 0 {
+                return Err(format!("Only {} stat points to spend", self.stat_points));
+            }
+            if let Some(attribute) = attributes.get_mut(id) {
+                attribute.adjust(*points);
+            }
+        }
+
+        let stats: Vec<&str> = config
+            .attributes
+            .iter()
+            .filter(|d| matches!(d.attribute_type, AttributeType::Stat))
+            .map(|d| d.id.as_str())
+            .collect();
+        for id in stats.iter().cycle().take(remaining.max(0) as usize) {
+            if let Some(attribute) = attributes.get_mut(*id) {
+                attribute.adjust(1);
+            }
+        }
+        Ok(attributes)
+    }
+
+    /// Add any attribute definitions the player doesn't have yet, at their starting values.
+    /// Returns true if anything was added.
+    pub fn backfill(
+        &self,
+        config: &AttributeConfig,
+        attributes: &mut HashMap<String, Attribute>,
+    ) -> bool {
+        let mut added = false;
+        for definition in &config.attributes {
+            if !attributes.contains_key(&definition.id) {
+                attributes.insert(definition.id.clone(), self.starting_attribute(definition));
+                added = true;
+            }
+        }
+        added
+    }
+
+    fn starting_attribute(&self, definition: &AttributeDefinition) -> Attribute {
+        let entry = self
+            .attributes
+            .iter()
+            .find(|a| a.attribute_id == definition.id);
+        let max_value = entry
+            .and_then(|a| a.max_value)
+            .unwrap_or(definition.max_value)
+            .min(definition.max_value);
+        let value = match entry {
+            Some(entry) => entry.value,
+            None if definition.attribute_category == AttributeCategory::Life => max_value,
+            None => definition.min_value,
+        };
+        Attribute::new(
+            definition.id.clone(),
+            definition.min_value,
+            max_value,
+            value.clamp(definition.min_value, max_value),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_template_seeds_every_definition() {
+        let config = AttributeConfig::default_config();
+        let attributes = PlayerTemplate::default_config()
+            .starting_attributes(&config, &HashMap::new())
+            .unwrap();
+        assert_eq!(attributes.len(), config.attributes.len());
+        assert_eq!(attributes["hp"].current_value, 50);
+        assert_eq!(attributes["hp"].max_value, 50);
+        assert_eq!(attributes["strength"].current_value, 10);
+    }
+
+    #[test]
+    fn unlisted_attributes_start_at_minimum_or_full_life() {
+        let config = AttributeConfig::default_config();
+        let template = PlayerTemplate {
+            attributes: vec![],
+            stat_points: 0,
+        };
+        let attributes = template
+            .starting_attributes(&config, &HashMap::new())
+            .unwrap();
+        assert_eq!(attributes["hp"].current_value, 999);
+        assert_eq!(attributes["level"].current_value, 1);
+    }
+
+    #[test]
+    fn stat_points_are_allocated_then_spread() {
+        let config = AttributeConfig::default_config();
+        let mut template = PlayerTemplate::default_config();
+        template.stat_points = 4;
+        let allocation = HashMap::from([("strength".to_string(), 3)]);
+        let attributes = template.starting_attributes(&config, &allocation).unwrap();
+        assert!(attributes["strength"].current_value >= 13);
+        let added: i64 = attributes
+            .values()
+            .filter(|a| {
+                config
+                    .find(&a.definition_id)
+                    .is_some_and(|d| matches!(d.attribute_type, AttributeType::Stat))
+            })
+            .map(|a| a.current_value - 10)
+            .sum();
+        assert_eq!(added, 4);
+    }
+
+    #[test]
+    fn allocation_is_validated() {
+        let config = AttributeConfig::default_config();
+        let mut template = PlayerTemplate::default_config();
+        template.stat_points = 2;
+        let too_many = HashMap::from([("strength".to_string(), 3)]);
+        assert!(template.starting_attributes(&config, &too_many).is_err());
+        let not_a_stat = HashMap::from([("hp".to_string(), 1)]);
+        assert!(template.starting_attributes(&config, &not_a_stat).is_err());
+    }
+
+    #[test]
+    fn backfill_adds_only_missing_definitions() {
+        let config = AttributeConfig::default_config();
+        let template = PlayerTemplate::default_config();
+        let mut attributes = HashMap::from([(
+            "hp".to_string(),
+            Attribute::new("hp".to_string(), 0, 50, 12),
+        )]);
+        assert!(template.backfill(&config, &mut attributes));
+        assert_eq!(attributes["hp"].current_value, 12);
+        assert_eq!(attributes.len(), config.attributes.len());
+        assert!(!template.backfill(&config, &mut attributes));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod battle;
 pub mod conversation;
 #[allow(clippy::module_inception)]
 pub mod engagement;
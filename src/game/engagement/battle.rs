@@ -0,0 +1,459 @@
+/// Handles resolved actions for [`crate::game::EngagementType::Battle`] engagements.
+///
+/// A battle engagement has two sides: players on one side and every other entity on the
+/// other. All combatants take turns in the engagement's turn order.
+///
+/// Each time a combatant's turn resolves there are two possible outcomes:
+/// - **Timeout** (`resolved.action` is `None`): the combatant didn't act in time. The
+///   `default_action` effect from the battle config is used instead, or the turn is skipped if
+///   none is configured.
+/// - **`ApplyEffect { effect_name, target_entity_id }`**: the named battle effect is applied to
+///   the target. If the target is missing or already defeated the first standing opponent is
+///   used instead. With a `hit_roll` configured the actor has to roll to hit first, and a
+///   critical hit doubles the effect's attribute update. One-off attribute updates apply
+///   immediately; anything else is attached to the target and run by the effects system.
+///
+/// After every action, and once per tick, the battle ends when either side has no standing
+/// combatants left.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::game::component::check::stat_modifier;
+use crate::game::component::{CheckOutcome, Effect, EffectType, TriggerInfo};
+use crate::game::engagement::ResolvedAction;
+use crate::game::entity::{Entity, EntityType};
+use crate::game::game_loop::attributes::{award_xp, is_defeated};
+use crate::game::game_loop::effects::{apply_effect, attach};
+use crate::game::{AttributeConfig, EngagementType, GameState, TurnAction, messaging};
+use crate::persistence::{Database, entity_repo};
+
+pub async fn handle(game_state: &Arc<GameState>, db: &Database, resolved: &ResolvedAction) {
+    let names = game_state.player_names().await;
+    let actor_name = {
+        let entities = game_state.active_entities.read().await;
+        match entities.get(&resolved.entity_id) {
+            Some(actor) if !is_defeated(actor, &game_state.attribute_config) => {
+                actor.display_name(&names)
+            }
+            // Defeated or missing combatants lose their turn.
+            _ => return,
+        }
+    };
+
+    let action = resolved.action.clone().or_else(|| {
+        game_state
+            .mud_config
+            .battle
+            .default_action
+            .clone()
+            .map(|effect_name| TurnAction::ApplyEffect {
+                effect_name,
+                target_entity_id: None,
+            })
+    });
+
+    match action {
+        None => {
+            notify(
+                game_state,
+                &resolved.entity_ids,
+                format!("{actor_name} hesitates."),
+            )
+            .await;
+        }
+        Some(TurnAction::ApplyEffect {
+            effect_name,
+            target_entity_id,
+        }) => {
+            use_effect(
+                game_state,
+                db,
+                resolved,
+                &actor_name,
+                &effect_name,
+                target_entity_id,
+            )
+            .await;
+        }
+        Some(_) => {
+            // Other action types are not handled in battle engagements
+        }
+    }
+
+    end_if_decided(game_state, db, resolved.engagement_id, &resolved.entity_ids).await;
+}
+
+/// Submit an action for every non-player combatant whose turn it is. NPCs pick one of their
+/// configured `battle_actions` at random, falling back to the battle config's attack effect.
+pub async fn submit_npc_actions(game_state: &Arc<GameState>) {
+    let awaiting = game_state
+        .engagements
+        .awaiting_actions(EngagementType::Battle)
+        .await;
+    for (engagement_id, entity_id) in awaiting {
+        let effect_name = {
+            let entities = game_state.active_entities.read().await;
+            let Some(entity) = entities.get(&entity_id) else {
+                continue;
+            };
+            if entity.entity_type == EntityType::Player {
+                continue;
+            }
+            let actions = entity
+                .config_id
+                .as_deref()
+                .and_then(|id| game_state.entity_configs.get(id))
+                .map(|c| c.battle_actions.as_slice())
+                .unwrap_or_default();
+            if actions.is_empty() {
+                game_state.mud_config.battle.attack_effect.clone()
+            } else {
+                actions[fastrand::usize(..actions.len())].clone()
+            }
+        };
+        game_state
+            .engagements
+            .submit_action(
+                engagement_id,
+                entity_id,
+                TurnAction::ApplyEffect {
+                    effect_name,
+                    target_entity_id: None,
+                },
+            )
+            .await;
+    }
+}
+
+/// End every battle that has already been decided, e.g. by an effect that ran outside of a turn
+/// or a combatant leaving.
+pub async fn end_decided_battles(game_state: &Arc<GameState>, db: &Database) {
+    for (engagement_id, entity_ids) in game_state.engagements.members(EngagementType::Battle).await
+    {
+        end_if_decided(game_state, db, engagement_id, &entity_ids).await;
+    }
+}
+
+/// End the battle if either side has no standing combatants. Returns true if it ended. Players
+/// still standing after a victory share the `xp` of every opponent they defeated.
+pub async fn end_if_decided(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    engagement_id: i64,
+    entity_ids: &[i64],
+) -> bool {
+    let (victors, opponents_standing, xp) = {
+        let entities = game_state.active_entities.read().await;
+        let (standing, defeated): (Vec<&Entity>, Vec<&Entity>) = entity_ids
+            .iter()
+            .filter_map(|id| entities.get(id))
+            .partition(|e| !is_defeated(e, &game_state.attribute_config));
+        let victors: Vec<i64> = standing
+            .iter()
+            .filter(|e| e.entity_type == EntityType::Player)
+            .map(|e| e.id)
+            .collect();
+        let xp: i64 = defeated
+            .iter()
+            .filter(|e| e.entity_type != EntityType::Player)
+            .filter_map(|e| e.config_id.as_deref())
+            .filter_map(|id| game_state.entity_configs.get(id))
+            .map(|config| config.xp)
+            .sum();
+        let opponents_standing = standing.len() - victors.len();
+        (victors, opponents_standing, xp)
+    };
+    if !victors.is_empty() && opponents_standing > 0 {
+        return false;
+    }
+
+    game_state.engagements.remove(engagement_id).await;
+    let outcome = if !victors.is_empty() {
+        "The battle is over. You are victorious!"
+    } else {
+        "The battle is over."
+    };
+    notify(game_state, entity_ids, outcome).await;
+    if xp > 0 && !victors.is_empty() {
+        let share = (xp / victors.len() as i64).max(1);
+        for entity_id in victors {
+            award_xp(game_state, db, entity_id, share).await;
+        }
+    }
+    true
+}
+
+async fn use_effect(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    resolved: &ResolvedAction,
+    actor_name: &str,
+    effect_name: &str,
+    target_entity_id: Option<i64>,
+) {
+    let Some(effect) = game_state.mud_config.battle.find_effect(effect_name) else {
+        tracing::warn!(effect_name, "unknown battle effect");
+        notify(
+            game_state,
+            &resolved.entity_ids,
+            format!("{actor_name} fumbles."),
+        )
+        .await;
+        return;
+    };
+
+    let names = game_state.player_names().await;
+    let (target_id, target_name) = {
+        let entities = game_state.active_entities.read().await;
+        let Some(target) = pick_target(
+            &entities,
+            &game_state.attribute_config,
+            &resolved.entity_ids,
+            resolved.entity_id,
+            target_entity_id,
+        ) else {
+            return;
+        };
+        (target.id, target.display_name(&names))
+    };
+
+    notify(
+        game_state,
+        &resolved.entity_ids,
+        format!("{actor_name} uses {effect_name} on {target_name}."),
+    )
+    .await;
+
+    let mut effect = effect.clone();
+    if let Some(hit_roll) = &game_state.mud_config.battle.hit_roll {
+        let roll = {
+            let entities = game_state.active_entities.read().await;
+            let defense = hit_roll
+                .defense_attribute_id
+                .as_ref()
+                .and_then(|id| entities.get(&target_id)?.attributes.get(id))
+                .map_or(0, |a| stat_modifier(a.effective_value()));
+            let Some(actor) = entities.get(&resolved.entity_id) else {
+                return;
+            };
+            hit_roll
+                .check(defense)
+                .roll(&actor.attributes, &mut game_state.rng())
+        };
+        notify(game_state, &[resolved.entity_id], roll.narrate()).await;
+        if !roll.succeeded() {
+            notify(
+                game_state,
+                &resolved.entity_ids,
+                format!("{actor_name} misses {target_name}."),
+            )
+            .await;
+            return;
+        }
+        if roll.outcome == CheckOutcome::CriticalSuccess {
+            notify(game_state, &resolved.entity_ids, "A critical hit!").await;
+            if let EffectType::AttributeUpdate { value, .. } = &mut effect.effect_type {
+                *value = value.saturating_mul(2);
+            }
+        }
+    }
+    let effect = &effect;
+
+    let immediate = effect.trigger_info == TriggerInfo::Once
+        && matches!(effect.effect_type, EffectType::AttributeUpdate { .. });
+    if immediate {
+        apply_once(game_state, db, resolved, target_id, &target_name, effect).await;
+    } else if let Err(e) = attach(game_state, db, target_id, effect).await {
+        tracing::error!(error = %e, "Failed to attach battle effect");
+    }
+}
+
+async fn apply_once(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    resolved: &ResolvedAction,
+    target_id: i64,
+    target_name: &str,
+    effect: &Effect,
+) {
+    let outcome = {
+        let mut entities = game_state.active_entities.write().await;
+        entities.get_mut(&target_id).map(|target| {
+            let changed = apply_effect(target, &effect.effect_type);
+            (
+                changed.then(|| target.attributes.clone()),
+                is_defeated(target, &game_state.attribute_config),
+            )
+        })
+    };
+    let Some((attributes, defeated)) = outcome else {
+        return;
+    };
+    if let Some(attributes) = attributes
+        && let Err(e) = entity_repo::update_attributes(db.pool(), target_id, &attributes).await
+    {
+        tracing::error!(error = %e, "Failed to persist attributes after battle effect");
+    }
+    if defeated {
+        notify(
+            game_state,
+            &resolved.entity_ids,
+            format!("{target_name} is defeated!"),
+        )
+        .await;
+    }
+}
+
+/// Choose who an effect lands on: the requested entity if it is a standing combatant other than
+/// the actor, otherwise the first standing opponent.
+fn pick_target<'a>(
+    entities: &'a HashMap<i64, Entity>,
+    attribute_config: &AttributeConfig,
+    entity_ids: &[i64],
+    actor_id: i64,
+    requested: Option<i64>,
+) -> Option<&'a Entity> {
+    let actor_is_player = entities.get(&actor_id)?.entity_type == EntityType::Player;
+    let standing = |e: &&Entity| !is_defeated(e, attribute_config);
+
+    if let Some(requested) = requested
+        && requested != actor_id
+        && entity_ids.contains(&requested)
+        && let Some(target) = entities.get(&requested).filter(standing)
+    {
+        return Some(target);
+    }
+
+    let mut opponents: Vec<&Entity> = entity_ids
+        .iter()
+        .filter_map(|id| entities.get(id))
+        .filter(|e| (e.entity_type == EntityType::Player) != actor_is_player)
+        .filter(standing)
+        .collect();
+    opponents.sort_by_key(|e| e.id);
+    opponents.first().copied()
+}
+
+/// Send a message to every player taking part in the battle.
+async fn notify(game_state: &Arc<GameState>, entity_ids: &[i64], content: impl Into<String>) {
+    let content = content.into();
+    let players = game_state.active_players.read().await;
+    for player in players
+        .values()
+        .filter(|p| entity_ids.contains(&p.entity_id))
+    {
+        messaging::message(&game_state.message_tx, player.id, content.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Location;
+    use crate::game::component::Attribute;
+
+    fn make_entity(id: i64, entity_type: EntityType, hp: i64) -> Entity {
+        let mut entity = Entity::new(
+            id,
+            entity_type,
+            Location {
+                world_id: "w1".to_string(),
+                dungeon_id: "d1".to_string(),
+                room_id: "r1".to_string(),
+            },
+        );
+        entity.attributes.insert(
+            "hp".to_string(),
+            Attribute::new("hp".to_string(), 0, 20, hp),
+        );
+        entity
+    }
+
+    fn make_entities() -> HashMap<i64, Entity> {
+        [
+            make_entity(1, EntityType::Player, 20),
+            make_entity(2, EntityType::Character, 0),
+            make_entity(3, EntityType::Character, 10),
+            make_entity(4, EntityType::Character, 10),
+        ]
+        .into_iter()
+        .map(|e| (e.id, e))
+        .collect()
+    }
+
+    #[test]
+    fn pick_target_uses_requested_standing_combatant() {
+        let entities = make_entities();
+        let config = AttributeConfig::default_config();
+        let target = pick_target(&entities, &config, &[1, 2, 3, 4], 1, Some(4)).unwrap();
+        assert_eq!(target.id, 4);
+    }
+
+    #[test]
+    fn pick_target_falls_back_to_first_standing_opponent() {
+        let entities = make_entities();
+        let config = AttributeConfig::default_config();
+        let ids = [1, 2, 3, 4];
+        assert_eq!(
+            pick_target(&entities, &config, &ids, 1, Some(2))
+                .unwrap()
+                .id,
+            3
+        );
+        assert_eq!(
+            pick_target(&entities, &config, &ids, 1, Some(1))
+                .unwrap()
+                .id,
+            3
+        );
+        assert_eq!(
+            pick_target(&entities, &config, &ids, 1, None).unwrap().id,
+            3
+        );
+        assert_eq!(
+            pick_target(&entities, &config, &ids, 3, None).unwrap().id,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn hit_roll_misses_on_a_natural_one_and_doubles_on_a_natural_twenty() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let mut game_state = GameState::load(None).unwrap();
+        game_state.mud_config.battle.hit_roll = Some(
+            toml::from_str(
+                r#"attribute_id = "strength"
+difficulty = 10"#,
+            )
+            .unwrap(),
+        );
+        let game_state = Arc::new(game_state);
+        game_state.active_entities.write().await.extend([
+            (1, make_entity(1, EntityType::Player, 20)),
+            (2, make_entity(2, EntityType::Character, 20)),
+        ]);
+        // Seed 14 rolls a natural 1 and then a natural 20.
+        *game_state.rng() = fastrand::Rng::with_seed(14);
+        let resolved = ResolvedAction {
+            engagement_id: 1,
+            engagement_type: EngagementType::Battle,
+            entity_ids: vec![1, 2],
+            entity_id: 1,
+            action: None,
+        };
+        let hp = |entities: &HashMap<i64, Entity>| entities[&2].attributes["hp"].current_value;
+
+        use_effect(&game_state, &db, &resolved, "Alice", "attack", Some(2)).await;
+        assert_eq!(hp(&*game_state.active_entities.read().await), 20);
+
+        use_effect(&game_state, &db, &resolved, "Alice", "attack", Some(2)).await;
+        assert_eq!(hp(&*game_state.active_entities.read().await), 10);
+    }
+
+    #[test]
+    fn pick_target_none_when_no_opponents_stand() {
+        let entities = make_entities();
+        let config = AttributeConfig::default_config();
+        assert!(pick_target(&entities, &config, &[1, 2], 1, None).is_none());
+    }
+}
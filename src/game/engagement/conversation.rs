@@ -2,25 +2,38 @@
 ///
 /// A conversation engagement has two entities: a player and an NPC. Only the player takes
 /// turns; the NPC's entity id is tracked for dialog-state lookups but never appears in the
-/// turn order.
+/// turn order. The NPC either follows a dialog tree or, for agent personas, replies through the
+/// configured [`crate::game::agent::AgentProvider`].
 ///
 /// Each time the player's turn resolves there are two possible outcomes:
 /// - **Timeout** (`resolved.action` is `None`): the player didn't respond in time.
 ///   The conversation is ended, the NPC's conversation state is cleaned up, and the
 ///   engagement is removed.
+/// - **`Respond { content }`** or **`SendMessage { content }`**: the player said something.
+///   Saying `bye` ends the conversation. Agent NPCs reply to anything else through their
+///   provider; scripted NPCs pick the dialog option matching what was said.
 /// - **`SelectDialogChoice { choice }`**: the player picked a numbered dialog option.
 ///   The handler validates the choice, advances to the matching reply node in the dialog
 ///   tree, updates the NPC's in-memory conversation context, and sends the next dialog
 ///   message to the player. If the reply has no further responses the conversation ends.
+///   Responses with a `roll` only go ahead when the player passes it; otherwise the options are
+///   offered again and that response stays locked for the player. Responses with `xp` award it
+///   to the player the first time they choose it.
+///   Agent conversations have no numbered options, so choices are ignored there.
 use std::sync::Arc;
 
 use crate::game::TurnAction;
+use crate::game::config::{DialogLine, PlayerResponse};
 use crate::game::engagement::ResolvedAction;
-use crate::game::game_loop::interactions::conversation::{format_dialog_message, pick_text};
+use crate::game::game_loop::attributes::award_xp;
+use crate::game::game_loop::interactions::conversation::{
+    agent_reply, format_dialog_message, pick_text,
+};
 use crate::game::player::Player;
 use crate::game::{GameState, messaging};
+use crate::persistence::{Database, dialog_reward_repo};
 
-pub async fn handle(game_state: &Arc<GameState>, resolved: &ResolvedAction) {
+pub async fn handle(game_state: &Arc<GameState>, db: &Database, resolved: &ResolvedAction) {
     let (player_entity_id, npc_entity_id) = match find_player_and_npc(game_state, resolved).await {
         Some(pair) => pair,
         None => return,
@@ -42,9 +55,31 @@ pub async fn handle(game_state: &Arc<GameState>, resolved: &ResolvedAction) {
             remove_npc_conversation_state(game_state, npc_entity_id, resolved.engagement_id).await;
             game_state.engagements.remove(resolved.engagement_id).await;
         }
+        Some(TurnAction::Respond { content } | TurnAction::SendMessage { content })
+            if is_farewell(content) =>
+        {
+            messaging::message(&game_state.message_tx, player.id, "You say goodbye.");
+            remove_npc_conversation_state(game_state, npc_entity_id, resolved.engagement_id).await;
+            game_state.engagements.remove(resolved.engagement_id).await;
+        }
+        Some(
+            TurnAction::SelectDialogChoice { choice: content }
+            | TurnAction::Respond { content }
+            | TurnAction::SendMessage { content },
+        ) if is_agent_conversation(game_state, npc_entity_id, resolved.engagement_id).await => {
+            agent_reply(
+                game_state,
+                player.id,
+                npc_entity_id,
+                resolved.engagement_id,
+                content.clone(),
+            )
+            .await;
+        }
         Some(TurnAction::SelectDialogChoice { choice }) => {
             handle_choice(
                 game_state,
+                db,
                 &player,
                 npc_entity_id,
                 resolved.engagement_id,
@@ -52,14 +87,218 @@ pub async fn handle(game_state: &Arc<GameState>, resolved: &ResolvedAction) {
             )
             .await;
         }
+        Some(TurnAction::Respond { content } | TurnAction::SendMessage { content }) => {
+            handle_speech(
+                game_state,
+                db,
+                &player,
+                npc_entity_id,
+                resolved.engagement_id,
+                content,
+            )
+            .await;
+        }
         Some(_) => {
             // Other action types are not handled in conversation engagements
         }
     }
 }
 
+/// Scripted NPCs can't understand arbitrary speech, so match what the player said against the
+/// current dialog options and pick that option if one fits.
+async fn handle_speech(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    npc_entity_id: i64,
+    engagement_id: i64,
+    content: &str,
+) {
+    let index = current_dialog(game_state, npc_entity_id, engagement_id)
+        .await
+        .and_then(|d| match_response(&d.responses, content));
+    match index {
+        Some(index) => {
+            let choice = (index + 1).to_string();
+            handle_choice(
+                game_state,
+                db,
+                player,
+                npc_entity_id,
+                engagement_id,
+                &choice,
+            )
+            .await;
+        }
+        None => {
+            messaging::message(
+                &game_state.message_tx,
+                player.id,
+                "They don't seem to understand. Pick one of the options.",
+            );
+            resend_current_dialog(game_state, player, npc_entity_id, engagement_id).await;
+        }
+    }
+}
+
+fn is_farewell(content: &str) -> bool {
+    matches!(
+        content.trim().to_lowercase().as_str(),
+        "bye" | "goodbye" | "farewell"
+    )
+}
+
+/// Find the dialog option the player's words refer to: an exact match of the option text, or
+/// the only option containing what they said.
+fn match_response(responses: &[PlayerResponse], content: &str) -> Option<usize> {
+    let normalize = |text: &str| text.trim().trim_end_matches(['.', '!', '?']).to_lowercase();
+    let said = normalize(content);
+    if said.is_empty() {
+        return None;
+    }
+    if let Some(index) = responses.iter().position(|r| normalize(&r.text) == said) {
+        return Some(index);
+    }
+    let mut partial = responses
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| normalize(&r.text).contains(&said));
+    match (partial.next(), partial.next()) {
+        (Some((index, _)), None) => Some(index),
+        _ => None,
+    }
+}
+
+async fn current_dialog(
+    game_state: &Arc<GameState>,
+    npc_entity_id: i64,
+    engagement_id: i64,
+) -> Option<DialogLine> {
+    let entities = game_state.active_entities.read().await;
+    entities
+        .get(&npc_entity_id)
+        .and_then(|e| e.ai.as_ref())
+        .and_then(|ai| ai.simple_conversation_state.as_ref())
+        .and_then(|s| s.contexts.get(&engagement_id))
+        .and_then(|ctx| ctx.current_dialog.clone())
+}
+
+/// The choices that led to the current dialog line.
+async fn current_path(
+    game_state: &Arc<GameState>,
+    npc_entity_id: i64,
+    engagement_id: i64,
+) -> Vec<usize> {
+    let entities = game_state.active_entities.read().await;
+    entities
+        .get(&npc_entity_id)
+        .and_then(|e| e.ai.as_ref())
+        .and_then(|ai| ai.simple_conversation_state.as_ref())
+        .and_then(|s| s.contexts.get(&engagement_id))
+        .map(|ctx| ctx.path.clone())
+        .unwrap_or_default()
+}
+
+/// Key for the response at `path` in the NPC's dialog tree, as `(npc, path)`. Keys use the NPC's
+/// config so they survive respawns and restarts.
+async fn response_key(
+    game_state: &Arc<GameState>,
+    npc_entity_id: i64,
+    path: &[usize],
+) -> Option<(String, String)> {
+    let npc = game_state
+        .active_entities
+        .read()
+        .await
+        .get(&npc_entity_id)
+        .map(|e| {
+            e.config_id
+                .clone()
+                .unwrap_or_else(|| format!("entity-{}", e.id))
+        })?;
+    let path = path
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(".");
+    Some((npc, path))
+}
+
+/// Award the XP for the response at `path` in the NPC's dialog tree, unless this player was
+/// already paid for it, so respawned or restarted conversations can't pay out twice.
+async fn award_dialog_xp(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    npc_entity_id: i64,
+    path: &[usize],
+    xp: i64,
+) {
+    if xp <= 0 {
+        return;
+    }
+    let Some((npc, path)) = response_key(game_state, npc_entity_id, path).await else {
+        return;
+    };
+    match dialog_reward_repo::claim(db.pool(), player.id, &npc, &path).await {
+        Ok(true) => award_xp(game_state, db, player.entity_id, xp).await,
+        Ok(false) => {}
+        Err(e) => tracing::error!(error = %e, "Failed to record dialog reward"),
+    }
+}
+
+/// Roll for the response at `path` if it has a `roll`. A player gets one attempt per response: a
+/// failed roll is recorded and later attempts are turned away without rolling.
+async fn passes_dialog_roll(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    npc_entity_id: i64,
+    path: &[usize],
+    response: &PlayerResponse,
+) -> bool {
+    let Some(check) = &response.roll else {
+        return true;
+    };
+    let key = response_key(game_state, npc_entity_id, path).await;
+    if let Some((npc, path)) = &key {
+        match dialog_reward_repo::has_failed_roll(db.pool(), player.id, npc, path).await {
+            Ok(false) => {}
+            Ok(true) => {
+                messaging::message(
+                    &game_state.message_tx,
+                    player.id,
+                    "You've already tried that.",
+                );
+                return false;
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to load dialog roll failures"),
+        }
+    }
+    let Some(roll) = game_state.roll_check(player.entity_id, check).await else {
+        return true;
+    };
+    messaging::message(&game_state.message_tx, player.id, roll.narrate());
+    if roll.succeeded() {
+        return true;
+    }
+    let failure = response
+        .roll_failure
+        .as_deref()
+        .unwrap_or("That didn't work.");
+    messaging::message(&game_state.message_tx, player.id, failure);
+    if let Some((npc, path)) = &key
+        && let Err(e) =
+            dialog_reward_repo::record_failed_roll(db.pool(), player.id, npc, path).await
+    {
+        tracing::error!(error = %e, "Failed to record dialog roll failure");
+    }
+    false
+}
+
 async fn handle_choice(
     game_state: &Arc<GameState>,
+    db: &Database,
     player: &Player,
     npc_entity_id: i64,
     engagement_id: i64,
@@ -74,17 +313,7 @@ async fn handle_choice(
         }
     };
 
-    let current_dialog = {
-        let entities = game_state.active_entities.read().await;
-        entities
-            .get(&npc_entity_id)
-            .and_then(|e| e.ai.as_ref())
-            .and_then(|ai| ai.simple_conversation_state.as_ref())
-            .and_then(|s| s.contexts.get(&engagement_id))
-            .and_then(|ctx| ctx.current_dialog.clone())
-    };
-
-    let dialog = match current_dialog {
+    let dialog = match current_dialog(game_state, npc_entity_id, engagement_id).await {
         Some(d) => d,
         None => {
             game_state.engagements.remove(engagement_id).await;
@@ -100,6 +329,13 @@ async fn handle_choice(
             return;
         }
     };
+    let mut path = current_path(game_state, npc_entity_id, engagement_id).await;
+    path.push(index + 1);
+    if !passes_dialog_roll(game_state, db, player, npc_entity_id, &path, response).await {
+        resend_current_dialog(game_state, player, npc_entity_id, engagement_id).await;
+        return;
+    }
+    award_dialog_xp(game_state, db, player, npc_entity_id, &path, response.xp).await;
 
     match &response.reply {
         None => {
@@ -119,6 +355,7 @@ async fn handle_choice(
                     && let Some(ctx) = state.contexts.get_mut(&engagement_id)
                 {
                     ctx.current_dialog = Some(*reply.clone());
+                    ctx.path = path;
                 }
             }
 
@@ -134,16 +371,7 @@ async fn resend_current_dialog(
     npc_entity_id: i64,
     engagement_id: i64,
 ) {
-    let dialog = {
-        let entities = game_state.active_entities.read().await;
-        entities
-            .get(&npc_entity_id)
-            .and_then(|e| e.ai.as_ref())
-            .and_then(|ai| ai.simple_conversation_state.as_ref())
-            .and_then(|s| s.contexts.get(&engagement_id))
-            .and_then(|ctx| ctx.current_dialog.clone())
-    };
-    if let Some(d) = dialog {
+    if let Some(d) = current_dialog(game_state, npc_entity_id, engagement_id).await {
         let text = pick_text(&d).to_string();
         let msg = format_dialog_message(&text, &d.responses);
         messaging::stream_message(game_state.message_tx.clone(), player.id, msg);
@@ -158,12 +386,29 @@ async fn remove_npc_conversation_state(
     let mut entities = game_state.active_entities.write().await;
     if let Some(npc) = entities.get_mut(&npc_entity_id)
         && let Some(ai) = npc.ai.as_mut()
-        && let Some(state) = ai.simple_conversation_state.as_mut()
     {
-        state.contexts.remove(&engagement_id);
+        if let Some(state) = ai.simple_conversation_state.as_mut() {
+            state.contexts.remove(&engagement_id);
+        }
+        if let Some(state) = ai.agent_conversation_state.as_mut() {
+            state.contexts.remove(&engagement_id);
+        }
     }
 }
 
+async fn is_agent_conversation(
+    game_state: &Arc<GameState>,
+    npc_entity_id: i64,
+    engagement_id: i64,
+) -> bool {
+    let entities = game_state.active_entities.read().await;
+    entities
+        .get(&npc_entity_id)
+        .and_then(|e| e.ai.as_ref())
+        .and_then(|ai| ai.agent_conversation_state.as_ref())
+        .is_some_and(|s| s.contexts.contains_key(&engagement_id))
+}
+
 /// Determine which entity is the player and which is the NPC.
 /// The player is the one whose turn resolved (entity_id in the resolved action).
 async fn find_player_and_npc(
@@ -195,3 +440,154 @@ async fn find_player_by_entity(game_state: &Arc<GameState>, entity_id: i64) -> O
         .find(|p| p.entity_id == entity_id)
         .cloned()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::{Attribute, RollMode};
+    use crate::game::entity_ai::{ConversationContext, EntityAI, SimpleConversationState};
+    use crate::game::messaging::Message;
+    use crate::game::{Entity, EntityType, SkillCheck};
+    use crate::persistence::fixtures::{insert_player, insert_room};
+
+    fn responses() -> Vec<PlayerResponse> {
+        ["Tell me about the town.", "Got a room?", "Tell me a story."]
+            .into_iter()
+            .map(|text| PlayerResponse {
+                text: text.to_string(),
+                reply: None,
+                xp: 0,
+                roll: None,
+                roll_failure: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn match_response_exact_or_unique_partial() {
+        let responses = responses();
+        assert_eq!(match_response(&responses, "got a room"), Some(1));
+        assert_eq!(match_response(&responses, "the town"), Some(0));
+        assert_eq!(match_response(&responses, "tell me"), None);
+        assert_eq!(match_response(&responses, "dragons"), None);
+        assert_eq!(match_response(&responses, "  "), None);
+    }
+
+    #[test]
+    fn farewell_words() {
+        assert!(is_farewell(" Bye "));
+        assert!(is_farewell("goodbye"));
+        assert!(!is_farewell("bye now"));
+    }
+
+    /// Alice, with no XP, standing next to the innkeeper (entity 900).
+    async fn meet_innkeeper(db: &Database, game_state: &GameState) -> Player {
+        let location = insert_room(db).await;
+        let player = insert_player(db, &location, "Alice").await;
+        let mut entity = Entity::new(player.entity_id, EntityType::Player, location.clone());
+        entity.attributes.insert(
+            "xp".to_string(),
+            Attribute::new("xp".to_string(), 0, 1000, 0),
+        );
+        let mut npc = Entity::new(900, EntityType::Character, location);
+        npc.config_id = Some("innkeeper".to_string());
+        let mut entities = game_state.active_entities.write().await;
+        entities.insert(entity.id, entity);
+        entities.insert(npc.id, npc);
+        player
+    }
+
+    /// Start conversation `engagement_id` with the innkeeper at `dialog`.
+    async fn start_dialog(game_state: &GameState, engagement_id: i64, dialog: &DialogLine) {
+        let mut state = SimpleConversationState::default();
+        state.contexts.insert(
+            engagement_id,
+            ConversationContext {
+                current_dialog: Some(dialog.clone()),
+                path: Vec::new(),
+            },
+        );
+        game_state
+            .active_entities
+            .write()
+            .await
+            .get_mut(&900)
+            .unwrap()
+            .ai = Some(EntityAI {
+            simple_conversation_state: Some(state),
+            ..Default::default()
+        });
+    }
+
+    #[tokio::test]
+    async fn choosing_a_rewarded_response_again_pays_no_more_xp() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let game_state = Arc::new(GameState::load(None).unwrap());
+        let player = meet_innkeeper(&db, &game_state).await;
+        let mut responses = responses();
+        responses[1].xp = 10;
+        let dialog = DialogLine {
+            text: "Welcome!".to_string(),
+            alts: Vec::new(),
+            responses,
+        };
+
+        for engagement_id in [1, 2] {
+            start_dialog(&game_state, engagement_id, &dialog).await;
+            handle_choice(&game_state, &db, &player, 900, engagement_id, "2").await;
+        }
+
+        let entities = game_state.active_entities.read().await;
+        assert_eq!(
+            entities[&player.entity_id].attributes["xp"].current_value,
+            10
+        );
+    }
+
+    #[tokio::test]
+    async fn a_failed_roll_locks_the_response_and_a_passed_one_moves_on() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let game_state = Arc::new(GameState::load(None).unwrap());
+        let player = meet_innkeeper(&db, &game_state).await;
+        let mut rx = game_state.message_tx.subscribe();
+        let mut responses = responses();
+        for response in &mut responses[..2] {
+            response.roll = Some(SkillCheck::new("charisma", 10, RollMode::Normal));
+            response.reply = Some(Box::new(DialogLine {
+                text: "Fine, fine.".to_string(),
+                alts: Vec::new(),
+                responses: Vec::new(),
+            }));
+        }
+        let dialog = DialogLine {
+            text: "Welcome!".to_string(),
+            alts: Vec::new(),
+            responses,
+        };
+        start_dialog(&game_state, 1, &dialog).await;
+        // Seed 20 rolls a 3 and then an 11 against DC 10.
+        *game_state.rng() = fastrand::Rng::with_seed(20);
+        let current = || async {
+            current_dialog(&game_state, 900, 1)
+                .await
+                .map(|d| d.text)
+                .unwrap()
+        };
+
+        handle_choice(&game_state, &db, &player, 900, 1, "1").await;
+        assert_eq!(current().await, "Welcome!");
+        handle_choice(&game_state, &db, &player, 900, 1, "1").await;
+        assert_eq!(current().await, "Welcome!");
+        let mut replies = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            if let Message::Complete(text) = message.message {
+                replies.push(text);
+            }
+        }
+        assert!(replies.contains(&"That didn't work.".to_string()));
+        assert!(replies.contains(&"You've already tried that.".to_string()));
+
+        handle_choice(&game_state, &db, &player, 900, 1, "2").await;
+        assert_eq!(current().await, "Fine, fine.");
+    }
+}
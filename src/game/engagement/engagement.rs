@@ -56,6 +56,17 @@ impl Engagement {
         }
     }
 
+    /// Remove an entity from the engagement, dropping any pending action and its place in the
+    /// turn order.
+    pub fn remove_entity(&mut self, entity_id: i64) {
+        if self.turn_order.current() == Some(entity_id) {
+            self.ticks_on_current_turn = 0;
+        }
+        self.entity_ids.retain(|&id| id != entity_id);
+        self.pending_actions.remove(&entity_id);
+        self.turn_order.remove(entity_id);
+    }
+
     /// Returns true if the turn should advance: the current entity has submitted an action
     /// or the turn has timed out.
     pub fn should_advance(&self, max_engage_ticks: u64) -> bool {
@@ -147,6 +158,21 @@ mod tests {
         assert!(!eng.should_advance(30));
     }
 
+    #[test]
+    fn remove_entity_drops_entity_and_action() {
+        let mut eng = make_engagement();
+        eng.pending_actions.insert(
+            10,
+            TurnAction::Respond {
+                content: "bye".to_string(),
+            },
+        );
+        eng.remove_entity(10);
+        assert_eq!(eng.entity_ids, vec![20, 30]);
+        assert!(eng.pending_actions.is_empty());
+        assert_eq!(eng.current_entity(), Some(20));
+    }
+
     #[test]
     fn should_advance_on_timeout() {
         let mut eng = make_engagement();
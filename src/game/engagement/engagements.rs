@@ -50,6 +50,84 @@ impl Engagements {
         })
     }
 
+    /// Returns true if the given entity is currently part of a Battle engagement.
+    pub async fn is_entity_in_battle(&self, entity_id: i64) -> bool {
+        self.find_for_entity(entity_id, EngagementType::Battle)
+            .await
+            .is_some()
+    }
+
+    /// Returns true if the given entity is part of any engagement.
+    pub async fn is_entity_engaged(&self, entity_id: i64) -> bool {
+        self.engagements_by_id
+            .read()
+            .await
+            .values()
+            .any(|e| e.entity_ids.contains(&entity_id))
+    }
+
+    /// Returns the id of the engagement of the given type that contains the entity, if any.
+    pub async fn find_for_entity(
+        &self,
+        entity_id: i64,
+        engagement_type: EngagementType,
+    ) -> Option<i64> {
+        self.engagements_by_id
+            .read()
+            .await
+            .values()
+            .find(|e| e.engagement_type == engagement_type && e.entity_ids.contains(&entity_id))
+            .map(|e| e.id)
+    }
+
+    /// Remove an entity from an engagement. Returns the entity ids still in the engagement, or
+    /// `None` if the engagement doesn't exist.
+    pub async fn remove_entity(&self, engagement_id: i64, entity_id: i64) -> Option<Vec<i64>> {
+        let mut map = self.engagements_by_id.write().await;
+        let engagement = map.get_mut(&engagement_id)?;
+        engagement.remove_entity(entity_id);
+        Some(engagement.entity_ids.clone())
+    }
+
+    /// Returns `(engagement_id, entity_ids)` for every engagement of the given type.
+    pub async fn members(&self, engagement_type: EngagementType) -> Vec<(i64, Vec<i64>)> {
+        self.engagements_by_id
+            .read()
+            .await
+            .values()
+            .filter(|e| e.engagement_type == engagement_type)
+            .map(|e| (e.id, e.entity_ids.clone()))
+            .collect()
+    }
+
+    /// Returns `(engagement_id, entity_id)` for every engagement of the given type whose
+    /// current entity hasn't submitted an action yet.
+    pub async fn awaiting_actions(&self, engagement_type: EngagementType) -> Vec<(i64, i64)> {
+        self.engagements_by_id
+            .read()
+            .await
+            .values()
+            .filter(|e| e.engagement_type == engagement_type)
+            .filter_map(|e| {
+                let current = e.current_entity()?;
+                (!e.pending_actions.contains_key(&current)).then_some((e.id, current))
+            })
+            .collect()
+    }
+
+    /// Submit a turn action for an entity in a specific engagement.
+    /// Returns true if the engagement exists and the entity is part of it.
+    pub async fn submit_action(
+        &self,
+        engagement_id: i64,
+        entity_id: i64,
+        action: TurnAction,
+    ) -> bool {
+        let mut map = self.engagements_by_id.write().await;
+        map.get_mut(&engagement_id)
+            .is_some_and(|e| e.submit_action(entity_id, action))
+    }
+
     /// Find the engagement containing the given entity and submit a turn action.
     /// Entities may submit actions off-turn; they are stored per-entity and resolved in order.
     /// Returns true if the entity is part of an engagement.
@@ -179,6 +257,76 @@ mod tests {
         assert!(!accepted);
     }
 
+    #[tokio::test]
+    async fn find_for_entity_matches_type() {
+        let engagements = Engagements::new();
+        let battle = engagements.add(EngagementType::Battle, vec![10, 20]).await;
+        assert_eq!(
+            engagements
+                .find_for_entity(10, EngagementType::Battle)
+                .await,
+            Some(battle)
+        );
+        assert!(
+            engagements
+                .find_for_entity(10, EngagementType::Conversation)
+                .await
+                .is_none()
+        );
+        assert!(engagements.is_entity_in_battle(20).await);
+        assert!(engagements.is_entity_engaged(20).await);
+        assert!(!engagements.is_entity_engaged(30).await);
+    }
+
+    #[tokio::test]
+    async fn remove_entity_returns_remaining_ids() {
+        let engagements = Engagements::new();
+        let id = engagements
+            .add(EngagementType::Battle, vec![10, 20, 30])
+            .await;
+        assert_eq!(engagements.remove_entity(id, 20).await, Some(vec![10, 30]));
+        assert_eq!(engagements.remove_entity(99, 20).await, None);
+    }
+
+    #[tokio::test]
+    async fn members_lists_engagements_of_type() {
+        let engagements = Engagements::new();
+        let battle = engagements.add(EngagementType::Battle, vec![10, 20]).await;
+        engagements.add_conversation(30, 40).await;
+        assert_eq!(
+            engagements.members(EngagementType::Battle).await,
+            vec![(battle, vec![10, 20])]
+        );
+    }
+
+    #[tokio::test]
+    async fn awaiting_actions_lists_current_entities_without_actions() {
+        let engagements = Engagements::new();
+        let id = engagements.add(EngagementType::Battle, vec![10, 20]).await;
+        assert_eq!(
+            engagements.awaiting_actions(EngagementType::Battle).await,
+            vec![(id, 10)]
+        );
+        assert!(
+            engagements
+                .submit_action(
+                    id,
+                    10,
+                    TurnAction::ApplyEffect {
+                        effect_name: "attack".to_string(),
+                        target_entity_id: None,
+                    },
+                )
+                .await
+        );
+        assert!(
+            engagements
+                .awaiting_actions(EngagementType::Battle)
+                .await
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn process_tick_advances_turn_after_action_submitted() {
         let engagements = Engagements::new();
@@ -3,10 +3,21 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum TurnAction {
-    ApplyEffect { effect_name: String },
-    SelectDialogChoice { choice: String },
-    SendMessage { content: String },
-    Respond { content: String },
+    ApplyEffect {
+        effect_name: String,
+        /// Entity the effect is aimed at. When `None` the handler picks a target.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target_entity_id: Option<i64>,
+    },
+    SelectDialogChoice {
+        choice: String,
+    },
+    SendMessage {
+        content: String,
+    },
+    Respond {
+        content: String,
+    },
 }
 
 #[cfg(test)]
@@ -17,12 +28,26 @@ mod tests {
     fn serde_apply_effect() {
         let action = TurnAction::ApplyEffect {
             effect_name: "fireball".to_string(),
+            target_entity_id: Some(7),
         };
         let json = serde_json::to_string(&action).unwrap();
         let restored: TurnAction = serde_json::from_str(&json).unwrap();
         assert_eq!(restored, action);
     }
 
+    #[test]
+    fn serde_apply_effect_without_target() {
+        let restored: TurnAction =
+            serde_json::from_str(r#"{"apply_effect":{"effect_name":"attack"}}"#).unwrap();
+        assert_eq!(
+            restored,
+            TurnAction::ApplyEffect {
+                effect_name: "attack".to_string(),
+                target_entity_id: None,
+            }
+        );
+    }
+
     #[test]
     fn serde_send_message() {
         let action = TurnAction::SendMessage {
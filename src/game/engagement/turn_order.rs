@@ -26,6 +26,21 @@ impl TurnOrder {
         self.current_index = (self.current_index + 1) % self.order.len();
     }
 
+    /// Remove an entity from the turn order. If it was the current entity, the turn passes to
+    /// the next entity in order.
+    pub fn remove(&mut self, entity_id: i64) {
+        let Some(index) = self.order.iter().position(|&id| id == entity_id) else {
+            return;
+        };
+        self.order.remove(index);
+        if index < self.current_index {
+            self.current_index -= 1;
+        }
+        if self.current_index >= self.order.len() {
+            self.current_index = 0;
+        }
+    }
+
     pub fn order(&self) -> &[i64] {
         &self.order
     }
@@ -71,6 +86,24 @@ mod tests {
         assert_eq!(turn_order.current(), None);
     }
 
+    #[test]
+    fn remove_before_current_keeps_current() {
+        let mut turn_order = TurnOrder::new(&[1, 2, 3]);
+        turn_order.advance();
+        turn_order.remove(1);
+        assert_eq!(turn_order.order(), &[2, 3]);
+        assert_eq!(turn_order.current(), Some(2));
+    }
+
+    #[test]
+    fn remove_current_passes_turn_to_next() {
+        let mut turn_order = TurnOrder::new(&[1, 2, 3]);
+        turn_order.advance();
+        turn_order.advance();
+        turn_order.remove(3);
+        assert_eq!(turn_order.current(), Some(1));
+    }
+
     #[test]
     fn advance_noop_when_empty() {
         let mut turn_order = TurnOrder::new(&[]);
@@ -2,12 +2,13 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::game::component::ActiveEffect;
 use crate::game::component::Attribute;
 use crate::game::component::Interaction;
 use crate::game::component::Location;
 use crate::game::entity_ai::EntityAI;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EntityType {
     Player,
     Character,
@@ -24,6 +25,24 @@ pub struct Entity {
     pub config_id: Option<String>,
     #[serde(default)]
     pub description: Option<String>,
+    #[serde(default)]
+    pub effects: Vec<ActiveEffect>,
+    /// Entity carrying this one. Carried entities share their holder's location but aren't
+    /// part of the room.
+    #[serde(default)]
+    pub holder_id: Option<i64>,
+    /// Whether the holder is wearing this entity rather than just carrying it.
+    #[serde(default)]
+    pub equipped: bool,
+    /// Uses left on a consumable. `None` means it hasn't been used yet.
+    #[serde(default)]
+    pub charges: Option<i64>,
+    /// Whether the entity's loot table has been rolled into it.
+    #[serde(default)]
+    pub loot_rolled: bool,
+    /// Unix time in milliseconds after which a spawned entity is removed from the world.
+    #[serde(default)]
+    pub despawn_at: Option<i64>,
     #[serde(skip)]
     pub ai: Option<EntityAI>,
 }
@@ -38,9 +57,38 @@ impl Entity {
             interactions: Vec::new(),
             config_id: None,
             description: None,
+            effects: Vec::new(),
+            holder_id: None,
+            equipped: false,
+            charges: None,
+            loot_rolled: false,
+            despawn_at: None,
             ai: None,
         }
     }
+
+    /// Short name derived from the config id, e.g. `entities/innkeeper` becomes `innkeeper`.
+    pub fn config_name(&self) -> Option<&str> {
+        let config_id = self.config_id.as_deref()?;
+        config_id.rsplit('/').next()
+    }
+
+    /// Name used when talking about the entity: the player's name for player entities, otherwise
+    /// the short config name, falling back to the entity type.
+    pub fn display_name(&self, player_names: &HashMap<i64, String>) -> String {
+        if let Some(name) = player_names.get(&self.id) {
+            return name.clone();
+        }
+        if let Some(name) = self.config_name() {
+            return name.to_string();
+        }
+        match self.entity_type {
+            EntityType::Character => "character",
+            EntityType::Object => "object",
+            EntityType::Player => "player",
+        }
+        .to_string()
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +111,23 @@ mod tests {
         assert_eq!(entity.location.dungeon_id, "d1");
         assert_eq!(entity.location.room_id, "r1");
     }
+
+    #[test]
+    fn config_name_uses_last_path_segment() {
+        let mut entity = Entity::new(1, EntityType::Character, test_location());
+        assert_eq!(entity.config_name(), None);
+        entity.config_id = Some("entities/town/innkeeper".to_string());
+        assert_eq!(entity.config_name(), Some("innkeeper"));
+    }
+
+    #[test]
+    fn display_name_prefers_player_name() {
+        let mut entity = Entity::new(1, EntityType::Character, test_location());
+        let mut names = HashMap::new();
+        assert_eq!(entity.display_name(&names), "character");
+        entity.config_id = Some("entities/innkeeper".to_string());
+        assert_eq!(entity.display_name(&names), "innkeeper");
+        names.insert(1, "Alice".to_string());
+        assert_eq!(entity.display_name(&names), "Alice");
+    }
 }
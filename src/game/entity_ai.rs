@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
+use crate::game::agent::ChatMessage;
 use crate::game::config::DialogLine;
 
 #[derive(Debug, Clone, Default)]
 pub struct ConversationContext {
     pub current_dialog: Option<DialogLine>,
+    /// 1-based choices that led from the dialog root to `current_dialog`.
+    pub path: Vec<usize>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -12,7 +15,22 @@ pub struct SimpleConversationState {
     pub contexts: HashMap<i64, ConversationContext>,
 }
 
+/// Chat history for one agent conversation.
+#[derive(Debug, Clone, Default)]
+pub struct AgentConversationContext {
+    pub history: Vec<ChatMessage>,
+    /// Whether a reply is still streaming. New input is turned away until it finishes, so
+    /// replies can't interleave or be recorded out of order.
+    pub replying: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AgentConversationState {
+    pub contexts: HashMap<i64, AgentConversationContext>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct EntityAI {
     pub simple_conversation_state: Option<SimpleConversationState>,
+    pub agent_conversation_state: Option<AgentConversationState>,
 }
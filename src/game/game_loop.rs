@@ -1,6 +1,7 @@
 pub mod attributes;
 pub mod effects;
 pub mod interactions;
+pub mod status;
 pub mod world_update;
 
 use std::sync::Arc;
@@ -23,12 +24,13 @@ pub async fn run(game_state: Arc<GameState>, db: Database) {
         ticker.tick().await;
 
         interactions::process(&game_state, &db, tick).await;
-        engagement::process(&game_state, tick).await;
-        effects::process(&game_state, tick).await;
-        attributes::process(&game_state, tick).await;
+        engagement::process(&game_state, &db, tick).await;
+        effects::process(&game_state, &db, tick).await;
+        attributes::process(&game_state, &db, tick).await;
+        status::process(&game_state, tick).await;
 
         if tick.is_multiple_of(world_update_ticks) {
-            world_update::process(&game_state, tick).await;
+            world_update::process(&game_state, &db, tick).await;
         }
 
         tick = tick.wrapping_add(1);
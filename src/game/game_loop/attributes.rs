@@ -1,7 +1,399 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::game::GameState;
+use crate::game::component::{Attribute, AttributeCategory, AttributeType};
+use crate::game::config::AttributeConfig;
+use crate::game::entity::{Entity, EntityType};
+use crate::game::game_loop::interactions::{container, inventory};
+use crate::game::{EngagementType, GameState, Location, messaging};
+use crate::persistence::{Database, entity_repo};
 
-pub async fn process(_game_state: &Arc<GameState>, tick: u64) {
+/// The changes produced by updating one entity's attributes for a single tick.
+#[derive(Debug)]
+struct AttributeOutcome {
+    entity_id: i64,
+    attributes: HashMap<String, Attribute>,
+    respawned_from: Option<Location>,
+    levelled_to: Option<i64>,
+}
+
+/// Drive attribute behaviour for every active entity.
+///
+/// Each tick the attribute system:
+/// 1. Clamps every attribute's current value to its min/max.
+/// 2. Applies the [`crate::game::Regeneration`] configured in `attributes.toml` whenever its
+///    interval elapses, unless the entity is defeated.
+/// 3. Treats any entity whose [`AttributeCategory::Life`] attribute is at its minimum as
+///    defeated. Defeated players are told, have their life attributes restored and are moved to
+///    the spawn location, leaving any battle they were in. Other defeated entities are left as
+///    they are, and characters with a loot table have it rolled into their corpse.
+/// 4. Raises the level of any player whose XP has crossed a threshold of the
+///    [`crate::game::config::LevellingConfig`], applying its increases and telling them.
+///
+/// Changed attributes and locations are written back to the database.
+pub async fn process(game_state: &Arc<GameState>, db: &Database, tick: u64) {
     tracing::debug!("Processing attributes tick={tick}");
+
+    let tick_rate_ms = game_state.mud_config.game_loop.tick_rate_ms;
+    let spawn = game_state.mud_config.spawn.location();
+    let players: HashMap<i64, i64> = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .map(|p| (p.entity_id, p.id))
+        .collect();
+
+    let outcomes: Vec<AttributeOutcome> = {
+        let mut entities = game_state.active_entities.write().await;
+        entities
+            .values_mut()
+            .filter_map(|entity| {
+                let mut changed =
+                    update_attributes(entity, &game_state.attribute_config, tick, tick_rate_ms);
+                let mut respawned_from = None;
+                if players.contains_key(&entity.id)
+                    && is_defeated(entity, &game_state.attribute_config)
+                {
+                    restore_life(entity, &game_state.attribute_config);
+                    respawned_from = Some(std::mem::replace(&mut entity.location, spawn.clone()));
+                    changed = true;
+                }
+                let levelled_to = players
+                    .contains_key(&entity.id)
+                    .then(|| level_up(entity, &game_state.attribute_config))
+                    .flatten();
+                changed |= levelled_to.is_some();
+                changed.then(|| AttributeOutcome {
+                    entity_id: entity.id,
+                    attributes: entity.attributes.clone(),
+                    respawned_from,
+                    levelled_to,
+                })
+            })
+            .collect()
+    };
+
+    let mut dungeon_changed = false;
+    for outcome in outcomes {
+        if let Err(e) =
+            entity_repo::update_attributes(db.pool(), outcome.entity_id, &outcome.attributes).await
+        {
+            tracing::error!(error = %e, "Failed to persist attributes");
+        }
+        if let Some(level) = outcome.levelled_to
+            && let Some(&player_id) = players.get(&outcome.entity_id)
+        {
+            messaging::message(
+                &game_state.message_tx,
+                player_id,
+                format!("You have reached level {level}!"),
+            );
+        }
+        let Some(old_location) = outcome.respawned_from else {
+            continue;
+        };
+        if let Err(e) = entity_repo::update_location(db.pool(), outcome.entity_id, &spawn).await {
+            tracing::error!(error = %e, "Failed to move defeated entity to spawn");
+        }
+        inventory::move_carried(game_state, db, outcome.entity_id, &spawn).await;
+        if let Some(engagement_id) = game_state
+            .engagements
+            .find_for_entity(outcome.entity_id, EngagementType::Battle)
+            .await
+        {
+            game_state
+                .engagements
+                .remove_entity(engagement_id, outcome.entity_id)
+                .await;
+        }
+        dungeon_changed |=
+            old_location.world_id != spawn.world_id || old_location.dungeon_id != spawn.dungeon_id;
+        if let Some(&player_id) = players.get(&outcome.entity_id) {
+            messaging::message(&game_state.message_tx, player_id, "You have been defeated!");
+            messaging::message(
+                &game_state.message_tx,
+                player_id,
+                "You come to your senses somewhere familiar.",
+            );
+        }
+    }
+
+    let corpses: Vec<i64> = {
+        let entities = game_state.active_entities.read().await;
+        entities
+            .values()
+            .filter(|e| e.entity_type == EntityType::Character && !e.loot_rolled)
+            .filter(|e| {
+                e.config_id
+                    .as_deref()
+                    .and_then(|id| game_state.entity_configs.get(id))
+                    .is_some_and(|config| config.loot.is_some())
+            })
+            .filter(|e| is_defeated(e, &game_state.attribute_config))
+            .map(|e| e.id)
+            .collect()
+    };
+    for corpse_id in corpses {
+        container::roll_loot(game_state, db, corpse_id).await;
+    }
+
+    if dungeon_changed && let Err(e) = game_state.sync_active_entities(db.pool()).await {
+        tracing::error!(error = %e, "Failed to sync active entities after respawn");
+    }
+}
+
+/// Add `amount` XP to an entity, telling its player. Any level this earns is applied by the next
+/// attribute update.
+pub async fn award_xp(game_state: &Arc<GameState>, db: &Database, entity_id: i64, amount: i64) {
+    let Some(xp_id) = game_state
+        .attribute_config
+        .find_type(AttributeType::XP)
+        .map(|d| d.id.clone())
+    else {
+        return;
+    };
+    if amount <= 0 {
+        return;
+    }
+    let attributes = {
+        let mut entities = game_state.active_entities.write().await;
+        entities.get_mut(&entity_id).and_then(|entity| {
+            let changed = entity.attributes.get_mut(&xp_id)?.adjust(amount);
+            changed.then(|| entity.attributes.clone())
+        })
+    };
+    let Some(attributes) = attributes else {
+        return;
+    };
+    if let Err(e) = entity_repo::update_attributes(db.pool(), entity_id, &attributes).await {
+        tracing::error!(error = %e, "Failed to persist awarded XP");
+    }
+    let player_id = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .find(|p| p.entity_id == entity_id)
+        .map(|p| p.id);
+    if let Some(player_id) = player_id {
+        messaging::message(
+            &game_state.message_tx,
+            player_id,
+            format!("You gain {amount} experience."),
+        );
+    }
+}
+
+/// Raise the entity's level to the one its XP is worth, applying the configured increases once
+/// per level gained. Returns the new level if it went up.
+fn level_up(entity: &mut Entity, config: &AttributeConfig) -> Option<i64> {
+    let levelling = config.levelling.as_ref()?;
+    let xp_id = &config.find_type(AttributeType::XP)?.id;
+    let level_id = &config.find_type(AttributeType::Level)?.id;
+    let xp = entity.attributes.get(xp_id)?.current_value;
+    let level = entity.attributes.get_mut(level_id)?;
+    let new_level = levelling.level_for(xp).min(level.max_value);
+    let gained = new_level - level.current_value;
+    if gained <= 0 {
+        return None;
+    }
+    level.current_value = new_level;
+
+    for increase in &levelling.increases {
+        let Some(attribute) = entity.attributes.get_mut(&increase.attribute_id) else {
+            continue;
+        };
+        let cap = config
+            .find(&increase.attribute_id)
+            .map_or(i64::MAX, |d| d.max_value);
+        attribute.max_value = attribute
+            .max_value
+            .saturating_add(increase.max_value.saturating_mul(gained))
+            .min(cap);
+        attribute.adjust(increase.value.saturating_mul(gained));
+    }
+    Some(new_level)
+}
+
+/// Clamp and regenerate an entity's attributes. Returns true if any value changed.
+fn update_attributes(
+    entity: &mut Entity,
+    config: &AttributeConfig,
+    tick: u64,
+    tick_rate_ms: u64,
+) -> bool {
+    let defeated = is_defeated(entity, config);
+    let mut changed = false;
+    for attribute in entity.attributes.values_mut() {
+        changed |= attribute.adjust(0);
+        if defeated {
+            continue;
+        }
+        let Some(regeneration) = config
+            .find(&attribute.definition_id)
+            .and_then(|d| d.regeneration.as_ref())
+        else {
+            continue;
+        };
+        let interval_ticks = (regeneration.interval_ms / tick_rate_ms.max(1)).max(1);
+        if tick.is_multiple_of(interval_ticks) {
+            changed |= attribute.adjust(regeneration.amount);
+        }
+    }
+    changed
+}
+
+/// Returns true if any of the entity's life attributes has reached its minimum.
+pub fn is_defeated(entity: &Entity, config: &AttributeConfig) -> bool {
+    life_attribute_ids(entity, config)
+        .iter()
+        .filter_map(|id| entity.attributes.get(id))
+        .any(|a| a.current_value <= a.min_value)
+}
+
+/// Returns true if the entity has a life attribute, and so can be fought and defeated.
+pub fn has_life(entity: &Entity, config: &AttributeConfig) -> bool {
+    !life_attribute_ids(entity, config).is_empty()
+}
+
+fn restore_life(entity: &mut Entity, config: &AttributeConfig) {
+    for id in life_attribute_ids(entity, config) {
+        if let Some(attribute) = entity.attributes.get_mut(&id) {
+            attribute.current_value = attribute.max_value;
+        }
+    }
+}
+
+fn life_attribute_ids(entity: &Entity, config: &AttributeConfig) -> HashSet<String> {
+    entity
+        .attributes
+        .keys()
+        .filter(|id| {
+            config
+                .find(id)
+                .is_some_and(|d| d.attribute_category == AttributeCategory::Life)
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_entity(hp: i64, mp: i64) -> Entity {
+        let mut entity = Entity::new(
+            1,
+            EntityType::Player,
+            Location {
+                world_id: "w1".to_string(),
+                dungeon_id: "d1".to_string(),
+                room_id: "r1".to_string(),
+            },
+        );
+        entity.attributes.insert(
+            "hp".to_string(),
+            Attribute::new("hp".to_string(), 0, 100, hp),
+        );
+        entity.attributes.insert(
+            "mp".to_string(),
+            Attribute::new("mp".to_string(), 0, 50, mp),
+        );
+        entity
+    }
+
+    #[test]
+    fn regenerates_on_interval() {
+        let config = AttributeConfig::default_config();
+        let mut entity = make_entity(10, 10);
+
+        // Default hp regen is every 5s, mp every 10s; at 1s per tick tick 5 only regens hp.
+        assert!(update_attributes(&mut entity, &config, 5, 1000));
+        assert_eq!(entity.attributes["hp"].current_value, 11);
+        assert_eq!(entity.attributes["mp"].current_value, 10);
+
+        assert!(!update_attributes(&mut entity, &config, 6, 1000));
+    }
+
+    #[test]
+    fn clamps_out_of_range_values() {
+        let config = AttributeConfig::default_config();
+        let mut entity = make_entity(150, 10);
+        assert!(update_attributes(&mut entity, &config, 1, 1000));
+        assert_eq!(entity.attributes["hp"].current_value, 100);
+    }
+
+    #[test]
+    fn defeated_entity_does_not_regenerate() {
+        let config = AttributeConfig::default_config();
+        let mut entity = make_entity(0, 10);
+        assert!(is_defeated(&entity, &config));
+        update_attributes(&mut entity, &config, 0, 1000);
+        assert_eq!(entity.attributes["hp"].current_value, 0);
+        assert_eq!(entity.attributes["mp"].current_value, 10);
+    }
+
+    #[test]
+    fn only_entities_with_life_attributes_have_life() {
+        let config = AttributeConfig::default_config();
+        let mut entity = make_entity(0, 10);
+        assert!(has_life(&entity, &config));
+        entity.attributes.remove("hp");
+        assert!(!has_life(&entity, &config));
+        assert!(!is_defeated(&entity, &config));
+    }
+
+    #[test]
+    fn level_up_applies_increases_per_level_gained() {
+        let config = AttributeConfig::default_config();
+        let mut entity = make_entity(40, 10);
+        for (id, min, max, value) in [("level", 1, 100, 1), ("xp", 0, i64::MAX, 350)] {
+            entity.attributes.insert(
+                id.to_string(),
+                Attribute::new(id.to_string(), min, max, value),
+            );
+        }
+        entity.attributes.insert(
+            "strength".to_string(),
+            Attribute::new("strength".to_string(), 1, 20, 10),
+        );
+
+        // 350 XP is past the 100 and 300 thresholds: two levels at once.
+        assert_eq!(level_up(&mut entity, &config), Some(3));
+        assert_eq!(entity.attributes["level"].current_value, 3);
+        assert_eq!(entity.attributes["hp"].max_value, 120);
+        assert_eq!(entity.attributes["hp"].current_value, 60);
+        assert_eq!(entity.attributes["strength"].current_value, 12);
+        // Strength's maximum comes from its definition and isn't raised.
+        assert_eq!(entity.attributes["strength"].max_value, 20);
+
+        assert_eq!(level_up(&mut entity, &config), None);
+    }
+
+    #[test]
+    fn level_up_needs_a_curve() {
+        let mut config = AttributeConfig::default_config();
+        config.levelling = None;
+        let mut entity = make_entity(40, 10);
+        entity.attributes.insert(
+            "xp".to_string(),
+            Attribute::new("xp".to_string(), 0, i64::MAX, 1_000),
+        );
+        entity.attributes.insert(
+            "level".to_string(),
+            Attribute::new("level".to_string(), 1, 100, 1),
+        );
+        assert_eq!(level_up(&mut entity, &config), None);
+    }
+
+    #[test]
+    fn restore_life_only_resets_life_attributes() {
+        let config = AttributeConfig::default_config();
+        let mut entity = make_entity(0, 5);
+        restore_life(&mut entity, &config);
+        assert!(!is_defeated(&entity, &config));
+        assert_eq!(entity.attributes["hp"].current_value, 100);
+        assert_eq!(entity.attributes["mp"].current_value, 5);
+    }
 }
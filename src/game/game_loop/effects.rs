@@ -1,7 +1,619 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::game::GameState;
+use crate::game::component::{ActiveEffect, Attribute, Effect, EffectType};
+use crate::game::entity::Entity;
+use crate::game::game_loop::attributes::award_xp;
+use crate::game::game_loop::interactions::{look, warp};
+use crate::game::game_state::now_ms;
+use crate::game::map::Navigation;
+use crate::game::{EngagementType, GameState, Location, messaging};
+use crate::persistence::{Database, PersistenceError, entity_effect_repo, entity_repo};
 
-pub async fn process(_game_state: &Arc<GameState>, tick: u64) {
+/// The changes produced by running one entity's effects for a single tick.
+#[derive(Debug, Default)]
+struct EffectOutcome {
+    entity_id: i64,
+    attributes: Option<HashMap<String, Attribute>>,
+    messages: Vec<String>,
+    /// Elapsed ticks of every effect still running, saved each tick so delayed and slow effects
+    /// pick up where they left off after a restart.
+    progress: Vec<(i64, u64)>,
+    expired: Vec<i64>,
+    warps: Vec<Navigation>,
+    spawns: Vec<Spawn>,
+    xp: i64,
+}
+
+/// An entity to create once the tick's effects have run.
+#[derive(Debug)]
+struct Spawn {
+    config_id: String,
+    location: Location,
+    lifetime_ms: Option<u64>,
+}
+
+/// Run the effects attached to every active entity for the current tick.
+///
+/// Each effect is applied when its [`crate::game::TriggerInfo`] fires. Attribute updates are
+/// clamped to the attribute's min/max. The start description is sent to the affected player
+/// on the effect's first tick and the end description when it expires. Attribute changes,
+/// effect progress (recorded every tick) and expirations are written back to the
+/// database so they survive the entity being reloaded. Warp effects move the entity once the
+/// tick's effects have run, and players look around their new room. Spawn effects then create
+/// their entities, experience effects award XP, and spawned entities whose lifetime has run out
+/// are removed.
+pub async fn process(game_state: &Arc<GameState>, db: &Database, tick: u64) {
     tracing::debug!("Processing effects tick={tick}");
+
+    let outcomes: Vec<EffectOutcome> = {
+        let mut entities = game_state.active_entities.write().await;
+        entities
+            .values_mut()
+            .filter(|e| !e.effects.is_empty())
+            .map(run_effects)
+            .collect()
+    };
+
+    for outcome in outcomes {
+        persist_outcome(db, &outcome).await;
+        let player = game_state
+            .active_players
+            .read()
+            .await
+            .values()
+            .find(|p| p.entity_id == outcome.entity_id)
+            .cloned();
+        if let Some(player) = &player {
+            for content in &outcome.messages {
+                messaging::message(&game_state.message_tx, player.id, content.clone());
+            }
+        }
+        for destination in &outcome.warps {
+            let warped = warp::warp_entity(game_state, db, outcome.entity_id, destination)
+                .await
+                .is_some();
+            if warped && let Some(player) = &player {
+                look::process(game_state, db, player).await;
+            }
+        }
+        for spawn in &outcome.spawns {
+            spawn_entity(game_state, db, spawn).await;
+        }
+        award_xp(game_state, db, outcome.entity_id, outcome.xp).await;
+    }
+
+    despawn_expired(game_state, db).await;
+}
+
+async fn spawn_entity(game_state: &Arc<GameState>, db: &Database, spawn: &Spawn) {
+    let spawned = game_state
+        .spawn_entity(
+            db.pool(),
+            &spawn.config_id,
+            &spawn.location,
+            None,
+            spawn.lifetime_ms,
+        )
+        .await;
+    match spawned {
+        Ok(Some(id)) => {
+            let name = spawn.config_id.rsplit('/').next().unwrap_or("something");
+            messaging::message_location(
+                game_state,
+                &spawn.location,
+                id,
+                format!("A {name} appears."),
+            )
+            .await;
+        }
+        Ok(None) => {}
+        Err(e) => tracing::error!(error = %e, "Failed to spawn entity"),
+    }
+}
+
+/// Remove active entities whose lifetime has run out.
+async fn despawn_expired(game_state: &Arc<GameState>, db: &Database) {
+    let now = now_ms();
+    let expired: Vec<i64> = game_state
+        .active_entities
+        .read()
+        .await
+        .values()
+        .filter(|e| e.despawn_at.is_some_and(|at| at <= now))
+        .map(|e| e.id)
+        .collect();
+    for id in expired {
+        despawn(game_state, db, id).await;
+    }
+}
+
+/// Remove a spawned entity from the world. Whatever it carried drops to the floor, and if it
+/// was worn its modifiers come off the wearer.
+pub async fn despawn(game_state: &Arc<GameState>, db: &Database, entity_id: i64) {
+    let names = game_state.player_names().await;
+    // Items despawned from unloaded dungeons are only in the database, but may still be worn.
+    let active = game_state
+        .active_entities
+        .read()
+        .await
+        .contains_key(&entity_id);
+    let stored = if active {
+        None
+    } else {
+        match entity_repo::find_by_id(db.pool(), entity_id).await {
+            Ok(stored) => stored,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load despawned entity");
+                None
+            }
+        }
+    };
+    let (removed, wearer) = {
+        let mut entities = game_state.active_entities.write().await;
+        let wearer_id = entities
+            .get(&entity_id)
+            .or(stored.as_ref())
+            .filter(|e| e.equipped)
+            .and_then(|e| e.holder_id);
+        let wearer = wearer_id.map(|wearer_id| {
+            let attributes = entities.get_mut(&wearer_id).map(|wearer| {
+                for attribute in wearer.attributes.values_mut() {
+                    attribute.remove_modifier(entity_id);
+                }
+                wearer.attributes.clone()
+            });
+            (wearer_id, attributes)
+        });
+        for item in entities
+            .values_mut()
+            .filter(|e| e.holder_id == Some(entity_id))
+        {
+            item.holder_id = None;
+            item.equipped = false;
+        }
+        let removed = entities
+            .remove(&entity_id)
+            .map(|e| (e.location.clone(), e.display_name(&names)));
+        (removed, wearer)
+    };
+    if let Some((wearer_id, attributes)) = wearer {
+        strip_modifiers(db, wearer_id, entity_id, attributes).await;
+    }
+    if let Some(engagement_id) = game_state
+        .engagements
+        .find_for_entity(entity_id, EngagementType::Battle)
+        .await
+    {
+        game_state
+            .engagements
+            .remove_entity(engagement_id, entity_id)
+            .await;
+    }
+    if let Some(engagement_id) = game_state
+        .engagements
+        .find_for_entity(entity_id, EngagementType::Conversation)
+        .await
+    {
+        game_state.engagements.remove(engagement_id).await;
+    }
+
+    let pool = db.pool();
+    match entity_repo::find_held_by(pool, entity_id).await {
+        Ok(held) => {
+            for item in held {
+                if let Err(e) = entity_repo::update_holder(pool, item.id, None).await {
+                    tracing::error!(error = %e, "Failed to drop despawned entity's items");
+                }
+                if let Err(e) = entity_repo::update_equipped(pool, item.id, false).await {
+                    tracing::error!(error = %e, "Failed to drop despawned entity's items");
+                }
+            }
+        }
+        Err(e) => tracing::error!(error = %e, "Failed to load despawned entity's items"),
+    }
+    if let Err(e) = entity_effect_repo::delete_by_entity(pool, entity_id).await {
+        tracing::error!(error = %e, "Failed to delete despawned entity's effects");
+    }
+    if let Err(e) = entity_repo::delete(pool, entity_id).await {
+        tracing::error!(error = %e, "Failed to delete despawned entity");
+    }
+
+    if let Some((location, name)) = removed {
+        messaging::message_location(
+            game_state,
+            &location,
+            entity_id,
+            format!("The {name} fades away."),
+        )
+        .await;
+    }
+}
+
+/// Persist a wearer's attributes after `item_id`'s modifiers came off. Wearers who aren't active
+/// are stripped straight in the database.
+async fn strip_modifiers(
+    db: &Database,
+    wearer_id: i64,
+    item_id: i64,
+    attributes: Option<HashMap<String, Attribute>>,
+) {
+    let attributes = match attributes {
+        Some(attributes) => attributes,
+        None => match entity_repo::find_by_id(db.pool(), wearer_id).await {
+            Ok(Some(mut wearer)) => {
+                for attribute in wearer.attributes.values_mut() {
+                    attribute.remove_modifier(item_id);
+                }
+                wearer.attributes
+            }
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load wearer of despawned item");
+                return;
+            }
+        },
+    };
+    if let Err(e) = entity_repo::update_attributes(db.pool(), wearer_id, &attributes).await {
+        tracing::error!(error = %e, "Failed to remove despawned item's modifiers");
+    }
+}
+
+/// Attach an effect to an active entity and persist it. It starts running on the next effects
+/// pass.
+pub async fn attach(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    entity_id: i64,
+    effect: &Effect,
+) -> Result<(), PersistenceError> {
+    let id = entity_effect_repo::insert(db.pool(), entity_id, effect).await?;
+    if let Some(entity) = game_state.active_entities.write().await.get_mut(&entity_id) {
+        entity.effects.push(ActiveEffect {
+            id,
+            effect: effect.clone(),
+            elapsed_ticks: 0,
+        });
+    }
+    Ok(())
+}
+
+/// Apply a single effect to an entity. Returns true if the entity's attributes changed.
+pub fn apply_effect(entity: &mut Entity, effect_type: &EffectType) -> bool {
+    match effect_type {
+        EffectType::AttributeUpdate {
+            attribute_id,
+            value,
+        } => match entity.attributes.get_mut(attribute_id) {
+            Some(attribute) => attribute.adjust(*value),
+            None => {
+                tracing::debug!(
+                    entity_id = entity.id,
+                    attribute_id,
+                    "effect targets missing attribute"
+                );
+                false
+            }
+        },
+        // Spawns, warps and experience need the database, so the effects system performs them
+        // after the tick.
+        EffectType::EntitySpawn { .. }
+        | EffectType::Warp { .. }
+        | EffectType::Experience { .. } => false,
+    }
+}
+
+fn run_effects(entity: &mut Entity) -> EffectOutcome {
+    let mut outcome = EffectOutcome {
+        entity_id: entity.id,
+        ..Default::default()
+    };
+    let mut attributes_changed = false;
+    let mut remaining = Vec::new();
+
+    for mut active in std::mem::take(&mut entity.effects) {
+        let elapsed = active.elapsed_ticks;
+        let trigger = active.effect.trigger_info.clone();
+
+        if elapsed == 0
+            && let Some(text) = &active.effect.description.start_description
+        {
+            outcome.messages.push(text.clone());
+        }
+
+        let fired = trigger.fires_at(elapsed);
+        if fired {
+            match &active.effect.effect_type {
+                EffectType::Warp { destination } => outcome.warps.push(destination.clone()),
+                EffectType::EntitySpawn {
+                    entity_id,
+                    location,
+                    lifetime_ms,
+                } => outcome.spawns.push(Spawn {
+                    config_id: entity_id.clone(),
+                    location: location.clone().unwrap_or_else(|| entity.location.clone()),
+                    lifetime_ms: *lifetime_ms,
+                }),
+                EffectType::Experience { amount } => outcome.xp += amount,
+                EffectType::AttributeUpdate { .. } => {}
+            }
+            attributes_changed |= apply_effect(entity, &active.effect.effect_type);
+        }
+
+        if trigger.expires_at(elapsed) {
+            if let Some(text) = &active.effect.description.end_description {
+                outcome.messages.push(text.clone());
+            }
+            outcome.expired.push(active.id);
+        } else {
+            active.elapsed_ticks += 1;
+            outcome.progress.push((active.id, active.elapsed_ticks));
+            remaining.push(active);
+        }
+    }
+
+    entity.effects = remaining;
+    if attributes_changed {
+        outcome.attributes = Some(entity.attributes.clone());
+    }
+    outcome
+}
+
+async fn persist_outcome(db: &Database, outcome: &EffectOutcome) {
+    let pool = db.pool();
+    if let Some(attributes) = &outcome.attributes
+        && let Err(e) = entity_repo::update_attributes(pool, outcome.entity_id, attributes).await
+    {
+        tracing::error!(error = %e, "Failed to persist attributes after effect");
+    }
+    if let Err(e) = entity_effect_repo::update_elapsed_ticks_many(pool, &outcome.progress).await {
+        tracing::error!(error = %e, "Failed to persist effect progress");
+    }
+    for id in &outcome.expired {
+        if let Err(e) = entity_effect_repo::delete(pool, *id).await {
+            tracing::error!(error = %e, "Failed to delete expired effect");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::{EffectDescription, TriggerInfo};
+    use crate::game::{EntityType, Location};
+    use crate::persistence::fixtures::insert_room;
+
+    fn make_entity() -> Entity {
+        let mut entity = Entity::new(
+            1,
+            EntityType::Character,
+            Location {
+                world_id: "w1".to_string(),
+                dungeon_id: "d1".to_string(),
+                room_id: "r1".to_string(),
+            },
+        );
+        entity.attributes.insert(
+            "hp".to_string(),
+            Attribute::new("hp".to_string(), 0, 100, 50),
+        );
+        entity
+    }
+
+    fn make_active(id: i64, value: i64, trigger_info: TriggerInfo) -> ActiveEffect {
+        ActiveEffect {
+            id,
+            effect: Effect {
+                name: "test".to_string(),
+                effect_type: EffectType::AttributeUpdate {
+                    attribute_id: "hp".to_string(),
+                    value,
+                },
+                trigger_info,
+                description: EffectDescription {
+                    start_description: Some("It begins.".to_string()),
+                    end_description: Some("It ends.".to_string()),
+                },
+            },
+            elapsed_ticks: 0,
+        }
+    }
+
+    #[test]
+    fn once_effect_applies_and_expires() {
+        let mut entity = make_entity();
+        entity.effects.push(make_active(7, 20, TriggerInfo::Once));
+
+        let outcome = run_effects(&mut entity);
+        assert_eq!(entity.attributes["hp"].current_value, 70);
+        assert!(entity.effects.is_empty());
+        assert_eq!(outcome.expired, vec![7]);
+        assert_eq!(outcome.messages, vec!["It begins.", "It ends."]);
+        assert!(outcome.attributes.is_some());
+    }
+
+    #[test]
+    fn over_time_effect_applies_on_schedule_and_clamps() {
+        let mut entity = make_entity();
+        entity.effects.push(make_active(
+            3,
+            -30,
+            TriggerInfo::OverTime {
+                start: 0,
+                end: Some(2),
+                rate: 1,
+            },
+        ));
+
+        let first = run_effects(&mut entity);
+        assert_eq!(entity.attributes["hp"].current_value, 20);
+        assert_eq!(first.progress, vec![(3, 1)]);
+        assert_eq!(first.messages, vec!["It begins."]);
+
+        run_effects(&mut entity);
+        assert_eq!(entity.attributes["hp"].current_value, 0);
+
+        let last = run_effects(&mut entity);
+        assert_eq!(entity.attributes["hp"].current_value, 0);
+        assert!(last.attributes.is_none());
+        assert_eq!(last.expired, vec![3]);
+        assert_eq!(last.messages, vec!["It ends."]);
+        assert!(entity.effects.is_empty());
+    }
+
+    #[test]
+    fn delayed_effect_records_progress_before_it_fires() {
+        let mut entity = make_entity();
+        entity.effects.push(make_active(
+            5,
+            -10,
+            TriggerInfo::OverTime {
+                start: 3,
+                end: None,
+                rate: 1,
+            },
+        ));
+
+        let first = run_effects(&mut entity);
+        assert_eq!(first.progress, vec![(5, 1)]);
+        assert!(first.attributes.is_none());
+
+        let second = run_effects(&mut entity);
+        assert_eq!(second.progress, vec![(5, 2)]);
+        assert!(second.messages.is_empty());
+        assert_eq!(entity.attributes["hp"].current_value, 50);
+    }
+
+    #[test]
+    fn warp_effect_is_queued_for_the_tick() {
+        let mut entity = make_entity();
+        let mut active = make_active(5, 0, TriggerInfo::Once);
+        let destination = Navigation {
+            room_id: Some("r2".to_string()),
+            ..Default::default()
+        };
+        active.effect.effect_type = EffectType::Warp {
+            destination: destination.clone(),
+        };
+        entity.effects.push(active);
+
+        let outcome = run_effects(&mut entity);
+        assert_eq!(outcome.warps, vec![destination]);
+        assert!(outcome.attributes.is_none());
+    }
+
+    #[test]
+    fn experience_effects_add_up_for_the_tick() {
+        let mut entity = make_entity();
+        for id in [1, 2] {
+            let mut active = make_active(id, 0, TriggerInfo::Once);
+            active.effect.effect_type = EffectType::Experience { amount: 15 };
+            entity.effects.push(active);
+        }
+
+        let outcome = run_effects(&mut entity);
+        assert_eq!(outcome.xp, 30);
+        assert!(outcome.attributes.is_none());
+    }
+
+    #[test]
+    fn effect_on_missing_attribute_is_ignored() {
+        let mut entity = make_entity();
+        let changed = apply_effect(
+            &mut entity,
+            &EffectType::AttributeUpdate {
+                attribute_id: "mp".to_string(),
+                value: 5,
+            },
+        );
+        assert!(!changed);
+        assert_eq!(entity.attributes["hp"].current_value, 50);
+    }
+
+    #[test]
+    fn spawn_effect_defaults_to_entity_location() {
+        let mut entity = make_entity();
+        let mut active = make_active(6, 0, TriggerInfo::Once);
+        active.effect.effect_type = EffectType::EntitySpawn {
+            entity_id: "entities/skeleton".to_string(),
+            location: None,
+            lifetime_ms: Some(30_000),
+        };
+        entity.effects.push(active);
+
+        let outcome = run_effects(&mut entity);
+        assert_eq!(outcome.spawns.len(), 1);
+        assert_eq!(outcome.spawns[0].config_id, "entities/skeleton");
+        assert_eq!(outcome.spawns[0].location, entity.location);
+        assert_eq!(outcome.spawns[0].lifetime_ms, Some(30_000));
+        assert_eq!(outcome.expired, vec![6]);
+    }
+
+    /// A wearer and an item worn by them, with the item's modifier on the wearer's hp, stored
+    /// in a fresh database.
+    async fn worn_item_in_db() -> (Database, Entity, Entity) {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+        insert_room(&db).await;
+        let mut wearer = make_entity();
+        wearer.id = entity_repo::insert(pool, &wearer).await.unwrap();
+        let mut item = Entity::new(0, EntityType::Object, wearer.location.clone());
+        item.holder_id = Some(wearer.id);
+        item.equipped = true;
+        item.id = entity_repo::insert(pool, &item).await.unwrap();
+        entity_repo::update_equipped(pool, item.id, true)
+            .await
+            .unwrap();
+        wearer
+            .attributes
+            .get_mut("hp")
+            .unwrap()
+            .set_modifier(item.id, 10);
+        entity_repo::update_attributes(pool, wearer.id, &wearer.attributes)
+            .await
+            .unwrap();
+        (db, wearer, item)
+    }
+
+    async fn assert_stripped_and_deleted(db: &Database, wearer_id: i64, item_id: i64) {
+        let stored = entity_repo::find_by_id(db.pool(), wearer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.attributes["hp"].modifiers.is_empty());
+        assert!(
+            entity_repo::find_by_id(db.pool(), item_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn despawning_a_worn_item_removes_its_modifiers() {
+        let (db, wearer, item) = worn_item_in_db().await;
+        let game_state = Arc::new(GameState::load(None).unwrap());
+        {
+            let mut entities = game_state.active_entities.write().await;
+            entities.insert(wearer.id, wearer.clone());
+            entities.insert(item.id, item.clone());
+        }
+
+        despawn(&game_state, &db, item.id).await;
+
+        let hp = game_state.active_entities.read().await[&wearer.id].attributes["hp"].clone();
+        assert!(hp.modifiers.is_empty());
+        assert_eq!(hp.effective_value(), 50);
+        assert_stripped_and_deleted(&db, wearer.id, item.id).await;
+    }
+
+    #[tokio::test]
+    async fn despawning_an_unloaded_worn_item_strips_the_stored_wearer() {
+        let (db, wearer, item) = worn_item_in_db().await;
+        let game_state = Arc::new(GameState::load(None).unwrap());
+
+        despawn(&game_state, &db, item.id).await;
+
+        assert_stripped_and_deleted(&db, wearer.id, item.id).await;
+    }
 }
@@ -1,7 +1,22 @@
+mod battle;
+mod channel;
+mod chat;
+mod consumable;
+pub mod container;
 pub mod conversation;
+mod equipment;
 mod help;
-mod look;
+pub mod inventory;
+pub mod look;
+pub mod mail;
 mod movement;
+mod object;
+mod portal;
+mod search;
+mod stats;
+pub mod target;
+mod tell;
+pub mod warp;
 
 use std::sync::Arc;
 
@@ -9,7 +24,7 @@ use tracing;
 
 use crate::game::component::interaction::Movement;
 use crate::game::player::Player;
-use crate::game::{GameState, Interaction};
+use crate::game::{EngagementType, GameState, Interaction, TurnAction, messaging};
 use crate::persistence::Database;
 
 pub async fn process(game_state: &Arc<GameState>, db: &Database, tick: u64) {
@@ -36,22 +51,155 @@ pub async fn process(game_state: &Arc<GameState>, db: &Database, tick: u64) {
                 Interaction::Movement(Movement::TryDirection(direction)) => {
                     movement::process(game_state, db, &player, direction).await;
                 }
-                Interaction::Movement(Movement::Warp(_)) => {}
+                Interaction::Movement(Movement::Warp(navigation)) => {
+                    warp::process(game_state, db, &player, &navigation).await;
+                }
+                Interaction::Movement(Movement::Enter(name)) => {
+                    movement::enter(game_state, db, &player, &name).await;
+                }
                 Interaction::EngagementAction(action) => {
-                    let accepted = game_state
-                        .engagements
-                        .submit_action_for_entity(player.entity_id, action)
-                        .await;
-                    tracing::debug!(
-                        entity_id = player.entity_id,
-                        accepted,
-                        "engagement action submitted"
-                    );
+                    submit_engagement_action(game_state, db, &player, action).await;
                 }
                 Interaction::StartConversation => {
                     conversation::process(game_state, &player).await;
                 }
+                Interaction::Attack(target) => {
+                    battle::attack(game_state, &player, &target).await;
+                }
+                Interaction::Flee => {
+                    battle::flee(game_state, db, &player).await;
+                }
+                Interaction::Say(content) => {
+                    chat::say(game_state, &player, &content).await;
+                }
+                Interaction::Emote(content) => {
+                    chat::emote(game_state, &player, &content).await;
+                }
+                Interaction::Whisper { target, content } => {
+                    chat::whisper(game_state, &player, &target, &content).await;
+                }
+                Interaction::Shout(content) => {
+                    chat::shout(game_state, &player, &content).await;
+                }
+                Interaction::ChannelPost { channel, content } => {
+                    channel::post(game_state, db, &player, &channel, &content).await;
+                }
+                Interaction::ChannelJoin(channel) => {
+                    channel::set_joined(game_state, db, &player, &channel, true).await;
+                }
+                Interaction::ChannelLeave(channel) => {
+                    channel::set_joined(game_state, db, &player, &channel, false).await;
+                }
+                Interaction::Channels => {
+                    channel::list(game_state, db, &player).await;
+                }
+                Interaction::Tell { target, content } => {
+                    tell::tell(game_state, db, &player, &target, &content).await;
+                }
+                Interaction::Reply(content) => {
+                    tell::reply(game_state, db, &player, &content).await;
+                }
+                Interaction::Mail { target, content } => {
+                    mail::send(game_state, db, &player, &target, &content).await;
+                }
+                Interaction::ReadMail => {
+                    mail::read(game_state, db, &player).await;
+                }
+                Interaction::Get(item) => {
+                    inventory::get(game_state, db, &player, &item).await;
+                }
+                Interaction::Drop(item) => {
+                    inventory::drop(game_state, db, &player, &item).await;
+                }
+                Interaction::Give { item, target } => {
+                    inventory::give(game_state, db, &player, &item, &target).await;
+                }
+                Interaction::Inventory => {
+                    inventory::list(game_state, &player).await;
+                }
+                Interaction::Wear(item) => {
+                    equipment::wear(game_state, db, &player, &item).await;
+                }
+                Interaction::Remove(item) => {
+                    equipment::remove(game_state, db, &player, &item).await;
+                }
+                Interaction::Equipment => {
+                    equipment::list(game_state, &player).await;
+                }
+                Interaction::Stats => {
+                    stats::process(game_state, &player).await;
+                }
+                Interaction::Search => {
+                    search::process(game_state, &player).await;
+                }
+                Interaction::Use { verb, item, target } => {
+                    consumable::use_item(game_state, db, &player, &verb, &item, target.as_deref())
+                        .await;
+                }
+                Interaction::Open(container) => {
+                    container::open(game_state, db, &player, &container).await;
+                }
+                Interaction::LookIn(container) => {
+                    container::look_in(game_state, db, &player, &container).await;
+                }
+                // Offered by entities and run through `Use`; players can't send one directly.
+                Interaction::Verb(_) => {}
+                Interaction::GetFrom { item, container } => {
+                    container::get_from(game_state, db, &player, &item, &container).await;
+                }
             }
         }
     }
 }
+
+/// Submit a turn action to the engagement it belongs to: effects go to the player's battle,
+/// everything else to their conversation. Free text that no engagement takes is tried as a
+/// custom verb on the entities around the player, e.g. `pull lever`.
+async fn submit_engagement_action(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    action: TurnAction,
+) {
+    let engagement_type = match action {
+        TurnAction::ApplyEffect { .. } => EngagementType::Battle,
+        _ => EngagementType::Conversation,
+    };
+    let free_text = match &action {
+        TurnAction::Respond { content } => Some(content.clone()),
+        _ => None,
+    };
+    let accepted = match game_state
+        .engagements
+        .find_for_entity(player.entity_id, engagement_type)
+        .await
+    {
+        Some(engagement_id) => {
+            game_state
+                .engagements
+                .submit_action(engagement_id, player.entity_id, action)
+                .await
+        }
+        None => false,
+    };
+    tracing::debug!(
+        entity_id = player.entity_id,
+        accepted,
+        "engagement action submitted"
+    );
+    if accepted {
+        return;
+    }
+    if let Some((verb, rest)) = free_text
+        .as_deref()
+        .and_then(|text| text.trim().split_once(char::is_whitespace))
+        && object::act(game_state, db, player, verb, rest).await
+    {
+        return;
+    }
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        "I don't understand that.",
+    );
+}
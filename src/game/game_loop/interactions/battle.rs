@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use crate::game::engagement::battle::end_if_decided;
+use crate::game::entity::EntityType;
+use crate::game::game_loop::attributes::{has_life, is_defeated};
+use crate::game::player::Player;
+use crate::game::{EngagementType, GameState, TurnAction, messaging};
+use crate::persistence::Database;
+
+use super::target;
+
+/// Attack an entity in the current room. Starts a battle if the player isn't in one, otherwise
+/// queues an attack on the target for the player's next turn. Targets outside the player's
+/// battle are refused, as are targets without a life attribute, since they could never be
+/// defeated.
+pub async fn attack(game_state: &Arc<GameState>, player: &Player, query: &str) {
+    let location = {
+        let entities = game_state.active_entities.read().await;
+        match entities.get(&player.entity_id) {
+            Some(e) => e.location.clone(),
+            None => return,
+        }
+    };
+
+    let Some(target_id) =
+        target::find_in_room(game_state, &location, player.entity_id, query).await
+    else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You don't see {query} here."),
+        );
+        return;
+    };
+
+    let names = game_state.player_names().await;
+    let (target_name, target_type, target_alive, target_defeated) = {
+        let entities = game_state.active_entities.read().await;
+        let Some(target) = entities.get(&target_id) else {
+            return;
+        };
+        (
+            target.display_name(&names),
+            target.entity_type.clone(),
+            has_life(target, &game_state.attribute_config),
+            is_defeated(target, &game_state.attribute_config),
+        )
+    };
+    let attack_action = TurnAction::ApplyEffect {
+        effect_name: game_state.mud_config.battle.attack_effect.clone(),
+        target_entity_id: Some(target_id),
+    };
+
+    if let Some(engagement_id) = game_state
+        .engagements
+        .find_for_entity(player.entity_id, EngagementType::Battle)
+        .await
+    {
+        if game_state
+            .engagements
+            .find_for_entity(target_id, EngagementType::Battle)
+            .await
+            != Some(engagement_id)
+        {
+            messaging::message(
+                &game_state.message_tx,
+                player.id,
+                format!("{target_name} isn't part of this fight."),
+            );
+            return;
+        }
+        if game_state
+            .engagements
+            .submit_action(engagement_id, player.entity_id, attack_action)
+            .await
+        {
+            messaging::message(
+                &game_state.message_tx,
+                player.id,
+                format!("You ready an attack on {target_name}."),
+            );
+        }
+        return;
+    }
+
+    let refusal = if target_type == EntityType::Player || !target_alive {
+        Some(format!("You can't attack {target_name}."))
+    } else if target_defeated {
+        Some(format!("{target_name} has already been defeated."))
+    } else if game_state.engagements.is_entity_engaged(target_id).await {
+        Some(format!("{target_name} is busy right now."))
+    } else if game_state
+        .engagements
+        .is_entity_engaged(player.entity_id)
+        .await
+    {
+        Some("You're busy right now.".to_string())
+    } else {
+        None
+    };
+    if let Some(refusal) = refusal {
+        messaging::message(&game_state.message_tx, player.id, refusal);
+        return;
+    }
+
+    let engagement_id = game_state
+        .engagements
+        .add(EngagementType::Battle, vec![player.entity_id, target_id])
+        .await;
+    game_state
+        .engagements
+        .submit_action(engagement_id, player.entity_id, attack_action)
+        .await;
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You attack {target_name}!"),
+    );
+}
+
+/// Leave the player's current battle. The battle ends if nobody is left to fight.
+pub async fn flee(game_state: &Arc<GameState>, db: &Database, player: &Player) {
+    let Some(engagement_id) = game_state
+        .engagements
+        .find_for_entity(player.entity_id, EngagementType::Battle)
+        .await
+    else {
+        messaging::message(&game_state.message_tx, player.id, "You aren't in a fight.");
+        return;
+    };
+
+    let Some(remaining) = game_state
+        .engagements
+        .remove_entity(engagement_id, player.entity_id)
+        .await
+    else {
+        return;
+    };
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        "You flee from the fight!",
+    );
+
+    let others: Vec<i64> = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .filter(|p| remaining.contains(&p.entity_id))
+        .map(|p| p.id)
+        .collect();
+    for player_id in others {
+        messaging::message(
+            &game_state.message_tx,
+            player_id,
+            format!("{} flees from the fight!", player.name),
+        );
+    }
+
+    end_if_decided(game_state, db, engagement_id, &remaining).await;
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::game::config::{ChannelConfig, find_channel};
+use crate::game::messaging::ChannelPost;
+use crate::game::player::Player;
+use crate::game::{GameState, messaging};
+use crate::persistence::{Database, channel_repo};
+
+/// Post to a channel the player is subscribed to. Every subscribed active player receives it,
+/// including the poster.
+pub async fn post(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    name: &str,
+    content: &str,
+) {
+    let Some(channel) = lookup(game_state, player, name) else {
+        return;
+    };
+    let choices = match channel_repo::find_by_channel(db.pool(), &channel.name).await {
+        Ok(rows) => rows.into_iter().collect::<HashMap<_, _>>(),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load channel subscriptions");
+            return;
+        }
+    };
+    if !is_subscribed(channel, &choices, player.id) {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You aren't on the {} channel. Join it first.", channel.name),
+        );
+        return;
+    }
+
+    let subscribers: Vec<i64> = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .map(|p| p.id)
+        .filter(|id| is_subscribed(channel, &choices, *id))
+        .collect();
+    for player_id in subscribers {
+        messaging::channel_post(
+            &game_state.message_tx,
+            player_id,
+            ChannelPost {
+                channel: channel.name.clone(),
+                speaker: player.name.clone(),
+                content: content.to_string(),
+            },
+        );
+    }
+}
+
+/// Join or leave a channel. The choice is saved so it survives reconnecting.
+pub async fn set_joined(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    name: &str,
+    joined: bool,
+) {
+    let Some(channel) = lookup(game_state, player, name) else {
+        return;
+    };
+    if let Err(e) = channel_repo::set_joined(db.pool(), player.id, &channel.name, joined).await {
+        tracing::error!(error = %e, "Failed to save channel subscription");
+        return;
+    }
+    let content = if joined {
+        format!("You join the {} channel.", channel.name)
+    } else {
+        format!("You leave the {} channel.", channel.name)
+    };
+    messaging::message(&game_state.message_tx, player.id, content);
+}
+
+/// List every channel and whether the player is on it.
+pub async fn list(game_state: &Arc<GameState>, db: &Database, player: &Player) {
+    let choices: HashMap<String, bool> = channel_repo::find_by_player(db.pool(), player.id)
+        .await
+        .map(|rows| rows.into_iter().collect())
+        .unwrap_or_default();
+    let channels = &game_state.mud_config.channels;
+    if channels.is_empty() {
+        messaging::message(&game_state.message_tx, player.id, "There are no channels.");
+        return;
+    }
+    let lines: Vec<String> = channels
+        .iter()
+        .map(|c| {
+            let joined = choices.get(&c.name).copied().unwrap_or(c.auto_join);
+            let mut line = c.name.clone();
+            if let Some(description) = &c.description {
+                line.push_str(&format!(" - {description}"));
+            }
+            if joined {
+                line.push_str(" (joined)");
+            }
+            line
+        })
+        .collect();
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("Channels:\n  {}", lines.join("\n  ")),
+    );
+}
+
+fn lookup<'a>(
+    game_state: &'a Arc<GameState>,
+    player: &Player,
+    name: &str,
+) -> Option<&'a ChannelConfig> {
+    let channel = find_channel(&game_state.mud_config.channels, name);
+    if channel.is_none() {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("There is no {name} channel."),
+        );
+    }
+    channel
+}
+
+fn is_subscribed(channel: &ChannelConfig, choices: &HashMap<i64, bool>, player_id: i64) -> bool {
+    choices
+        .get(&player_id)
+        .copied()
+        .unwrap_or(channel.auto_join)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_falls_back_to_auto_join() {
+        let channels = ChannelConfig::default_config();
+        let ooc = find_channel(&channels, "ooc").unwrap();
+        let trade = find_channel(&channels, "trade").unwrap();
+        let choices = HashMap::from([(1, false), (2, true)]);
+        assert!(!is_subscribed(ooc, &choices, 1));
+        assert!(is_subscribed(ooc, &choices, 3));
+        assert!(is_subscribed(trade, &choices, 2));
+        assert!(!is_subscribed(trade, &choices, 3));
+    }
+}
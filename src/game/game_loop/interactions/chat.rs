@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::game::entity::EntityType;
+use crate::game::messaging::{Speech, SpeechKind};
+use crate::game::player::Player;
+use crate::game::{GameState, Location, messaging};
+
+/// Say something to everyone in the player's room.
+pub async fn say(game_state: &Arc<GameState>, player: &Player, content: &str) {
+    let Some(location) = location(game_state, player).await else {
+        return;
+    };
+    let others = messaging::players_where(game_state, player.entity_id, |l| *l == location).await;
+    send(
+        game_state,
+        player,
+        SpeechKind::Say,
+        format!("You say, \"{content}\""),
+        &others,
+        format!("{} says, \"{content}\"", player.name),
+    );
+}
+
+/// Describe an action to everyone in the player's room, e.g. `emote waves` gives "Alice waves."
+pub async fn emote(game_state: &Arc<GameState>, player: &Player, content: &str) {
+    let Some(location) = location(game_state, player).await else {
+        return;
+    };
+    let others = messaging::players_where(game_state, player.entity_id, |l| *l == location).await;
+    let text = emote_text(&player.name, content);
+    send(
+        game_state,
+        player,
+        SpeechKind::Emote,
+        text.clone(),
+        &others,
+        text,
+    );
+}
+
+/// Whisper to another player in the same room. Everyone else only sees that a whisper happened.
+pub async fn whisper(game_state: &Arc<GameState>, player: &Player, target: &str, content: &str) {
+    let Some(location) = location(game_state, player).await else {
+        return;
+    };
+    let listener = {
+        let entities = game_state.active_entities.read().await;
+        game_state
+            .active_players
+            .read()
+            .await
+            .values()
+            .filter(|p| p.entity_id != player.entity_id && p.name.eq_ignore_ascii_case(target))
+            .find(|p| {
+                entities
+                    .get(&p.entity_id)
+                    .is_some_and(|e| e.entity_type == EntityType::Player && e.location == location)
+            })
+            .cloned()
+    };
+    let Some(listener) = listener else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You don't see {target} here."),
+        );
+        return;
+    };
+
+    send(
+        game_state,
+        player,
+        SpeechKind::Whisper,
+        format!("You whisper to {}, \"{content}\"", listener.name),
+        &[listener.id],
+        format!("{} whispers to you, \"{content}\"", player.name),
+    );
+    let onlookers: Vec<i64> =
+        messaging::players_where(game_state, player.entity_id, |l| *l == location)
+            .await
+            .into_iter()
+            .filter(|id| *id != listener.id)
+            .collect();
+    for player_id in onlookers {
+        messaging::message(
+            &game_state.message_tx,
+            player_id,
+            format!("{} whispers something to {}.", player.name, listener.name),
+        );
+    }
+}
+
+/// Shout to everyone in the player's dungeon.
+pub async fn shout(game_state: &Arc<GameState>, player: &Player, content: &str) {
+    let Some(location) = location(game_state, player).await else {
+        return;
+    };
+    let others = messaging::players_where(game_state, player.entity_id, |l| {
+        l.world_id == location.world_id && l.dungeon_id == location.dungeon_id
+    })
+    .await;
+    send(
+        game_state,
+        player,
+        SpeechKind::Shout,
+        format!("You shout, \"{content}\""),
+        &others,
+        format!("{} shouts, \"{content}\"", player.name),
+    );
+}
+
+async fn location(game_state: &Arc<GameState>, player: &Player) -> Option<Location> {
+    let entities = game_state.active_entities.read().await;
+    entities.get(&player.entity_id).map(|e| e.location.clone())
+}
+
+fn send(
+    game_state: &Arc<GameState>,
+    player: &Player,
+    kind: SpeechKind,
+    own_text: String,
+    listeners: &[i64],
+    text: String,
+) {
+    messaging::speech(
+        &game_state.message_tx,
+        player.id,
+        Speech::new(kind, &player.name, own_text),
+    );
+    for player_id in listeners {
+        messaging::speech(
+            &game_state.message_tx,
+            *player_id,
+            Speech::new(kind, &player.name, text.clone()),
+        );
+    }
+}
+
+fn emote_text(name: &str, content: &str) -> String {
+    let content = content.trim();
+    if content.ends_with(['.', '!', '?']) {
+        format!("{name} {content}")
+    } else {
+        format!("{name} {content}.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emote_adds_missing_punctuation() {
+        assert_eq!(emote_text("Alice", "waves"), "Alice waves.");
+        assert_eq!(emote_text("Alice", "cheers!"), "Alice cheers!");
+    }
+}
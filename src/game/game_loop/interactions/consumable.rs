@@ -0,0 +1,333 @@
+use std::sync::Arc;
+
+use crate::game::game_loop::effects;
+use crate::game::player::Player;
+use crate::game::{GameState, messaging};
+use crate::persistence::{Database, entity_effect_repo, entity_repo};
+
+use super::inventory::{find_carried, is_equipped};
+use super::object;
+use super::target;
+
+/// Use a carried consumable on the player or, when `target_query` is given, on someone in the
+/// room. Its `on_use` effects are attached to the target and one charge is spent; the item is
+/// destroyed when the last charge is gone. Items the map placed are kept with no charges left
+/// instead, so map loads don't recreate them and world updates can respawn them. Worn items must be removed first so their modifiers
+/// come off before the item can be used up. An entity that declares `verb` itself, such as a
+/// sign to `read`, handles it instead.
+pub async fn use_item(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    verb: &str,
+    item_query: &str,
+    target_query: Option<&str>,
+) {
+    if target_query.is_none() && object::act(game_state, db, player, verb, item_query).await {
+        return;
+    }
+    let Some((item_id, item_name)) = find_carried(game_state, player, item_query).await else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You aren't carrying {item_query}."),
+        );
+        return;
+    };
+    let consumable = {
+        let entities = game_state.active_entities.read().await;
+        entities.get(&item_id).and_then(|item| {
+            let config = game_state.entity_configs.get(item.config_id.as_deref()?)?;
+            let consumable = config.consumable.clone()?;
+            let charges = item.charges.unwrap_or(consumable.charges);
+            Some((consumable.on_use, charges))
+        })
+    };
+    let Some((on_use, charges)) = consumable else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You can't {verb} the {item_name}."),
+        );
+        return;
+    };
+    if is_equipped(game_state, item_id).await {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You'll need to remove the {item_name} first."),
+        );
+        return;
+    }
+    let Some(location) = game_state
+        .active_entities
+        .read()
+        .await
+        .get(&player.entity_id)
+        .map(|e| e.location.clone())
+    else {
+        return;
+    };
+
+    let (target_id, target_name) = match target_query {
+        None => (player.entity_id, None),
+        Some(query) => {
+            let names = game_state.player_names().await;
+            let found =
+                match target::find_in_room(game_state, &location, player.entity_id, query).await {
+                    Some(id) => game_state
+                        .active_entities
+                        .read()
+                        .await
+                        .get(&id)
+                        .map(|e| (e.id, e.display_name(&names))),
+                    None => None,
+                };
+            let Some((id, name)) = found else {
+                messaging::message(
+                    &game_state.message_tx,
+                    player.id,
+                    format!("You don't see {query} here."),
+                );
+                return;
+            };
+            (id, Some(name))
+        }
+    };
+
+    for effect in &on_use {
+        if let Err(e) = effects::attach(game_state, db, target_id, effect).await {
+            tracing::error!(error = %e, "Failed to attach consumable effect");
+        }
+    }
+
+    let (you, others) = match &target_name {
+        None => (
+            format!("You use the {item_name}."),
+            format!("{} uses the {item_name}.", player.name),
+        ),
+        Some(name) => (
+            format!("You use the {item_name} on {name}."),
+            format!("{} uses the {item_name} on {name}.", player.name),
+        ),
+    };
+    messaging::message(&game_state.message_tx, player.id, you);
+    messaging::message_location(game_state, &location, player.entity_id, others).await;
+
+    let remaining = charges - 1;
+    if remaining > 0 {
+        if let Some(item) = game_state.active_entities.write().await.get_mut(&item_id) {
+            item.charges = Some(remaining);
+        }
+        if let Err(e) = entity_repo::update_charges(db.pool(), item_id, remaining).await {
+            tracing::error!(error = %e, "Failed to update consumable charges");
+        }
+        return;
+    }
+
+    game_state.active_entities.write().await.remove(&item_id);
+    if let Err(e) = entity_effect_repo::delete_by_entity(db.pool(), item_id).await {
+        tracing::error!(error = %e, "Failed to delete consumable effects");
+    }
+    match entity_repo::is_map_placed(db.pool(), item_id).await {
+        Ok(true) => {
+            if let Err(e) = entity_repo::update_charges(db.pool(), item_id, 0).await {
+                tracing::error!(error = %e, "Failed to mark consumable used up");
+            }
+            if let Err(e) = entity_repo::update_holder(db.pool(), item_id, None).await {
+                tracing::error!(error = %e, "Failed to mark consumable used up");
+            }
+        }
+        Ok(false) => {
+            if let Err(e) = entity_repo::delete(db.pool(), item_id).await {
+                tracing::error!(error = %e, "Failed to delete used up consumable");
+            }
+        }
+        Err(e) => tracing::error!(error = %e, "Failed to look up used up consumable"),
+    }
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("The {item_name} is used up."),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::config::EntityConfig;
+    use crate::game::{Entity, EntityType, Location};
+    use crate::persistence::fixtures::{insert_player, insert_room};
+
+    const POTION: &str = r#"
+entity_type = "object"
+
+[consumable]
+charges = 2
+
+[[consumable.on_use]]
+name = "healing_draught"
+effect_type = { type = "attribute_update", attribute_id = "hp", value = 5 }
+trigger_info = { type = "once" }
+"#;
+
+    struct Setup {
+        game_state: Arc<GameState>,
+        db: Database,
+        player: Player,
+        location: Location,
+    }
+
+    async fn setup() -> Setup {
+        let db = Database::connect_in_memory().await.unwrap();
+        let location = insert_room(&db).await;
+        let player = insert_player(&db, &location, "Alice").await;
+        let mut game_state = GameState::load(None).unwrap();
+        let config: EntityConfig = toml::from_str(POTION).unwrap();
+        game_state
+            .entity_configs
+            .insert("entities/potion".to_string(), config);
+        let game_state = Arc::new(game_state);
+        game_state.active_entities.write().await.insert(
+            player.entity_id,
+            Entity::new(player.entity_id, EntityType::Player, location.clone()),
+        );
+        Setup {
+            game_state,
+            db,
+            player,
+            location,
+        }
+    }
+
+    /// Give the player a potion with `charges` left, returning its id.
+    async fn carry_potion(setup: &Setup, charges: Option<i64>, map_placed: bool) -> i64 {
+        let pool = setup.db.pool();
+        let id = if map_placed {
+            entity_repo::insert_config_entity_if_missing(
+                pool,
+                &EntityType::Object,
+                &setup.location,
+                "entities/potion",
+                None,
+            )
+            .await
+            .unwrap()
+            .0
+        } else {
+            let mut potion = Entity::new(0, EntityType::Object, setup.location.clone());
+            potion.config_id = Some("entities/potion".to_string());
+            entity_repo::insert(pool, &potion).await.unwrap()
+        };
+        entity_repo::update_holder(pool, id, Some(setup.player.entity_id))
+            .await
+            .unwrap();
+        let mut potion = Entity::new(id, EntityType::Object, setup.location.clone());
+        potion.config_id = Some("entities/potion".to_string());
+        potion.holder_id = Some(setup.player.entity_id);
+        potion.charges = charges;
+        setup
+            .game_state
+            .active_entities
+            .write()
+            .await
+            .insert(id, potion);
+        id
+    }
+
+    async fn use_potion(setup: &Setup) {
+        use_item(
+            &setup.game_state,
+            &setup.db,
+            &setup.player,
+            "drink",
+            "potion",
+            None,
+        )
+        .await;
+    }
+
+    async fn effect_count(setup: &Setup) -> usize {
+        entity_effect_repo::find_active_by_entity(setup.db.pool(), setup.player.entity_id)
+            .await
+            .unwrap()
+            .len()
+    }
+
+    #[tokio::test]
+    async fn use_spends_a_charge_and_attaches_effects() {
+        let setup = setup().await;
+        let id = carry_potion(&setup, None, false).await;
+
+        use_potion(&setup).await;
+
+        let charges = setup.game_state.active_entities.read().await[&id].charges;
+        assert_eq!(charges, Some(1));
+        let stored = entity_repo::find_by_id(setup.db.pool(), id).await.unwrap();
+        assert_eq!(stored.unwrap().charges, Some(1));
+        assert_eq!(effect_count(&setup).await, 1);
+    }
+
+    #[tokio::test]
+    async fn last_charge_deletes_a_spawned_item() {
+        let setup = setup().await;
+        let id = carry_potion(&setup, Some(1), false).await;
+
+        use_potion(&setup).await;
+
+        assert!(
+            !setup
+                .game_state
+                .active_entities
+                .read()
+                .await
+                .contains_key(&id)
+        );
+        let stored = entity_repo::find_by_id(setup.db.pool(), id).await.unwrap();
+        assert!(stored.is_none());
+    }
+
+    #[tokio::test]
+    async fn last_charge_keeps_a_map_placed_item_out_of_the_world() {
+        let setup = setup().await;
+        let id = carry_potion(&setup, Some(1), true).await;
+
+        use_potion(&setup).await;
+
+        let pool = setup.db.pool();
+        let stored = entity_repo::find_by_id(pool, id).await.unwrap().unwrap();
+        assert_eq!(stored.charges, Some(0));
+        assert_eq!(stored.holder_id, None);
+        let (reloaded_id, is_new) = entity_repo::insert_config_entity_if_missing(
+            pool,
+            &EntityType::Object,
+            &setup.location,
+            "entities/potion",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!((reloaded_id, is_new), (id, false));
+        let loaded = entity_repo::find_config_entities_by_dungeon(pool, "w1", "d1")
+            .await
+            .unwrap();
+        assert!(loaded.iter().all(|e| e.id != id));
+    }
+
+    #[tokio::test]
+    async fn worn_items_must_be_removed_before_use() {
+        let setup = setup().await;
+        let id = carry_potion(&setup, None, false).await;
+        if let Some(potion) = setup.game_state.active_entities.write().await.get_mut(&id) {
+            potion.equipped = true;
+        }
+
+        use_potion(&setup).await;
+
+        assert_eq!(
+            setup.game_state.active_entities.read().await[&id].charges,
+            None
+        );
+        assert_eq!(effect_count(&setup).await, 0);
+    }
+}
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use crate::game::entity::{Entity, EntityType};
+use crate::game::game_loop::attributes;
+use crate::game::player::Player;
+use crate::game::{GameState, Location, messaging};
+use crate::persistence::{Database, entity_repo};
+
+use super::inventory::find_carried;
+use super::object;
+use super::target::{self, matches};
+
+/// Open a container, rolling its loot the first time, and list what's inside. Entities that
+/// declare their own `open` verb, such as doors, handle it instead.
+pub async fn open(game_state: &Arc<GameState>, db: &Database, player: &Player, query: &str) {
+    if object::act(game_state, db, player, "open", query).await {
+        return;
+    }
+    let Some((container_id, container_name)) =
+        find_container(game_state, player, query, "open").await
+    else {
+        return;
+    };
+    roll_loot(game_state, db, container_id).await;
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You open the {container_name}."),
+    );
+    if let Some(location) = player_location(game_state, player).await {
+        messaging::message_location(
+            game_state,
+            &location,
+            player.entity_id,
+            format!("{} opens the {container_name}.", player.name),
+        )
+        .await;
+    }
+    list_contents(game_state, player, container_id, &container_name).await;
+}
+
+/// List what's inside a container.
+pub async fn look_in(game_state: &Arc<GameState>, db: &Database, player: &Player, query: &str) {
+    let Some((container_id, container_name)) =
+        find_container(game_state, player, query, "look in").await
+    else {
+        return;
+    };
+    roll_loot(game_state, db, container_id).await;
+    list_contents(game_state, player, container_id, &container_name).await;
+}
+
+/// Take an item out of a container and carry it.
+pub async fn get_from(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    item_query: &str,
+    container_query: &str,
+) {
+    let Some((container_id, container_name)) =
+        find_container(game_state, player, container_query, "look in").await
+    else {
+        return;
+    };
+    roll_loot(game_state, db, container_id).await;
+
+    let names = game_state.player_names().await;
+    let item = {
+        let mut entities = game_state.active_entities.write().await;
+        let mut candidates: Vec<&Entity> = entities
+            .values()
+            .filter(|e| e.holder_id == Some(container_id))
+            .filter(|e| matches(e, &names, item_query))
+            .collect();
+        candidates.sort_by_key(|e| e.id);
+        let found = candidates.first().map(|e| (e.id, e.display_name(&names)));
+        if let Some((id, _)) = &found
+            && let Some(item) = entities.get_mut(id)
+        {
+            item.holder_id = Some(player.entity_id);
+            item.equipped = false;
+        }
+        found
+    };
+    let Some((item_id, item_name)) = item else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("There's no {item_query} in the {container_name}."),
+        );
+        return;
+    };
+
+    if let Err(e) = entity_repo::update_holder(db.pool(), item_id, Some(player.entity_id)).await {
+        tracing::error!(error = %e, "Failed to update entity holder");
+    }
+    if let Err(e) = entity_repo::update_equipped(db.pool(), item_id, false).await {
+        tracing::error!(error = %e, "Failed to update equipped item");
+    }
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You take the {item_name} from the {container_name}."),
+    );
+    if let Some(location) = player_location(game_state, player).await {
+        messaging::message_location(
+            game_state,
+            &location,
+            player.entity_id,
+            format!(
+                "{} takes the {item_name} from the {container_name}.",
+                player.name
+            ),
+        )
+        .await;
+    }
+}
+
+/// Fill an entity with drops from its loot table, once. Later calls do nothing.
+pub async fn roll_loot(game_state: &Arc<GameState>, db: &Database, container_id: i64) {
+    let pending = {
+        let mut entities = game_state.active_entities.write().await;
+        let Some(container) = entities.get_mut(&container_id) else {
+            return;
+        };
+        if container.loot_rolled {
+            return;
+        }
+        container.loot_rolled = true;
+        let drops = container
+            .config_id
+            .as_deref()
+            .and_then(|id| game_state.entity_configs.get(id))
+            .and_then(|config| config.loot.as_deref())
+            .and_then(|loot| game_state.loot_tables.get(loot))
+            .map(|table| table.roll())
+            .unwrap_or_default();
+        (container.location.clone(), drops)
+    };
+    let (location, drops) = pending;
+
+    if let Err(e) = entity_repo::update_loot_rolled(db.pool(), container_id, true).await {
+        tracing::error!(error = %e, "Failed to record rolled loot");
+    }
+    for config_id in drops {
+        if let Err(e) = game_state
+            .spawn_entity(db.pool(), &config_id, &location, Some(container_id), None)
+            .await
+        {
+            tracing::error!(error = %e, config_id, "Failed to spawn loot");
+        }
+    }
+}
+
+/// Containers are objects configured as one, or the bodies of defeated characters.
+fn is_container(game_state: &GameState, entity: &Entity) -> bool {
+    match entity.entity_type {
+        EntityType::Object => entity
+            .config_id
+            .as_deref()
+            .and_then(|id| game_state.entity_configs.get(id))
+            .is_some_and(|config| config.container),
+        EntityType::Character => attributes::is_defeated(entity, &game_state.attribute_config),
+        EntityType::Player => false,
+    }
+}
+
+/// Find a container in the room or carried by the player. Tells the player when there isn't
+/// one, using `verb` to describe what they tried.
+async fn find_container(
+    game_state: &Arc<GameState>,
+    player: &Player,
+    query: &str,
+    verb: &str,
+) -> Option<(i64, String)> {
+    let location = player_location(game_state, player).await?;
+    let id = match target::find_in_room(game_state, &location, player.entity_id, query).await {
+        Some(id) => Some(id),
+        None => find_carried(game_state, player, query)
+            .await
+            .map(|(id, _)| id),
+    };
+    let names = game_state.player_names().await;
+    let found = match id {
+        Some(id) => {
+            let entities = game_state.active_entities.read().await;
+            entities
+                .get(&id)
+                .filter(|e| is_container(game_state, e))
+                .map(|e| (e.id, e.display_name(&names)))
+        }
+        None => None,
+    };
+    if found.is_none() {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You can't {verb} {query}."),
+        );
+    }
+    found
+}
+
+async fn list_contents(
+    game_state: &Arc<GameState>,
+    player: &Player,
+    container_id: i64,
+    container_name: &str,
+) {
+    let names = game_state.player_names().await;
+    let mut items: Vec<String> = {
+        let entities = game_state.active_entities.read().await;
+        entities
+            .values()
+            .filter(|e| e.holder_id == Some(container_id))
+            .map(|e| e.display_name(&names))
+            .collect()
+    };
+    let content = if items.is_empty() {
+        format!("The {container_name} is empty.")
+    } else {
+        items.sort();
+        format!("The {container_name} contains:\n  {}", items.join("\n  "))
+    };
+    messaging::message(&game_state.message_tx, player.id, content);
+}
+
+async fn player_location(game_state: &Arc<GameState>, player: &Player) -> Option<Location> {
+    game_state
+        .active_entities
+        .read()
+        .await
+        .get(&player.entity_id)
+        .map(|e| e.location.clone())
+}
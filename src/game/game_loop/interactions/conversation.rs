@@ -1,7 +1,13 @@
 use std::sync::Arc;
 
+use futures_util::StreamExt;
+
+use crate::game::agent::ChatMessage;
 use crate::game::config::{DialogLine, PersonaConfig, PlayerResponse};
-use crate::game::entity_ai::{ConversationContext, EntityAI, SimpleConversationState};
+use crate::game::entity_ai::{
+    AgentConversationContext, ConversationContext, EntityAI, SimpleConversationState,
+};
+use crate::game::messaging::StreamingState;
 use crate::game::player::Player;
 use crate::game::{GameState, messaging};
 
@@ -9,6 +15,9 @@ enum TalkCandidate {
     AgentStub {
         label: String,
     },
+    Agent {
+        npc_entity_id: i64,
+    },
     StandardDialog {
         npc_entity_id: i64,
         dialog_root: DialogLine,
@@ -40,13 +49,20 @@ pub async fn process(game_state: &Arc<GameState>, player: &Player) {
     // Find a talkable entity in the same room
     let candidate = {
         let entities = game_state.active_entities.read().await;
+        let revealed = game_state.revealed.read().await;
         entities
             .values()
             .filter(|e| e.id != player.entity_id && e.location == player_location)
+            .filter(|e| !game_state.is_hidden(e, &revealed))
             .find_map(|e| {
                 let config_id = e.config_id.as_deref()?;
                 let config = game_state.entity_configs.get(config_id)?;
                 match &config.persona {
+                    Some(PersonaConfig::Agent { .. }) if game_state.agent_provider.is_some() => {
+                        Some(TalkCandidate::Agent {
+                            npc_entity_id: e.id,
+                        })
+                    }
                     Some(PersonaConfig::Agent { .. }) => {
                         let label = e.description.as_deref().unwrap_or("entity").to_string();
                         Some(TalkCandidate::AgentStub { label })
@@ -78,6 +94,33 @@ pub async fn process(game_state: &Arc<GameState>, player: &Player) {
                 format!("The {label} doesn't seem ready to talk."),
             );
         }
+        Some(TalkCandidate::Agent { npc_entity_id }) => {
+            let engagement_id = game_state
+                .engagements
+                .add_conversation(player.entity_id, npc_entity_id)
+                .await;
+
+            {
+                let mut entities = game_state.active_entities.write().await;
+                if let Some(npc) = entities.get_mut(&npc_entity_id) {
+                    npc.ai
+                        .get_or_insert_default()
+                        .agent_conversation_state
+                        .get_or_insert_default()
+                        .contexts
+                        .insert(engagement_id, AgentConversationContext::default());
+                }
+            }
+
+            agent_reply(
+                game_state,
+                player.id,
+                npc_entity_id,
+                engagement_id,
+                format!("{} approaches you and says hello.", player.name),
+            )
+            .await;
+        }
         Some(TalkCandidate::StandardDialog {
             npc_entity_id,
             dialog_root,
@@ -95,10 +138,12 @@ pub async fn process(game_state: &Arc<GameState>, player: &Player) {
                         engagement_id,
                         ConversationContext {
                             current_dialog: Some(dialog_root.clone()),
+                            path: Vec::new(),
                         },
                     );
                     npc.ai = Some(EntityAI {
                         simple_conversation_state: Some(state),
+                        ..Default::default()
                     });
                 }
             }
@@ -110,6 +155,122 @@ pub async fn process(game_state: &Arc<GameState>, player: &Player) {
     }
 }
 
+/// Stream an agent NPC's reply to `prompt` to the player, then record the exchange in the
+/// conversation's chat history. The reply is generated in the background so the game loop
+/// isn't held up by the provider. Only one reply per conversation streams at a time; input
+/// that arrives while the NPC is still answering is turned away.
+pub async fn agent_reply(
+    game_state: &Arc<GameState>,
+    player_id: i64,
+    npc_entity_id: i64,
+    engagement_id: i64,
+    prompt: String,
+) {
+    let Some(provider) = game_state.agent_provider.clone() else {
+        return;
+    };
+    let Some((label, persona, history)) = ({
+        let mut entities = game_state.active_entities.write().await;
+        entities.get_mut(&npc_entity_id).and_then(|npc| {
+            let label = npc.description.as_deref().unwrap_or("entity").to_string();
+            let ctx = npc
+                .ai
+                .as_mut()?
+                .agent_conversation_state
+                .as_mut()?
+                .contexts
+                .get_mut(&engagement_id)?;
+            if ctx.replying {
+                messaging::message(
+                    &game_state.message_tx,
+                    player_id,
+                    format!("The {label} is still answering."),
+                );
+                return None;
+            }
+            ctx.replying = true;
+            let history = ctx.history.clone();
+            let persona = npc
+                .config_id
+                .as_deref()
+                .and_then(|id| game_state.entity_configs.get(id))
+                .and_then(|config| match &config.persona {
+                    Some(PersonaConfig::Agent {
+                        persona: Some(persona),
+                        ..
+                    }) => Some(persona.clone()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| default_persona(&label));
+            Some((label, persona, history))
+        })
+    }) else {
+        return;
+    };
+
+    let game_state = game_state.clone();
+    tokio::spawn(async move {
+        let tx = &game_state.message_tx;
+        let mut stream = provider.stream_reply(&persona, &history, &prompt);
+        let mut reply = String::new();
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(text) => {
+                    messaging::chunk(tx, player_id, text.clone(), StreamingState::Streaming);
+                    reply.push_str(&text);
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, npc_entity_id, "Agent reply failed");
+                    break;
+                }
+            }
+        }
+        if reply.is_empty() {
+            messaging::message(tx, player_id, format!("The {label} doesn't answer."));
+        } else {
+            messaging::chunk(tx, player_id, "", StreamingState::Complete);
+        }
+
+        let max_history = game_state
+            .mud_config
+            .agent
+            .as_ref()
+            .map(|c| c.max_history)
+            .unwrap_or_default();
+        let mut entities = game_state.active_entities.write().await;
+        if let Some(npc) = entities.get_mut(&npc_entity_id)
+            && let Some(ai) = npc.ai.as_mut()
+            && let Some(state) = ai.agent_conversation_state.as_mut()
+            && let Some(ctx) = state.contexts.get_mut(&engagement_id)
+        {
+            ctx.replying = false;
+            if !reply.is_empty() {
+                record_exchange(&mut ctx.history, prompt, reply, max_history);
+            }
+        }
+    });
+}
+
+fn default_persona(label: &str) -> String {
+    format!(
+        "You are {label}, a character in a text adventure. Stay in character and keep your \
+         replies short."
+    )
+}
+
+/// Append a prompt and reply to the history, dropping the oldest messages beyond `max_history`.
+fn record_exchange(
+    history: &mut Vec<ChatMessage>,
+    prompt: String,
+    reply: String,
+    max_history: usize,
+) {
+    history.push(ChatMessage::user(prompt));
+    history.push(ChatMessage::assistant(reply));
+    let excess = history.len().saturating_sub(max_history);
+    history.drain(..excess);
+}
+
 pub fn pick_text(dialog: &DialogLine) -> &str {
     if dialog.alts.is_empty() {
         &dialog.text
@@ -134,3 +295,108 @@ pub fn format_dialog_message(text: &str, responses: &[PlayerResponse]) -> String
     }
     msg
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Notify;
+
+    use super::*;
+    use crate::game::Location;
+    use crate::game::agent::{AgentProvider, ReplyStream};
+    use crate::game::entity::{Entity, EntityType};
+    use crate::game::messaging::Message;
+
+    /// Answers "Hello." once `release` is notified.
+    #[derive(Default)]
+    struct HeldProvider {
+        release: Arc<Notify>,
+    }
+
+    impl AgentProvider for HeldProvider {
+        fn stream_reply(&self, _: &str, _: &[ChatMessage], _: &str) -> ReplyStream {
+            let release = self.release.clone();
+            Box::pin(futures_util::stream::once(async move {
+                release.notified().await;
+                Ok("Hello.".to_string())
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn input_is_turned_away_while_a_reply_streams() {
+        let provider = Arc::new(HeldProvider::default());
+        let mut game_state = GameState::load(None).unwrap();
+        game_state.agent_provider = Some(provider.clone());
+        game_state.mud_config.agent =
+            Some(toml::from_str("provider = \"ollama\"\nmodel = \"test\"").unwrap());
+        let game_state = Arc::new(game_state);
+        let mut rx = game_state.message_tx.subscribe();
+
+        let mut npc = Entity::new(
+            2,
+            EntityType::Character,
+            Location {
+                world_id: "w1".to_string(),
+                dungeon_id: "d1".to_string(),
+                room_id: "r1".to_string(),
+            },
+        );
+        npc.description = Some("sage".to_string());
+        npc.ai
+            .get_or_insert_default()
+            .agent_conversation_state
+            .get_or_insert_default()
+            .contexts
+            .insert(9, AgentConversationContext::default());
+        game_state.active_entities.write().await.insert(2, npc);
+
+        agent_reply(&game_state, 1, 2, 9, "Hi".to_string()).await;
+        agent_reply(&game_state, 1, 2, 9, "Hello?".to_string()).await;
+        let refusal = rx.recv().await.unwrap();
+        assert!(
+            matches!(refusal.message, Message::Complete(text) if text == "The sage is still answering.")
+        );
+
+        provider.release.notify_one();
+        loop {
+            let message = rx.recv().await.unwrap();
+            if matches!(
+                message.message,
+                Message::Streaming {
+                    state: StreamingState::Complete,
+                    ..
+                }
+            ) {
+                break;
+            }
+        }
+        // The history is written after the final chunk, under the entities lock.
+        let ctx = loop {
+            tokio::task::yield_now().await;
+            let entities = game_state.active_entities.read().await;
+            let ai = entities[&2].ai.as_ref().unwrap();
+            let ctx = &ai.agent_conversation_state.as_ref().unwrap().contexts[&9];
+            if !ctx.replying {
+                break ctx.clone();
+            }
+        };
+        assert_eq!(
+            ctx.history,
+            vec![ChatMessage::user("Hi"), ChatMessage::assistant("Hello.")]
+        );
+    }
+
+    #[test]
+    fn record_exchange_trims_oldest_messages() {
+        let mut history = vec![ChatMessage::user("one"), ChatMessage::assistant("two")];
+        record_exchange(&mut history, "three".to_string(), "four".to_string(), 3);
+        assert_eq!(
+            history,
+            vec![
+                ChatMessage::assistant("two"),
+                ChatMessage::user("three"),
+                ChatMessage::assistant("four"),
+            ]
+        );
+    }
+}
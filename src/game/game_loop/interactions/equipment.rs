@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::game::component::Attribute;
+use crate::game::config::EquipmentConfig;
+use crate::game::entity::Entity;
+use crate::game::player::Player;
+use crate::game::{GameState, messaging};
+use crate::persistence::{Database, entity_repo};
+
+use super::inventory::find_carried;
+
+/// Wear a carried item, applying its attribute modifiers to the player.
+pub async fn wear(game_state: &Arc<GameState>, db: &Database, player: &Player, query: &str) {
+    let Some((item_id, item_name)) = find_carried(game_state, player, query).await else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You aren't carrying {query}."),
+        );
+        return;
+    };
+    let names = game_state.player_names().await;
+
+    let attributes = {
+        let mut entities = game_state.active_entities.write().await;
+        let Some(item) = entities.get(&item_id) else {
+            return;
+        };
+        let Some(equipment) = equipment_config(game_state, item) else {
+            drop(entities);
+            messaging::message(
+                &game_state.message_tx,
+                player.id,
+                format!("You can't wear the {item_name}."),
+            );
+            return;
+        };
+        if item.equipped {
+            drop(entities);
+            messaging::message(
+                &game_state.message_tx,
+                player.id,
+                format!("You're already wearing the {item_name}."),
+            );
+            return;
+        }
+        let occupied = entities
+            .values()
+            .filter(|e| e.holder_id == Some(player.entity_id) && e.equipped)
+            .find(|e| equipment_config(game_state, e).is_some_and(|c| c.slot == equipment.slot))
+            .map(|e| e.display_name(&names));
+        if let Some(worn) = occupied {
+            drop(entities);
+            messaging::message(
+                &game_state.message_tx,
+                player.id,
+                format!(
+                    "You're already wearing the {worn} on your {}. Remove it first.",
+                    slot_label(&equipment.slot)
+                ),
+            );
+            return;
+        }
+
+        if let Some(item) = entities.get_mut(&item_id) {
+            item.equipped = true;
+        }
+        let Some(wearer) = entities.get_mut(&player.entity_id) else {
+            return;
+        };
+        for modifier in &equipment.modifiers {
+            if let Some(attribute) = wearer.attributes.get_mut(&modifier.attribute_id) {
+                attribute.set_modifier(item_id, modifier.value);
+            }
+        }
+        wearer.attributes.clone()
+    };
+
+    persist(db, player.entity_id, item_id, true, &attributes).await;
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You put on the {item_name}."),
+    );
+}
+
+/// Take off a worn item, removing its attribute modifiers from the player.
+pub async fn remove(game_state: &Arc<GameState>, db: &Database, player: &Player, query: &str) {
+    let Some((item_id, item_name)) = find_carried(game_state, player, query).await else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You aren't wearing {query}."),
+        );
+        return;
+    };
+
+    let attributes = {
+        let mut entities = game_state.active_entities.write().await;
+        match entities.get_mut(&item_id) {
+            Some(item) if item.equipped => item.equipped = false,
+            _ => {
+                drop(entities);
+                messaging::message(
+                    &game_state.message_tx,
+                    player.id,
+                    format!("You aren't wearing the {item_name}."),
+                );
+                return;
+            }
+        }
+        let Some(wearer) = entities.get_mut(&player.entity_id) else {
+            return;
+        };
+        for attribute in wearer.attributes.values_mut() {
+            attribute.remove_modifier(item_id);
+        }
+        wearer.attributes.clone()
+    };
+
+    persist(db, player.entity_id, item_id, false, &attributes).await;
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You take off the {item_name}."),
+    );
+}
+
+/// Show worn items and the attributes they change.
+pub async fn list(game_state: &Arc<GameState>, player: &Player) {
+    let names = game_state.player_names().await;
+    let (mut worn, mut modified) = {
+        let entities = game_state.active_entities.read().await;
+        let worn: Vec<String> = entities
+            .values()
+            .filter(|e| e.holder_id == Some(player.entity_id) && e.equipped)
+            .map(|e| {
+                let slot = equipment_config(game_state, e)
+                    .map(|c| slot_label(&c.slot))
+                    .unwrap_or_default();
+                format!("{slot}: {}", e.display_name(&names))
+            })
+            .collect();
+        let modified: Vec<String> = entities
+            .get(&player.entity_id)
+            .map(|e| {
+                e.attributes
+                    .values()
+                    .filter(|a| !a.modifiers.is_empty())
+                    .map(|a| {
+                        let bonus = a.effective_value() - a.current_value;
+                        format!(
+                            "{} {} (base {}, {bonus:+})",
+                            a.definition_id,
+                            a.effective_value(),
+                            a.current_value
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        (worn, modified)
+    };
+
+    if worn.is_empty() {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            "You aren't wearing anything special.",
+        );
+        return;
+    }
+    worn.sort();
+    modified.sort();
+    let mut content = format!("You are wearing:\n  {}", worn.join("\n  "));
+    if !modified.is_empty() {
+        content.push_str(&format!("\nAttributes:\n  {}", modified.join("\n  ")));
+    }
+    messaging::message(&game_state.message_tx, player.id, content);
+}
+
+fn equipment_config<'a>(game_state: &'a GameState, entity: &Entity) -> Option<&'a EquipmentConfig> {
+    entity
+        .config_id
+        .as_deref()
+        .and_then(|id| game_state.entity_configs.get(id))?
+        .equipment
+        .as_ref()
+}
+
+fn slot_label(slot: &str) -> String {
+    slot.replace('_', " ")
+}
+
+async fn persist(
+    db: &Database,
+    wearer_id: i64,
+    item_id: i64,
+    equipped: bool,
+    attributes: &HashMap<String, Attribute>,
+) {
+    if let Err(e) = entity_repo::update_equipped(db.pool(), item_id, equipped).await {
+        tracing::error!(error = %e, "Failed to update equipped item");
+    }
+    if let Err(e) = entity_repo::update_attributes(db.pool(), wearer_id, attributes).await {
+        tracing::error!(error = %e, "Failed to persist equipment modifiers");
+    }
+}
@@ -6,7 +6,35 @@ use crate::game::{GameState, messaging};
 pub async fn process(game_state: &Arc<GameState>, player: &Player) {
     let help_text = r"Commands:
   n/north, s/south, e/east, w/west - Move
+  ne, nw, se, sw, u/up, d/down - Move diagonally, up or down (climb/descend)
+  enter <name>, go <name> - Take a named exit or go into something, like a portal
   l/look - Examine current room
+  search - Look for anything hidden here
+  get/take <item>, drop <item> - Pick up or put down an item
+  give <item> to <target> - Hand an item to someone
+  i/inventory - List what you're carrying
+  wear/wield <item>, remove <item> - Put on or take off equipment
+  eq/equipment - Show what you're wearing and how it changes you
+  score/stats - Show your attributes and what's affecting you (F2 toggles the sheet)
+  open <container>, look in <container> - See what a chest or corpse holds
+  get <item> from <container> - Take something out of a container
+  use <item> [on <target>] - Use an item; drink, eat and read work too
+  <verb> <thing> - Try other things on objects around you, e.g. pull lever
+  say <text> - Say something to everyone in the room
+  emote/me <action> - Act something out, like: me waves
+  whisper <player> <text> - Whisper to a player in the room
+  shout <text> - Shout to everyone in the dungeon
+  tell <player> <text> - Send a private message to a player anywhere
+  reply <text> - Answer the last player who sent you a tell
+  mail <player> <text> - Leave a letter, even for players who are away
+  mail - Read your new letters
+  chat <channel> <text> - Post to a chat channel
+  join/leave <channel> - Join or leave a chat channel
+  channels - List chat channels
+  talk - Talk to someone nearby
+  <anything else> - Say it to whoever you're talking to (bye to leave)
+  attack <target> - Start or continue a fight
+  flee - Run away from a fight
   h/help - Show this help";
     messaging::message(&game_state.message_tx, player.id, help_text);
 }
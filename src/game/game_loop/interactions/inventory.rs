@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::game::entity::{Entity, EntityType};
+use crate::game::player::Player;
+use crate::game::{GameState, Location, messaging};
+use crate::persistence::{Database, entity_repo};
+
+use super::target::{self, matches};
+
+/// Pick up an object in the player's room.
+pub async fn get(game_state: &Arc<GameState>, db: &Database, player: &Player, query: &str) {
+    let Some(location) = location(game_state, player).await else {
+        return;
+    };
+    let names = game_state.player_names().await;
+    let item = match target::find_in_room(game_state, &location, player.entity_id, query).await {
+        Some(id) => {
+            let entities = game_state.active_entities.read().await;
+            entities
+                .get(&id)
+                .filter(|e| e.entity_type == EntityType::Object)
+                .map(|e| (e.id, e.display_name(&names)))
+        }
+        None => None,
+    };
+    let Some((item_id, item_name)) = item else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You can't pick up {query}."),
+        );
+        return;
+    };
+
+    set_holder(game_state, db, item_id, Some(player.entity_id)).await;
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You pick up the {item_name}."),
+    );
+    messaging::message_location(
+        game_state,
+        &location,
+        player.entity_id,
+        format!("{} picks up the {item_name}.", player.name),
+    )
+    .await;
+}
+
+/// Drop a carried item into the player's room.
+pub async fn drop(game_state: &Arc<GameState>, db: &Database, player: &Player, query: &str) {
+    let Some(location) = location(game_state, player).await else {
+        return;
+    };
+    let Some((item_id, item_name)) = find_carried(game_state, player, query).await else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You aren't carrying {query}."),
+        );
+        return;
+    };
+
+    if is_equipped(game_state, item_id).await {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You'll need to remove the {item_name} first."),
+        );
+        return;
+    }
+    set_holder(game_state, db, item_id, None).await;
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You drop the {item_name}."),
+    );
+    messaging::message_location(
+        game_state,
+        &location,
+        player.entity_id,
+        format!("{} drops the {item_name}.", player.name),
+    )
+    .await;
+}
+
+/// Hand a carried item to someone in the player's room.
+pub async fn give(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    item_query: &str,
+    target_query: &str,
+) {
+    let Some(location) = location(game_state, player).await else {
+        return;
+    };
+    let Some((item_id, item_name)) = find_carried(game_state, player, item_query).await else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You aren't carrying {item_query}."),
+        );
+        return;
+    };
+    if is_equipped(game_state, item_id).await {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You'll need to remove the {item_name} first."),
+        );
+        return;
+    }
+    let names = game_state.player_names().await;
+    let recipient =
+        match target::find_in_room(game_state, &location, player.entity_id, target_query).await {
+            Some(id) => {
+                let entities = game_state.active_entities.read().await;
+                entities
+                    .get(&id)
+                    .filter(|e| e.entity_type != EntityType::Object)
+                    .map(|e| (e.id, e.display_name(&names)))
+            }
+            None => None,
+        };
+    let Some((recipient_id, recipient_name)) = recipient else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You don't see {target_query} here."),
+        );
+        return;
+    };
+
+    set_holder(game_state, db, item_id, Some(recipient_id)).await;
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You give the {item_name} to {recipient_name}."),
+    );
+    let recipient_player = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .find(|p| p.entity_id == recipient_id)
+        .map(|p| p.id);
+    if let Some(recipient_player) = recipient_player {
+        messaging::message(
+            &game_state.message_tx,
+            recipient_player,
+            format!("{} gives you the {item_name}.", player.name),
+        );
+    }
+}
+
+/// List what the player is carrying.
+pub async fn list(game_state: &Arc<GameState>, player: &Player) {
+    let names = game_state.player_names().await;
+    let mut items: Vec<String> = {
+        let entities = game_state.active_entities.read().await;
+        entities
+            .values()
+            .filter(|e| e.holder_id == Some(player.entity_id))
+            .map(|e| {
+                let name = e.display_name(&names);
+                if e.equipped {
+                    format!("{name} (worn)")
+                } else {
+                    name
+                }
+            })
+            .collect()
+    };
+    let content = if items.is_empty() {
+        "You aren't carrying anything.".to_string()
+    } else {
+        items.sort();
+        format!("You are carrying:\n  {}", items.join("\n  "))
+    };
+    messaging::message(&game_state.message_tx, player.id, content);
+}
+
+/// Move everything `holder_id` carries, and everything those carry, to `location`.
+pub async fn move_carried(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    holder_id: i64,
+    location: &Location,
+) {
+    let moved = {
+        let mut entities = game_state.active_entities.write().await;
+        let carried = carried_by(&entities, holder_id);
+        for id in &carried {
+            if let Some(entity) = entities.get_mut(id) {
+                entity.location = location.clone();
+            }
+        }
+        carried
+    };
+    for id in moved {
+        if let Err(e) = entity_repo::update_location(db.pool(), id, location).await {
+            tracing::error!(error = %e, "Failed to move carried entity");
+        }
+    }
+}
+
+/// Ids of every entity carried by `holder_id`, directly or inside something it carries.
+pub fn carried_by(entities: &HashMap<i64, Entity>, holder_id: i64) -> Vec<i64> {
+    let mut carried = Vec::new();
+    let mut holders = vec![holder_id];
+    while let Some(holder) = holders.pop() {
+        for entity in entities.values().filter(|e| e.holder_id == Some(holder)) {
+            if entity.id != holder_id && !carried.contains(&entity.id) {
+                carried.push(entity.id);
+                holders.push(entity.id);
+            }
+        }
+    }
+    carried
+}
+
+/// Find a carried item the query refers to, returning its id and display name.
+pub async fn find_carried(
+    game_state: &Arc<GameState>,
+    player: &Player,
+    query: &str,
+) -> Option<(i64, String)> {
+    let names = game_state.player_names().await;
+    let entities = game_state.active_entities.read().await;
+    let mut candidates: Vec<&Entity> = entities
+        .values()
+        .filter(|e| e.holder_id == Some(player.entity_id))
+        .filter(|e| matches(e, &names, query))
+        .collect();
+    candidates.sort_by_key(|e| e.id);
+    candidates.first().map(|e| (e.id, e.display_name(&names)))
+}
+
+/// Whether the item is being worn.
+pub async fn is_equipped(game_state: &Arc<GameState>, item_id: i64) -> bool {
+    let entities = game_state.active_entities.read().await;
+    entities.get(&item_id).is_some_and(|e| e.equipped)
+}
+
+async fn set_holder(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    item_id: i64,
+    holder_id: Option<i64>,
+) {
+    {
+        let mut entities = game_state.active_entities.write().await;
+        if let Some(item) = entities.get_mut(&item_id) {
+            item.holder_id = holder_id;
+        }
+    }
+    if let Err(e) = entity_repo::update_holder(db.pool(), item_id, holder_id).await {
+        tracing::error!(error = %e, "Failed to update entity holder");
+    }
+}
+
+async fn location(game_state: &Arc<GameState>, player: &Player) -> Option<Location> {
+    let entities = game_state.active_entities.read().await;
+    entities.get(&player.entity_id).map(|e| e.location.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(id: i64, holder_id: Option<i64>) -> Entity {
+        let mut entity = Entity::new(
+            id,
+            EntityType::Object,
+            Location {
+                world_id: "w1".to_string(),
+                dungeon_id: "d1".to_string(),
+                room_id: "r1".to_string(),
+            },
+        );
+        entity.holder_id = holder_id;
+        entity
+    }
+
+    #[test]
+    fn carried_by_includes_nested_items() {
+        let entities: HashMap<i64, Entity> = [
+            entity(1, None),
+            entity(2, Some(1)),
+            entity(3, Some(2)),
+            entity(4, None),
+        ]
+        .into_iter()
+        .map(|e| (e.id, e))
+        .collect();
+        let mut carried = carried_by(&entities, 1);
+        carried.sort();
+        assert_eq!(carried, vec![2, 3]);
+        assert!(carried_by(&entities, 4).is_empty());
+    }
+}
@@ -1,37 +1,50 @@
 use std::sync::Arc;
 
 use crate::game::entity::EntityType;
+use crate::game::messaging::{RoomDescription, VisibleEntity};
 use crate::game::player::Player;
 use crate::game::{GameState, messaging};
 use crate::persistence::Database;
 use crate::persistence::room_repo;
 
 pub async fn process(game_state: &Arc<GameState>, db: &Database, player: &Player) {
-    let (location, entity_descriptions) = {
+    let names = game_state.player_names().await;
+    let (location, attributes, visible) = {
         let entities = game_state.active_entities.read().await;
-        let location = match entities.get(&player.entity_id) {
-            Some(e) => e.location.clone(),
+        let revealed = game_state.revealed.read().await;
+        let (location, attributes) = match entities.get(&player.entity_id) {
+            Some(e) => (e.location.clone(), e.attributes.clone()),
             None => return,
         };
-        let descriptions: Vec<(EntityType, Option<String>)> = entities
+        let visible: Vec<VisibleEntity> = entities
             .values()
             .filter(|e| e.id != player.entity_id && e.location == location)
-            .map(|e| (e.entity_type.clone(), e.description.clone()))
+            .filter(|e| e.holder_id.is_none())
+            .filter(|e| !game_state.is_hidden(e, &revealed))
+            .map(|e| {
+                let line = match (&e.entity_type, names.get(&e.id), &e.description) {
+                    (EntityType::Player, Some(name), _) => format!("{name} is here."),
+                    (_, _, Some(description)) => description.clone(),
+                    (entity_type, _, None) => {
+                        format!("A {} is here.", entity_type_label(entity_type))
+                    }
+                };
+                VisibleEntity {
+                    entity_type: e.entity_type.clone(),
+                    line,
+                }
+            })
             .collect();
-        (location, descriptions)
+        (location, attributes, visible)
     };
 
-    if let Ok(Some(room)) =
+    let Ok(Some(room)) =
         room_repo::find_by_id(db.pool(), &location.dungeon_id, &location.room_id).await
-    {
-        messaging::message_room_description(&game_state.message_tx, player.id, &room);
-    }
-
-    for (entity_type, description) in entity_descriptions {
-        let content = description
-            .unwrap_or_else(|| format!("A {} is here.", entity_type_label(&entity_type)));
-        messaging::message(&game_state.message_tx, player.id, content);
-    }
+    else {
+        return;
+    };
+    let description = RoomDescription::new(&room, &location, &attributes, visible);
+    messaging::room_description(&game_state.message_tx, player.id, description);
 }
 
 fn entity_type_label(entity_type: &EntityType) -> &'static str {
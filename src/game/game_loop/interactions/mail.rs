@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::game::player::Player;
+use crate::game::{GameState, messaging};
+use crate::persistence::{Database, mail_repo, player_repo};
+
+/// Leave a letter for another player, online or not.
+pub async fn send(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    target: &str,
+    content: &str,
+) {
+    let pool = db.pool();
+    let recipient = match player_repo::find_by_name(pool, target).await {
+        Ok(Some(recipient)) => recipient,
+        Ok(None) => {
+            messaging::message(
+                &game_state.message_tx,
+                player.id,
+                format!("There's nobody called {target}."),
+            );
+            return;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to look up mail recipient");
+            return;
+        }
+    };
+    if let Err(e) = mail_repo::insert(pool, player.id, recipient.id, content).await {
+        tracing::error!(error = %e, "Failed to save mail");
+        return;
+    }
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You send a letter to {}.", recipient.name),
+    );
+    let online = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .any(|p| p.id == recipient.id);
+    if online {
+        notify_unread(game_state, db, &recipient).await;
+    }
+}
+
+/// Show the player their unread mail and mark it read.
+pub async fn read(game_state: &Arc<GameState>, db: &Database, player: &Player) {
+    let pool = db.pool();
+    let unread = match mail_repo::find_unread(pool, player.id).await {
+        Ok(unread) => unread,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load mail");
+            return;
+        }
+    };
+    if unread.is_empty() {
+        messaging::message(&game_state.message_tx, player.id, "You have no new mail.");
+        return;
+    }
+    for mail in unread {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!(
+                "Letter from {} ({}):\n  {}",
+                mail.sender_name, mail.sent_at, mail.content
+            ),
+        );
+        if let Err(e) = mail_repo::mark_read(pool, mail.id).await {
+            tracing::error!(error = %e, "Failed to mark mail read");
+        }
+    }
+}
+
+/// Tell the player how many unread letters are waiting, if any.
+pub async fn notify_unread(game_state: &Arc<GameState>, db: &Database, player: &Player) {
+    let count = match mail_repo::count_unread(db.pool(), player.id).await {
+        Ok(count) => count,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to count unread mail");
+            return;
+        }
+    };
+    let content = match count {
+        0 => return,
+        1 => "You have a new letter. Type mail to read it.".to_string(),
+        n => format!("You have {n} new letters. Type mail to read them."),
+    };
+    messaging::message(&game_state.message_tx, player.id, content);
+}
@@ -1,84 +1,43 @@
 use std::sync::Arc;
 
-use tracing;
-
 use crate::game::component::interaction::Direction;
+use crate::game::map::Navigation;
 use crate::game::player::Player;
-use crate::game::{GameState, Location, messaging};
+use crate::game::{GameState, Location, Room, messaging};
 use crate::persistence::Database;
-use crate::persistence::{entity_repo, room_repo};
+use crate::persistence::room_repo;
 
-use super::look;
+use super::{look, portal, warp};
 
+/// Move the player through the exit in `direction` of their current room.
 pub async fn process(
     game_state: &Arc<GameState>,
     db: &Database,
     player: &Player,
     direction: Direction,
 ) {
-    let location = {
-        let entities = game_state.active_entities.read().await;
-        match entities.get(&player.entity_id) {
-            Some(e) => e.location.clone(),
-            None => return,
-        }
-    };
-
-    let room = match room_repo::find_by_id(db.pool(), &location.dungeon_id, &location.room_id).await
-    {
-        Ok(Some(r)) => r,
-        _ => return,
-    };
-
-    let nav = match direction {
-        Direction::North => room.north,
-        Direction::South => room.south,
-        Direction::East => room.east,
-        Direction::West => room.west,
+    if is_in_battle(game_state, player).await {
+        return;
+    }
+    let Some((location, room)) = current_room(game_state, db, player).await else {
+        return;
     };
 
-    match nav {
+    match room.exit(&direction) {
         Some(nav) => {
-            let room_id = match nav.room_id {
-                Some(id) => id,
-                None => {
-                    messaging::message(
-                        &game_state.message_tx,
-                        player.id,
-                        "Nothing in that direction.",
-                    );
-                    return;
-                }
-            };
-            let old_world_id = location.world_id.clone();
-            let old_dungeon_id = location.dungeon_id.clone();
-            let new_location = Location {
-                world_id: nav.world_id.unwrap_or(location.world_id),
-                dungeon_id: nav.dungeon_id.unwrap_or(location.dungeon_id),
-                room_id,
-            };
-            {
-                let mut entities = game_state.active_entities.write().await;
-                if let Some(entity) = entities.get_mut(&player.entity_id) {
-                    entity.location = new_location.clone();
-                }
-            }
-            if let Err(e) =
-                entity_repo::update_location(db.pool(), player.entity_id, &new_location).await
-            {
-                tracing::error!(error = %e, "Failed to update entity location in DB");
-            }
-            if (new_location.world_id != old_world_id || new_location.dungeon_id != old_dungeon_id)
-                && let Err(e) = game_state.sync_active_entities(db.pool()).await
-            {
-                tracing::error!(error = %e, "Failed to sync active entities after dungeon change");
-            }
-            messaging::message(
-                &game_state.message_tx,
-                player.id,
-                format!("You move {direction}."),
-            );
-            look::process(game_state, db, player).await;
+            follow(
+                game_state,
+                db,
+                player,
+                &location,
+                nav,
+                Departure {
+                    you: format!("You move {direction}."),
+                    leaves: format!("{} leaves {direction}.", player.name),
+                    arrives: format!("{} arrives {}.", player.name, direction.arrival()),
+                },
+            )
+            .await;
         }
         None => {
             messaging::message(
@@ -89,3 +48,111 @@ pub async fn process(
         }
     }
 }
+
+/// Take the named exit of the player's current room, e.g. `enter tavern`. Falls back to entering
+/// a portal entity in the room when no exit has that name.
+pub async fn enter(game_state: &Arc<GameState>, db: &Database, player: &Player, name: &str) {
+    if is_in_battle(game_state, player).await {
+        return;
+    }
+    let Some((location, room)) = current_room(game_state, db, player).await else {
+        return;
+    };
+
+    match room.named_exit(name) {
+        Some((exit, nav)) => {
+            follow(
+                game_state,
+                db,
+                player,
+                &location,
+                nav,
+                Departure {
+                    you: format!("You head to the {exit}."),
+                    leaves: format!("{} leaves for the {exit}.", player.name),
+                    arrives: format!("{} arrives.", player.name),
+                },
+            )
+            .await;
+        }
+        None => portal::process(game_state, db, player, name).await,
+    }
+}
+
+async fn is_in_battle(game_state: &Arc<GameState>, player: &Player) -> bool {
+    let in_battle = game_state
+        .engagements
+        .is_entity_in_battle(player.entity_id)
+        .await;
+    if in_battle {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            "You can't leave in the middle of a fight. Try to flee!",
+        );
+    }
+    in_battle
+}
+
+async fn current_room(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+) -> Option<(Location, Room)> {
+    let location = {
+        let entities = game_state.active_entities.read().await;
+        entities.get(&player.entity_id)?.location.clone()
+    };
+    let room = room_repo::find_by_id(db.pool(), &location.dungeon_id, &location.room_id)
+        .await
+        .ok()
+        .flatten()?;
+    Some((location, room))
+}
+
+/// Messages for the player taking an exit and for everyone in the rooms they leave and enter.
+struct Departure {
+    you: String,
+    leaves: String,
+    arrives: String,
+}
+
+async fn follow(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    location: &Location,
+    nav: &Navigation,
+    departure: Departure,
+) {
+    let Some(room_id) = nav.room_id.clone() else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            "Nothing in that direction.",
+        );
+        return;
+    };
+    let new_location = Location {
+        world_id: nav
+            .world_id
+            .clone()
+            .unwrap_or_else(|| location.world_id.clone()),
+        dungeon_id: nav
+            .dungeon_id
+            .clone()
+            .unwrap_or_else(|| location.dungeon_id.clone()),
+        room_id,
+    };
+    messaging::message_location(game_state, location, player.entity_id, departure.leaves).await;
+    warp::move_entity(game_state, db, player.entity_id, &new_location).await;
+    messaging::message_location(
+        game_state,
+        &new_location,
+        player.entity_id,
+        departure.arrives,
+    )
+    .await;
+    messaging::message(&game_state.message_tx, player.id, departure.you);
+    look::process(game_state, db, player).await;
+}
@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use crate::game::component::{CustomVerb, Interaction};
+use crate::game::game_loop::effects;
+use crate::game::player::Player;
+use crate::game::{GameState, Location, messaging};
+use crate::persistence::{Database, room_repo};
+
+use super::inventory::find_carried;
+use super::target;
+
+/// Run a custom verb from the interactions of an entity the player can see or is carrying,
+/// e.g. `pull lever`. Returns false if nothing here answers to the verb, so the caller can fall
+/// back to its usual handling.
+pub async fn act(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    verb: &str,
+    query: &str,
+) -> bool {
+    let query = query.trim();
+    let query = query.strip_prefix("the ").unwrap_or(query);
+    if query.is_empty() {
+        return false;
+    }
+    let Some(location) = game_state
+        .active_entities
+        .read()
+        .await
+        .get(&player.entity_id)
+        .map(|e| e.location.clone())
+    else {
+        return false;
+    };
+
+    let mut candidates = Vec::new();
+    if let Some(id) = target::find_in_room(game_state, &location, player.entity_id, query).await {
+        candidates.push(id);
+    }
+    if let Some((id, _)) = find_carried(game_state, player, query).await {
+        candidates.push(id);
+    }
+    let found = {
+        let entities = game_state.active_entities.read().await;
+        candidates.iter().find_map(|id| {
+            entities
+                .get(id)?
+                .interactions
+                .iter()
+                .find_map(|interaction| match interaction {
+                    Interaction::Verb(custom) if custom.matches(verb) => Some(custom.clone()),
+                    _ => None,
+                })
+        })
+    };
+    let Some(custom) = found else {
+        return false;
+    };
+    perform(game_state, db, player, &location, &custom).await;
+    true
+}
+
+async fn perform(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    location: &Location,
+    verb: &CustomVerb,
+) {
+    let failure = verb.failure.as_deref().unwrap_or("Nothing happens.");
+    if let Some(check) = &verb.check {
+        let passed = game_state
+            .active_entities
+            .read()
+            .await
+            .get(&player.entity_id)
+            .is_some_and(|e| check.passes(&e.attributes));
+        if !passed {
+            messaging::message(&game_state.message_tx, player.id, failure);
+            return;
+        }
+    }
+    if let Some(skill_check) = &verb.roll {
+        let Some(roll) = game_state.roll_check(player.entity_id, skill_check).await else {
+            return;
+        };
+        messaging::message(&game_state.message_tx, player.id, roll.narrate());
+        if !roll.succeeded() {
+            messaging::message(&game_state.message_tx, player.id, failure);
+            return;
+        }
+    }
+
+    messaging::message(&game_state.message_tx, player.id, verb.response.clone());
+    if let Some(room_response) = &verb.room_response {
+        messaging::message_location(
+            game_state,
+            location,
+            player.entity_id,
+            room_response.replace("{actor}", &player.name),
+        )
+        .await;
+    }
+    for effect in &verb.effects {
+        if let Err(e) = effects::attach(game_state, db, player.entity_id, effect).await {
+            tracing::error!(error = %e, "Failed to attach verb effect");
+        }
+    }
+    if verb.exits.is_empty() {
+        return;
+    }
+    let mut room =
+        match room_repo::find_by_id(db.pool(), &location.dungeon_id, &location.room_id).await {
+            Ok(Some(room)) => room,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load room for exit change");
+                return;
+            }
+        };
+    for change in &verb.exits {
+        room.set_exit(&change.exit, change.to.clone());
+    }
+    if let Err(e) = room_repo::update_exits(db.pool(), &room, &location.dungeon_id).await {
+        tracing::error!(error = %e, "Failed to update room exits");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::entity::{Entity, EntityType};
+    use crate::game::messaging::Message;
+
+    fn location() -> Location {
+        Location {
+            world_id: "w1".to_string(),
+            dungeon_id: "d1".to_string(),
+            room_id: "r1".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn verbs_with_a_roll_only_work_when_it_passes() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let game_state = Arc::new(GameState::load(None).unwrap());
+        let mut rx = game_state.message_tx.subscribe();
+        let player = Player {
+            id: 1,
+            client_id: "client".to_string(),
+            name: "Alice".to_string(),
+            entity_id: 10,
+        };
+        let mut lever = Entity::new(11, EntityType::Object, location());
+        lever.config_id = Some("entities/lever".to_string());
+        lever.interactions = vec![Interaction::Verb(
+            toml::from_str(
+                r#"
+verb = "pull"
+response = "The lever clunks down."
+failure = "It won't budge."
+roll = { attribute_id = "strength", difficulty = 10 }
+"#,
+            )
+            .unwrap(),
+        )];
+        game_state.active_entities.write().await.extend([
+            (10, Entity::new(10, EntityType::Player, location())),
+            (11, lever),
+        ]);
+        // Seed 14 rolls a natural 1 and then a natural 20.
+        *game_state.rng() = fastrand::Rng::with_seed(14);
+
+        let mut replies = Vec::new();
+        for _ in 0..2 {
+            assert!(act(&game_state, &db, &player, "pull", "lever").await);
+            while let Ok(message) = rx.try_recv() {
+                if let Message::Complete(text) = message.message {
+                    replies.push(text);
+                }
+            }
+        }
+        assert_eq!(replies.len(), 4);
+        assert!(replies[0].contains("critical failure"));
+        assert_eq!(replies[1], "It won't budge.");
+        assert!(replies[2].contains("critical success"));
+        assert_eq!(replies[3], "The lever clunks down.");
+    }
+}
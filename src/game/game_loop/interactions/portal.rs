@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::game::player::Player;
+use crate::game::{GameState, messaging};
+use crate::persistence::Database;
+
+use super::target;
+use super::{look, warp};
+
+/// Enter a portal entity in the player's room, warping to the destination from its config.
+pub async fn process(game_state: &Arc<GameState>, db: &Database, player: &Player, name: &str) {
+    let location = {
+        let entities = game_state.active_entities.read().await;
+        match entities.get(&player.entity_id) {
+            Some(e) => e.location.clone(),
+            None => return,
+        }
+    };
+
+    let names = game_state.player_names().await;
+    let portal = match target::find_in_room(game_state, &location, player.entity_id, name).await {
+        Some(id) => {
+            let entities = game_state.active_entities.read().await;
+            entities.get(&id).and_then(|e| {
+                let destination = e
+                    .config_id
+                    .as_deref()
+                    .and_then(|id| game_state.entity_configs.get(id))?
+                    .portal
+                    .clone()?;
+                Some((e.display_name(&names), destination))
+            })
+        }
+        None => None,
+    };
+    let Some((portal_name, destination)) = portal else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You can't enter {name}."),
+        );
+        return;
+    };
+
+    if warp::warp_entity(game_state, db, player.entity_id, &destination)
+        .await
+        .is_none()
+    {
+        tracing::warn!(portal = portal_name, "portal destination does not exist");
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("The {portal_name} flickers, but nothing happens."),
+        );
+        return;
+    }
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("You step through the {portal_name}."),
+    );
+    look::process(game_state, db, player).await;
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use crate::game::component::SkillCheck;
+use crate::game::player::Player;
+use crate::game::{GameState, messaging};
+
+/// Roll to find each hidden entity in the player's room. Found entities become visible to
+/// everyone until the next world update that finds the room empty. Each visit to a room gets one
+/// search, so players can't keep rolling until they get lucky.
+pub async fn process(game_state: &Arc<GameState>, player: &Player) {
+    let names = game_state.player_names().await;
+    let (location, hidden) = {
+        let entities = game_state.active_entities.read().await;
+        let revealed = game_state.revealed.read().await;
+        let Some(location) = entities.get(&player.entity_id).map(|e| e.location.clone()) else {
+            return;
+        };
+        let mut hidden: Vec<(i64, String, SkillCheck)> = entities
+            .values()
+            .filter(|e| e.location == location && e.holder_id.is_none())
+            .filter(|e| game_state.is_hidden(e, &revealed))
+            .filter_map(|e| {
+                let config = game_state.entity_configs.get(e.config_id.as_deref()?)?;
+                let name = e
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| format!("A {} is here.", e.display_name(&names)));
+                Some((e.id, name, config.hidden.clone()?))
+            })
+            .collect();
+        hidden.sort_by_key(|(id, _, _)| *id);
+        (location, hidden)
+    };
+    {
+        let mut searched = game_state.searched.write().await;
+        if searched.get(&player.entity_id) == Some(&location) {
+            messaging::message(
+                &game_state.message_tx,
+                player.id,
+                "You've already searched here.",
+            );
+            return;
+        }
+        searched.insert(player.entity_id, location.clone());
+    }
+
+    let mut found = Vec::new();
+    for (id, description, check) in hidden {
+        let Some(roll) = game_state.roll_check(player.entity_id, &check).await else {
+            return;
+        };
+        messaging::message(&game_state.message_tx, player.id, roll.narrate());
+        if roll.succeeded() {
+            found.push((id, description));
+        }
+    }
+
+    if found.is_empty() {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            "You search around but find nothing.",
+        );
+        return;
+    }
+    let mut revealed = game_state.revealed.write().await;
+    for (id, description) in found {
+        revealed.insert(id);
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("You find something! {description}"),
+        );
+    }
+    drop(revealed);
+    messaging::message_location(
+        game_state,
+        &location,
+        player.entity_id,
+        format!("{} finds something hidden.", player.name),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::entity::{Entity, EntityType};
+    use crate::game::game_loop::world_update;
+    use crate::game::{EntityConfig, Location};
+    use crate::persistence::Database;
+
+    fn location(room_id: &str) -> Location {
+        Location {
+            world_id: "w1".to_string(),
+            dungeon_id: "d1".to_string(),
+            room_id: room_id.to_string(),
+        }
+    }
+
+    /// Alice (entity 10) online in `r1` with a key (entity 11) hidden behind a DC 10 check.
+    async fn room_with_hidden_key() -> (Arc<GameState>, Player) {
+        let mut game_state = GameState::load(None).unwrap();
+        let config: EntityConfig = toml::from_str(
+            r#"
+entity_type = "object"
+hidden = { attribute_id = "perception", difficulty = 10 }
+"#,
+        )
+        .unwrap();
+        game_state
+            .entity_configs
+            .insert("entities/key".to_string(), config);
+        let game_state = Arc::new(game_state);
+        let player = Player {
+            id: 1,
+            client_id: "client".to_string(),
+            name: "Alice".to_string(),
+            entity_id: 10,
+        };
+        let mut key = Entity::new(11, EntityType::Object, location("r1"));
+        key.config_id = Some("entities/key".to_string());
+        game_state.active_entities.write().await.extend([
+            (10, Entity::new(10, EntityType::Player, location("r1"))),
+            (11, key),
+        ]);
+        game_state
+            .active_players
+            .write()
+            .await
+            .insert(player.client_id.clone(), player.clone());
+        (game_state, player)
+    }
+
+    #[tokio::test]
+    async fn found_entities_hide_again_once_the_room_is_empty() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let (game_state, player) = room_with_hidden_key().await;
+        // Seed 10 rolls an 18 against DC 10.
+        *game_state.rng() = fastrand::Rng::with_seed(10);
+
+        process(&game_state, &player).await;
+        assert!(game_state.revealed.read().await.contains(&11));
+
+        // Searching again in the same visit changes nothing, and an occupied room stays found.
+        process(&game_state, &player).await;
+        world_update::process(&game_state, &db, 1).await;
+        assert!(game_state.revealed.read().await.contains(&11));
+        assert!(game_state.searched.read().await.contains_key(&10));
+
+        game_state
+            .active_entities
+            .write()
+            .await
+            .get_mut(&10)
+            .unwrap()
+            .location = location("r2");
+        world_update::process(&game_state, &db, 2).await;
+        assert!(game_state.revealed.read().await.is_empty());
+        assert!(game_state.searched.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_failed_search_uses_up_the_visit() {
+        let (game_state, player) = room_with_hidden_key().await;
+        // Seed 14 rolls a natural 1 and then a natural 20, which a second search never gets.
+        *game_state.rng() = fastrand::Rng::with_seed(14);
+
+        process(&game_state, &player).await;
+        process(&game_state, &player).await;
+        assert!(game_state.revealed.read().await.is_empty());
+    }
+}
@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use crate::game::messaging::CharacterSheet;
+use crate::game::player::Player;
+use crate::game::{GameState, messaging};
+
+/// Send the player their character sheet: every attribute grouped by category, and the effects
+/// acting on them.
+pub async fn process(game_state: &Arc<GameState>, player: &Player) {
+    let sheet = {
+        let entities = game_state.active_entities.read().await;
+        let Some(entity) = entities.get(&player.entity_id) else {
+            return;
+        };
+        CharacterSheet::new(&player.name, entity, &game_state.attribute_config)
+    };
+    messaging::character_sheet(&game_state.message_tx, player.id, sheet);
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::game::entity::Entity;
+use crate::game::{GameState, Location};
+
+/// Returns true if `query` refers to the entity, matching its display name exactly or any word
+/// of its description (case-insensitive).
+pub fn matches(entity: &Entity, player_names: &HashMap<i64, String>, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return false;
+    }
+    if entity.display_name(player_names).to_lowercase() == query {
+        return true;
+    }
+    entity.description.as_deref().is_some_and(|d| {
+        d.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word == query)
+    })
+}
+
+/// Find an entity at `location`, other than `exclude_entity_id`, that `query` refers to. Carried
+/// entities aren't part of the room and never match, and neither do hidden ones nobody has found.
+pub async fn find_in_room(
+    game_state: &Arc<GameState>,
+    location: &Location,
+    exclude_entity_id: i64,
+    query: &str,
+) -> Option<i64> {
+    let names = game_state.player_names().await;
+    let entities = game_state.active_entities.read().await;
+    let revealed = game_state.revealed.read().await;
+    let mut candidates: Vec<&Entity> = entities
+        .values()
+        .filter(|e| e.id != exclude_entity_id && &e.location == location)
+        .filter(|e| e.holder_id.is_none())
+        .filter(|e| !game_state.is_hidden(e, &revealed))
+        .filter(|e| matches(e, &names, query))
+        .collect();
+    candidates.sort_by_key(|e| e.id);
+    candidates.first().map(|e| e.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::entity::EntityType;
+
+    fn make_entity(id: i64, config_id: Option<&str>, description: Option<&str>) -> Entity {
+        let mut entity = Entity::new(
+            id,
+            EntityType::Character,
+            Location {
+                world_id: "w1".to_string(),
+                dungeon_id: "d1".to_string(),
+                room_id: "r1".to_string(),
+            },
+        );
+        entity.config_id = config_id.map(str::to_string);
+        entity.description = description.map(str::to_string);
+        entity
+    }
+
+    #[test]
+    fn matches_name_or_description_word() {
+        let entity = make_entity(1, Some("entities/innkeeper"), Some("A burly goblin guard."));
+        let names = HashMap::new();
+        assert!(matches(&entity, &names, "Innkeeper"));
+        assert!(matches(&entity, &names, "goblin"));
+        assert!(!matches(&entity, &names, "gob"));
+        assert!(!matches(&entity, &names, ""));
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use crate::game::messaging::{Speech, SpeechKind};
+use crate::game::player::Player;
+use crate::game::{GameState, messaging};
+use crate::persistence::{Database, player_repo};
+
+/// Send a private message to an online player anywhere in the game. Names are matched against
+/// online players first, since player names aren't unique.
+pub async fn tell(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    target: &str,
+    content: &str,
+) {
+    let online = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .filter(|p| p.name.eq_ignore_ascii_case(target))
+        .min_by_key(|p| (p.id == player.id, p.id))
+        .cloned();
+    if let Some(recipient) = online {
+        send(game_state, player, &recipient, content).await;
+        return;
+    }
+
+    match player_repo::find_by_name(db.pool(), target).await {
+        Ok(Some(recipient)) => not_around(game_state, player, &recipient.name),
+        Ok(None) => messaging::message(
+            &game_state.message_tx,
+            player.id,
+            format!("There's nobody called {target}."),
+        ),
+        Err(e) => tracing::error!(error = %e, "Failed to look up tell recipient"),
+    }
+}
+
+/// Tell the last player who sent this player a tell.
+pub async fn reply(game_state: &Arc<GameState>, db: &Database, player: &Player, content: &str) {
+    let target = game_state
+        .reply_targets
+        .read()
+        .await
+        .get(&player.id)
+        .copied();
+    let Some(target) = target else {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            "Nobody has sent you a tell to reply to.",
+        );
+        return;
+    };
+
+    let online = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .find(|p| p.id == target)
+        .cloned();
+    if let Some(recipient) = online {
+        send(game_state, player, &recipient, content).await;
+        return;
+    }
+
+    match player_repo::find_by_id(db.pool(), target).await {
+        Ok(Some(recipient)) => not_around(game_state, player, &recipient.name),
+        Ok(None) => messaging::message(
+            &game_state.message_tx,
+            player.id,
+            "The player you'd reply to is gone.",
+        ),
+        Err(e) => tracing::error!(error = %e, "Failed to look up reply recipient"),
+    }
+}
+
+async fn send(game_state: &Arc<GameState>, player: &Player, recipient: &Player, content: &str) {
+    if recipient.id == player.id {
+        messaging::message(&game_state.message_tx, player.id, "You mutter to yourself.");
+        return;
+    }
+
+    game_state
+        .reply_targets
+        .write()
+        .await
+        .insert(recipient.id, player.id);
+    messaging::speech(
+        &game_state.message_tx,
+        player.id,
+        Speech::new(
+            SpeechKind::Tell,
+            &player.name,
+            format!("You tell {}, \"{content}\"", recipient.name),
+        ),
+    );
+    messaging::speech(
+        &game_state.message_tx,
+        recipient.id,
+        Speech::new(
+            SpeechKind::Tell,
+            &player.name,
+            format!("{} tells you, \"{content}\"", player.name),
+        ),
+    );
+}
+
+fn not_around(game_state: &Arc<GameState>, player: &Player, name: &str) {
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        format!("{name} isn't around. Try: mail {name} <message>"),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::fixtures::{insert_player, insert_room};
+
+    #[tokio::test]
+    async fn tell_and_reply_reach_the_online_player_with_a_shared_name() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let game_state = Arc::new(GameState::load(None).unwrap());
+        let location = insert_room(&db).await;
+        insert_player(&db, &location, "Bob").await;
+        let online_bob = &insert_player(&db, &location, "Bob").await;
+        let alice = &insert_player(&db, &location, "Alice").await;
+        for player in [online_bob, alice] {
+            game_state
+                .active_players
+                .write()
+                .await
+                .insert(player.client_id.clone(), player.clone());
+        }
+
+        tell(&game_state, &db, alice, "bob", "hello").await;
+        assert_eq!(
+            game_state.reply_targets.read().await.get(&online_bob.id),
+            Some(&alice.id)
+        );
+
+        reply(&game_state, &db, online_bob, "hi").await;
+        assert_eq!(
+            game_state.reply_targets.read().await.get(&alice.id),
+            Some(&online_bob.id)
+        );
+    }
+}
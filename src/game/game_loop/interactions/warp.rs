@@ -0,0 +1,211 @@
+use std::sync::Arc;
+
+use crate::game::map::Navigation;
+use crate::game::player::Player;
+use crate::game::{GameState, Location, messaging};
+use crate::persistence::{Database, dungeon_repo, entity_repo, room_repo, world_repo};
+
+use super::{inventory, look};
+
+/// Warp a player to the destination described by `navigation`, then look around.
+pub async fn process(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    player: &Player,
+    navigation: &Navigation,
+) {
+    if warp_entity(game_state, db, player.entity_id, navigation)
+        .await
+        .is_none()
+    {
+        messaging::message(
+            &game_state.message_tx,
+            player.id,
+            "You feel a strange pull, but nothing happens.",
+        );
+        return;
+    }
+    messaging::message(
+        &game_state.message_tx,
+        player.id,
+        "The world twists around you.",
+    );
+    look::process(game_state, db, player).await;
+}
+
+/// Move an entity to a warp destination, telling players in both rooms it vanished and appeared.
+/// Returns the new location, or `None` if the entity isn't active or the destination doesn't
+/// exist.
+pub async fn warp_entity(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    entity_id: i64,
+    navigation: &Navigation,
+) -> Option<Location> {
+    let names = game_state.player_names().await;
+    let (from, name) = {
+        let entities = game_state.active_entities.read().await;
+        let entity = entities.get(&entity_id)?;
+        (entity.location.clone(), entity.display_name(&names))
+    };
+    let destination = resolve(db, &from, navigation).await?;
+    messaging::message_location(game_state, &from, entity_id, format!("{name} vanishes.")).await;
+    move_entity(game_state, db, entity_id, &destination).await;
+    messaging::message_location(
+        game_state,
+        &destination,
+        entity_id,
+        format!("{name} appears."),
+    )
+    .await;
+    Some(destination)
+}
+
+/// Resolve a warp destination relative to `from`. Missing world and dungeon ids default to the
+/// current ones; the room id is required. Returns `None` unless the world, dungeon and room all
+/// exist and belong together.
+pub async fn resolve(db: &Database, from: &Location, navigation: &Navigation) -> Option<Location> {
+    let pool = db.pool();
+    let location = Location {
+        world_id: navigation
+            .world_id
+            .clone()
+            .unwrap_or_else(|| from.world_id.clone()),
+        dungeon_id: navigation
+            .dungeon_id
+            .clone()
+            .unwrap_or_else(|| from.dungeon_id.clone()),
+        room_id: navigation.room_id.clone()?,
+    };
+
+    world_repo::find_by_id(pool, &location.world_id)
+        .await
+        .ok()
+        .flatten()?;
+    let dungeons = dungeon_repo::find_by_world(pool, &location.world_id)
+        .await
+        .ok()?;
+    if !dungeons.iter().any(|d| d.id == location.dungeon_id) {
+        return None;
+    }
+    room_repo::find_by_id(pool, &location.dungeon_id, &location.room_id)
+        .await
+        .ok()
+        .flatten()?;
+    Some(location)
+}
+
+/// Move an entity and everything it carries to `location` in memory and in the database,
+/// syncing active entities when it changes dungeon.
+pub async fn move_entity(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    entity_id: i64,
+    location: &Location,
+) {
+    let old_location = {
+        let mut entities = game_state.active_entities.write().await;
+        match entities.get_mut(&entity_id) {
+            Some(entity) => std::mem::replace(&mut entity.location, location.clone()),
+            None => return,
+        }
+    };
+    if let Err(e) = entity_repo::update_location(db.pool(), entity_id, location).await {
+        tracing::error!(error = %e, "Failed to update entity location in DB");
+    }
+    inventory::move_carried(game_state, db, entity_id, location).await;
+    if (location.world_id != old_location.world_id
+        || location.dungeon_id != old_location.dungeon_id)
+        && let Err(e) = game_state.sync_active_entities(db.pool()).await
+    {
+        tracing::error!(error = %e, "Failed to sync active entities after dungeon change");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Description, Dungeon, Room, World};
+
+    async fn setup() -> Database {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+        for world_id in ["w1", "w2"] {
+            world_repo::insert(pool, &World::new(world_id.to_string()))
+                .await
+                .unwrap();
+        }
+        dungeon_repo::insert(pool, &Dungeon::new("d1".to_string()), "w1")
+            .await
+            .unwrap();
+        dungeon_repo::insert(pool, &Dungeon::new("d2".to_string()), "w2")
+            .await
+            .unwrap();
+        for (dungeon_id, room_id) in [("d1", "r1"), ("d1", "r2"), ("d2", "vault")] {
+            room_repo::insert(
+                pool,
+                &Room::new(room_id.to_string(), Description::default()),
+                dungeon_id,
+            )
+            .await
+            .unwrap();
+        }
+        db
+    }
+
+    fn from() -> Location {
+        Location {
+            world_id: "w1".to_string(),
+            dungeon_id: "d1".to_string(),
+            room_id: "r1".to_string(),
+        }
+    }
+
+    fn navigation(world_id: Option<&str>, dungeon_id: Option<&str>, room_id: &str) -> Navigation {
+        Navigation {
+            world_id: world_id.map(str::to_string),
+            dungeon_id: dungeon_id.map(str::to_string),
+            room_id: Some(room_id.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_defaults_to_current_world_and_dungeon() {
+        let db = setup().await;
+        let location = resolve(&db, &from(), &navigation(None, None, "r2"))
+            .await
+            .unwrap();
+        assert_eq!(location.dungeon_id, "d1");
+        assert_eq!(location.room_id, "r2");
+    }
+
+    #[tokio::test]
+    async fn resolve_across_worlds() {
+        let db = setup().await;
+        let location = resolve(&db, &from(), &navigation(Some("w2"), Some("d2"), "vault"))
+            .await
+            .unwrap();
+        assert_eq!(location.world_id, "w2");
+    }
+
+    #[tokio::test]
+    async fn resolve_rejects_invalid_destinations() {
+        let db = setup().await;
+        assert!(
+            resolve(&db, &from(), &navigation(None, None, "missing"))
+                .await
+                .is_none()
+        );
+        assert!(
+            resolve(&db, &from(), &navigation(Some("w2"), None, "r1"))
+                .await
+                .is_none()
+        );
+        assert!(
+            resolve(&db, &from(), &navigation(Some("nope"), None, "r1"))
+                .await
+                .is_none()
+        );
+        assert!(resolve(&db, &from(), &Navigation::new()).await.is_none());
+    }
+}
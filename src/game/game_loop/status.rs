@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::game::messaging::{self, PlayerStatus};
+use crate::game::{GameState, Player};
+
+/// Push a fresh [`PlayerStatus`] to every active player whose status attributes or location
+/// changed since the last one they were sent. Players who just joined always get one.
+pub async fn process(game_state: &Arc<GameState>, tick: u64) {
+    tracing::debug!("Processing player status tick={tick}");
+
+    let players: Vec<Player> = game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .cloned()
+        .collect();
+    let statuses: Vec<(i64, PlayerStatus)> = {
+        let entities = game_state.active_entities.read().await;
+        players
+            .iter()
+            .filter_map(|player| {
+                let entity = entities.get(&player.entity_id)?;
+                let status = PlayerStatus::new(
+                    entity,
+                    &game_state.attribute_config,
+                    &game_state.mud_config.status.attributes,
+                );
+                Some((player.id, status))
+            })
+            .collect()
+    };
+
+    let mut sent = game_state.player_statuses.write().await;
+    for (player_id, status) in statuses {
+        if sent.get(&player_id) == Some(&status) {
+            continue;
+        }
+        messaging::status(&game_state.message_tx, player_id, status.clone());
+        sent.insert(player_id, status);
+    }
+}
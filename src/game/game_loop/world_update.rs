@@ -1,7 +1,431 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::game::GameState;
+use crate::game::config::EntityConfig;
+use crate::game::entity::{Entity, EntityType};
+use crate::game::game_loop::effects;
+use crate::game::game_loop::interactions::inventory;
+use crate::game::game_state::now_ms;
+use crate::game::{GameState, Location};
+use crate::persistence::{Database, entity_effect_repo, entity_repo};
 
-pub async fn process(_game_state: &Arc<GameState>, tick: u64) {
+/// Send disturbed config entities home.
+///
+/// An entity placed by the map is disturbed once it has left its original room, been picked up
+/// by anything other than a player, had its loot rolled or its charges spent, or, when its
+/// [`crate::game::config::RespawnConfig`] resets attributes, had its attributes change (which
+/// includes being killed). After it has been disturbed for the configured delay it is moved back
+/// and reset, and loot left inside it is removed so it can be rolled again. Entities carried by
+/// players are left alone, and entities that skip occupied rooms wait until no players are in
+/// the room they're in or the room they return to. Only active entities and stored ones whose
+/// saved state shows they were disturbed are checked, so an entity in an unloaded dungeon whose
+/// only change is its attributes waits until the dungeon loads again.
+///
+/// Spawned entities whose lifetime ran out while their dungeon wasn't loaded are removed too.
+/// Hidden entities players found go back into hiding unless someone is still in the room with
+/// them, and players who have left the room they searched can search it again.
+pub async fn process(game_state: &Arc<GameState>, db: &Database, tick: u64) {
     tracing::info!("Processing world update tick={tick}");
+
+    match entity_repo::find_expired(db.pool(), now_ms()).await {
+        Ok(expired) => {
+            for id in expired {
+                effects::despawn(game_state, db, id).await;
+            }
+        }
+        Err(e) => tracing::error!(error = %e, "Failed to load expired entities"),
+    }
+
+    let homes: HashMap<i64, Location> = match entity_repo::find_original_locations(db.pool()).await
+    {
+        Ok(homes) => homes.into_iter().collect(),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load original entity locations");
+            return;
+        }
+    };
+    let occupied = occupied_rooms(game_state).await;
+    hide_unwatched(game_state, &occupied).await;
+    forget_searches(game_state).await;
+    let tick_rate_ms = game_state.mud_config.game_loop.tick_rate_ms;
+    let mut disturbed = HashSet::new();
+
+    for entity in candidates(game_state, db, &homes).await {
+        let id = entity.id;
+        let Some(home) = homes.get(&id) else {
+            continue;
+        };
+        let Some(config) = entity
+            .config_id
+            .as_deref()
+            .and_then(|config_id| game_state.entity_configs.get(config_id))
+        else {
+            continue;
+        };
+        let respawn = &config.respawn;
+        if !respawn.enabled || !is_disturbed(&entity, home, config) {
+            continue;
+        }
+        if let Some(holder_id) = entity.holder_id
+            && load_entity(game_state, db, holder_id)
+                .await
+                .is_some_and(|holder| holder.entity_type == EntityType::Player)
+        {
+            continue;
+        }
+
+        disturbed.insert(id);
+        let since = *game_state
+            .respawn_timers
+            .write()
+            .await
+            .entry(id)
+            .or_insert(tick);
+        if tick.saturating_sub(since).saturating_mul(tick_rate_ms) < respawn.delay_ms {
+            continue;
+        }
+        if respawn.skip_occupied && (occupied.contains(&entity.location) || occupied.contains(home))
+        {
+            continue;
+        }
+
+        respawn_entity(game_state, db, entity, home, config, &homes).await;
+        disturbed.remove(&id);
+    }
+
+    game_state
+        .respawn_timers
+        .write()
+        .await
+        .retain(|id, _| disturbed.contains(id));
+}
+
+/// Map-placed entities that may need to go home: every active one, plus stored ones whose saved
+/// state already shows they were disturbed.
+async fn candidates(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    homes: &HashMap<i64, Location>,
+) -> Vec<Entity> {
+    let mut candidates: Vec<Entity> = game_state
+        .active_entities
+        .read()
+        .await
+        .values()
+        .filter(|e| homes.contains_key(&e.id))
+        .cloned()
+        .collect();
+    match entity_repo::find_disturbed_placed(db.pool()).await {
+        Ok(stored) => {
+            let active: HashSet<i64> = candidates.iter().map(|e| e.id).collect();
+            candidates.extend(stored.into_iter().filter(|e| !active.contains(&e.id)));
+        }
+        Err(e) => tracing::error!(error = %e, "Failed to load disturbed entities"),
+    }
+    candidates
+}
+
+/// Hide found entities again once nobody is in the room with them.
+async fn hide_unwatched(game_state: &Arc<GameState>, occupied: &[Location]) {
+    let entities = game_state.active_entities.read().await;
+    game_state.revealed.write().await.retain(|id| {
+        entities
+            .get(id)
+            .is_some_and(|e| occupied.contains(&e.location))
+    });
+}
+
+/// Forget searches by players who are no longer in the room they searched.
+async fn forget_searches(game_state: &Arc<GameState>) {
+    let entities = game_state.active_entities.read().await;
+    game_state
+        .searched
+        .write()
+        .await
+        .retain(|entity_id, location| {
+            entities
+                .get(entity_id)
+                .is_some_and(|e| &e.location == location)
+        });
+}
+
+/// Returns true if the entity is no longer as the map placed it.
+fn is_disturbed(entity: &Entity, home: &Location, config: &EntityConfig) -> bool {
+    let attributes_changed = config.respawn.reset_attributes
+        && config.starting_attributes().iter().any(|(id, start)| {
+            entity
+                .attributes
+                .get(id)
+                .is_none_or(|a| a.current_value != start.current_value)
+        });
+    entity.location != *home
+        || entity.holder_id.is_some()
+        || entity.equipped
+        || entity.loot_rolled
+        || entity.charges.is_some()
+        || attributes_changed
+}
+
+/// Put the entity back in its original room as the map placed it.
+fn reset(entity: &mut Entity, home: &Location, config: &EntityConfig) {
+    entity.location = home.clone();
+    entity.holder_id = None;
+    entity.equipped = false;
+    entity.loot_rolled = false;
+    entity.charges = None;
+    entity.interactions = config.interactions();
+    if config.respawn.reset_attributes {
+        entity.attributes = config.starting_attributes();
+    }
+}
+
+/// Reset the entity and send it home, taking whatever it carries along.
+async fn respawn_entity(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    mut entity: Entity,
+    home: &Location,
+    config: &EntityConfig,
+    homes: &HashMap<i64, Location>,
+) {
+    if entity.loot_rolled {
+        remove_loot(game_state, db, entity.id, homes).await;
+    }
+    reset(&mut entity, home, config);
+    if let Err(e) = entity_repo::update(db.pool(), &entity).await {
+        tracing::error!(error = %e, entity_id = entity.id, "Failed to respawn entity");
+        return;
+    }
+    tracing::debug!(entity_id = entity.id, "Respawned entity");
+
+    // Carried items that aren't loaded only exist in the database, so move those there too.
+    inventory::move_carried(game_state, db, entity.id, home).await;
+    if let Err(e) = entity_repo::update_carried_location(db.pool(), entity.id, home).await {
+        tracing::error!(error = %e, entity_id = entity.id, "Failed to move carried entities");
+    }
+
+    let home_active = game_state
+        .active_dungeons
+        .read()
+        .await
+        .contains(&(home.world_id.clone(), home.dungeon_id.clone()));
+    let mut entities = game_state.active_entities.write().await;
+    if !home_active {
+        for id in inventory::carried_by(&entities, entity.id) {
+            entities.remove(&id);
+        }
+        entities.remove(&entity.id);
+        return;
+    }
+    if let Some(active) = entities.get_mut(&entity.id) {
+        entity.effects = std::mem::take(&mut active.effects);
+        entity.ai = active.ai.take();
+    } else {
+        drop(entities);
+        entity.effects = entity_effect_repo::find_active_by_entity(db.pool(), entity.id)
+            .await
+            .unwrap_or_default();
+        entities = game_state.active_entities.write().await;
+    }
+    entities.insert(entity.id, entity);
+}
+
+/// Delete rolled loot still inside `container_id`. Entities the map placed are left to go home
+/// on their own.
+async fn remove_loot(
+    game_state: &Arc<GameState>,
+    db: &Database,
+    container_id: i64,
+    homes: &HashMap<i64, Location>,
+) {
+    let held = match entity_repo::find_held_by(db.pool(), container_id).await {
+        Ok(held) => held,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load container contents");
+            return;
+        }
+    };
+    for item in held.iter().filter(|e| !homes.contains_key(&e.id)) {
+        game_state.active_entities.write().await.remove(&item.id);
+        if let Err(e) = entity_effect_repo::delete_by_entity(db.pool(), item.id).await {
+            tracing::error!(error = %e, "Failed to delete leftover loot effects");
+        }
+        if let Err(e) = entity_repo::delete(db.pool(), item.id).await {
+            tracing::error!(error = %e, "Failed to remove leftover loot");
+        }
+    }
+}
+
+/// The active copy of an entity, or the stored one when its dungeon isn't loaded.
+async fn load_entity(game_state: &Arc<GameState>, db: &Database, id: i64) -> Option<Entity> {
+    if let Some(entity) = game_state.active_entities.read().await.get(&id) {
+        return Some(entity.clone());
+    }
+    entity_repo::find_by_id(db.pool(), id).await.ok().flatten()
+}
+
+async fn occupied_rooms(game_state: &Arc<GameState>) -> Vec<Location> {
+    let players = game_state.active_players.read().await;
+    let entities = game_state.active_entities.read().await;
+    players
+        .values()
+        .filter_map(|p| entities.get(&p.entity_id))
+        .map(|e| e.location.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::Attribute;
+    use crate::game::{Description, Room};
+    use crate::persistence::fixtures::insert_room;
+    use crate::persistence::room_repo;
+
+    fn location(room_id: &str) -> Location {
+        Location {
+            world_id: "w1".to_string(),
+            dungeon_id: "d1".to_string(),
+            room_id: room_id.to_string(),
+        }
+    }
+
+    fn goblin_config() -> EntityConfig {
+        toml::from_str(
+            r#"
+entity_type = "character"
+
+[[attributes]]
+definition_id = "hp"
+min_value = 0
+max_value = 20
+current_value = 20
+"#,
+        )
+        .unwrap()
+    }
+
+    fn goblin(room_id: &str, hp: i64) -> Entity {
+        let mut entity = Entity::new(1, EntityType::Character, location(room_id));
+        entity.attributes.insert(
+            "hp".to_string(),
+            Attribute::new("hp".to_string(), 0, 20, hp),
+        );
+        entity
+    }
+
+    /// A goblin placed in `r1` that wandered to `r2` carrying a coin, with no players about.
+    async fn wandering_goblin(db: &Database) -> (GameState, Entity, Entity) {
+        let home = insert_room(db).await;
+        room_repo::insert(
+            db.pool(),
+            &Room::new("r2".to_string(), Description::new(None)),
+            "d1",
+        )
+        .await
+        .unwrap();
+        let mut game_state = GameState::load(None).unwrap();
+        game_state
+            .entity_configs
+            .insert("entities/goblin".to_string(), goblin_config());
+
+        let (goblin_id, _) = entity_repo::insert_config_entity_if_missing(
+            db.pool(),
+            &EntityType::Character,
+            &home,
+            "entities/goblin",
+            None,
+        )
+        .await
+        .unwrap();
+        let mut goblin = goblin("r2", 20);
+        goblin.id = goblin_id;
+        goblin.config_id = Some("entities/goblin".to_string());
+        entity_repo::update(db.pool(), &goblin).await.unwrap();
+
+        let mut coin = Entity::new(0, EntityType::Object, location("r2"));
+        coin.holder_id = Some(goblin_id);
+        coin.id = entity_repo::insert(db.pool(), &coin).await.unwrap();
+        (game_state, goblin, coin)
+    }
+
+    #[tokio::test]
+    async fn respawned_character_brings_what_it_carries_home() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let (game_state, goblin, coin) = wandering_goblin(&db).await;
+        let game_state = Arc::new(game_state);
+        game_state
+            .active_dungeons
+            .write()
+            .await
+            .insert(("w1".to_string(), "d1".to_string()));
+        game_state
+            .active_entities
+            .write()
+            .await
+            .extend([(goblin.id, goblin.clone()), (coin.id, coin.clone())]);
+
+        process(&game_state, &db, 1).await;
+
+        let entities = game_state.active_entities.read().await;
+        assert_eq!(entities[&goblin.id].location, location("r1"));
+        assert_eq!(entities[&coin.id].location, location("r1"));
+        let stored = entity_repo::find_by_id(db.pool(), coin.id).await.unwrap();
+        assert_eq!(stored.unwrap().location, location("r1"));
+    }
+
+    #[tokio::test]
+    async fn stored_disturbed_entities_go_home_while_unloaded() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let (game_state, goblin, coin) = wandering_goblin(&db).await;
+        let game_state = Arc::new(game_state);
+
+        process(&game_state, &db, 1).await;
+
+        for id in [goblin.id, coin.id] {
+            let stored = entity_repo::find_by_id(db.pool(), id).await.unwrap();
+            assert_eq!(stored.unwrap().location, location("r1"));
+        }
+        assert!(game_state.active_entities.read().await.is_empty());
+    }
+
+    #[test]
+    fn untouched_entity_is_not_disturbed() {
+        assert!(!is_disturbed(
+            &goblin("r1", 20),
+            &location("r1"),
+            &goblin_config()
+        ));
+    }
+
+    #[test]
+    fn wandering_killed_or_looted_entities_are_disturbed() {
+        let config = goblin_config();
+        let home = location("r1");
+        assert!(is_disturbed(&goblin("r2", 20), &home, &config));
+        assert!(is_disturbed(&goblin("r1", 0), &home, &config));
+        let mut looted = goblin("r1", 20);
+        looted.loot_rolled = true;
+        assert!(is_disturbed(&looted, &home, &config));
+    }
+
+    #[test]
+    fn attribute_changes_are_ignored_without_reset_attributes() {
+        let mut config = goblin_config();
+        config.respawn.reset_attributes = false;
+        assert!(!is_disturbed(&goblin("r1", 5), &location("r1"), &config));
+    }
+
+    #[test]
+    fn reset_restores_location_and_starting_attributes() {
+        let config = goblin_config();
+        let mut entity = goblin("r2", 0);
+        entity.loot_rolled = true;
+        entity.holder_id = Some(7);
+        reset(&mut entity, &location("r1"), &config);
+        assert_eq!(entity.location, location("r1"));
+        assert_eq!(entity.attributes["hp"].current_value, 20);
+        assert!(entity.holder_id.is_none());
+        assert!(!entity.loot_rolled);
+    }
 }
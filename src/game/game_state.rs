@@ -1,30 +1,61 @@
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 use sqlx::SqlitePool;
 use tokio::sync::RwLock;
 use tokio::sync::broadcast;
 
-use crate::game::config::{AttributeConfig, EntityConfig, MudConfig, load_entity_configs};
-use crate::game::engagement::Engagements;
-use crate::game::entity::Entity;
+use crate::game::agent::{self, AgentProvider};
+use crate::game::component::{ActiveEffect, CheckRoll, Location, SkillCheck};
+use crate::game::config::{
+    AttributeConfig, EntityConfig, EntityTypeConfig, LootTable, MudConfig, load_entity_configs,
+    load_loot_tables,
+};
+use crate::game::engagement::{EngagementType, Engagements};
+use crate::game::entity::{Entity, EntityType};
 use crate::game::mailbox::Mailboxes;
-use crate::game::messaging::PlayerMessage;
+use crate::game::messaging;
+use crate::game::messaging::{PlayerMessage, PlayerStatus};
 use crate::game::player::Player;
-use crate::persistence::PersistenceError;
+use crate::persistence::{PersistenceError, entity_effect_repo, entity_repo, interaction_repo};
 
 mod entity_sync;
 
+/// Milliseconds since the Unix epoch.
+pub fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
 pub struct GameState {
     pub attribute_config: AttributeConfig,
     pub mud_config: MudConfig,
     pub entity_configs: HashMap<String, EntityConfig>,
+    pub loot_tables: HashMap<String, LootTable>,
     pub active_entities: RwLock<HashMap<i64, Entity>>,
     pub active_dungeons: RwLock<HashSet<(String, String)>>,
     pub engagements: Engagements,
     pub mailboxes: Mailboxes,
     pub active_players: RwLock<HashMap<String, Player>>,
+    /// Tick at which each disturbed config entity was first noticed by a world update, used for
+    /// respawn delays.
+    pub respawn_timers: RwLock<HashMap<i64, u64>>,
+    /// Id of the last player to send each player a tell, keyed by player id, for `reply`.
+    pub reply_targets: RwLock<HashMap<i64, i64>>,
+    /// Last status sent to each player, keyed by player id, so only changes are pushed.
+    pub player_statuses: RwLock<HashMap<i64, PlayerStatus>>,
+    /// Entities with a hidden config that players have found with `search`.
+    pub revealed: RwLock<HashSet<i64>>,
+    /// Room each player entity last searched. Searching it again needs a visit elsewhere first.
+    pub searched: RwLock<HashMap<i64, Location>>,
+    /// Dice for skill checks, seeded from `rng_seed` in `mud.toml` when it's set.
+    rng: Mutex<fastrand::Rng>,
     pub message_tx: broadcast::Sender<PlayerMessage>,
+    /// Provider for agent personas, present when `mud.toml` has an `[agent]` section.
+    pub agent_provider: Option<Arc<dyn AgentProvider>>,
 }
 
 impl GameState {
@@ -57,29 +88,179 @@ impl GameState {
             HashMap::new()
         };
 
+        let loot_tables = if let Some(dir) = config_dir {
+            load_loot_tables(dir).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+
+        let agent_provider = match &mud_config.agent {
+            Some(config) => Some(agent::build_provider(config)?),
+            None => None,
+        };
+
         let (message_tx, _) = broadcast::channel::<PlayerMessage>(512);
+        let rng = match mud_config.rng_seed {
+            Some(seed) => fastrand::Rng::with_seed(seed),
+            None => fastrand::Rng::new(),
+        };
 
         Ok(Self {
             attribute_config,
             mud_config,
             entity_configs,
+            loot_tables,
             active_entities: RwLock::new(HashMap::new()),
             active_dungeons: RwLock::new(HashSet::new()),
             engagements: Engagements::new(),
             mailboxes: Mailboxes::new(),
             active_players: RwLock::new(HashMap::new()),
+            respawn_timers: RwLock::new(HashMap::new()),
+            reply_targets: RwLock::new(HashMap::new()),
+            player_statuses: RwLock::new(HashMap::new()),
+            revealed: RwLock::new(HashSet::new()),
+            searched: RwLock::new(HashMap::new()),
+            rng: Mutex::new(rng),
             message_tx,
+            agent_provider,
         })
     }
 
+    /// The dice used for skill checks.
+    pub fn rng(&self) -> MutexGuard<'_, fastrand::Rng> {
+        self.rng.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Roll `check` for an active entity with the game's dice. `None` if the entity isn't active.
+    pub async fn roll_check(&self, entity_id: i64, check: &SkillCheck) -> Option<CheckRoll> {
+        let entities = self.active_entities.read().await;
+        let entity = entities.get(&entity_id)?;
+        Some(check.roll(&entity.attributes, &mut self.rng()))
+    }
+
+    /// Map of entity id to player name for every active player.
+    pub async fn player_names(&self) -> HashMap<i64, String> {
+        self.active_players
+            .read()
+            .await
+            .values()
+            .map(|p| (p.entity_id, p.name.clone()))
+            .collect()
+    }
+
+    /// Returns true if `entity` is hidden and hasn't been found yet.
+    pub fn is_hidden(&self, entity: &Entity, revealed: &HashSet<i64>) -> bool {
+        !revealed.contains(&entity.id)
+            && entity
+                .config_id
+                .as_deref()
+                .and_then(|id| self.entity_configs.get(id))
+                .is_some_and(|config| config.hidden.is_some())
+    }
+
     pub async fn sync_active_entities(&self, pool: &SqlitePool) -> Result<(), PersistenceError> {
         entity_sync::sync(self, pool).await
     }
+
+    /// Create a new entity from a config at `location`, optionally carried by `holder_id` and
+    /// removed again after `lifetime_ms`. It becomes active if its dungeon is. Returns the new
+    /// entity's id, or `None` if the config doesn't exist.
+    pub async fn spawn_entity(
+        &self,
+        pool: &SqlitePool,
+        config_id: &str,
+        location: &Location,
+        holder_id: Option<i64>,
+        lifetime_ms: Option<u64>,
+    ) -> Result<Option<i64>, PersistenceError> {
+        let Some(config) = self.entity_configs.get(config_id) else {
+            tracing::warn!(config_id, "Cannot spawn unknown entity config");
+            return Ok(None);
+        };
+        let entity_type = match config.entity_type {
+            EntityTypeConfig::Character => EntityType::Character,
+            EntityTypeConfig::Object => EntityType::Object,
+        };
+        let mut entity = Entity::new(0, entity_type, location.clone());
+        entity.config_id = Some(config_id.to_string());
+        entity.description = config.description.clone();
+        entity.holder_id = holder_id;
+        entity.attributes = config.starting_attributes();
+        entity.despawn_at = lifetime_ms.map(|ms| now_ms().saturating_add_unsigned(ms));
+        entity.interactions = config.interactions();
+        entity.id = entity_repo::insert(pool, &entity).await?;
+        for interaction in &entity.interactions {
+            interaction_repo::insert(pool, entity.id, interaction).await?;
+        }
+        for effect in &config.entity_effects {
+            let id = entity_effect_repo::insert(pool, entity.id, effect).await?;
+            entity.effects.push(ActiveEffect {
+                id,
+                effect: effect.clone(),
+                elapsed_ticks: 0,
+            });
+        }
+        let id = entity.id;
+        let dungeon = (location.world_id.clone(), location.dungeon_id.clone());
+        if self.active_dungeons.read().await.contains(&dungeon) {
+            self.active_entities.write().await.insert(id, entity);
+        }
+        Ok(Some(id))
+    }
+
+    /// Make `player` the client's active player and tell everyone in the room they arrived.
+    /// A player the client was already playing leaves the game first.
+    pub async fn add_player(&self, client_id: &str, player: Player, entity: Entity) {
+        self.remove_player(client_id).await;
+        let location = entity.location.clone();
+        let (entity_id, name) = (player.entity_id, player.name.clone());
+        self.active_entities.write().await.insert(entity.id, entity);
+        self.active_players
+            .write()
+            .await
+            .insert(client_id.to_string(), player);
+        messaging::message_location(self, &location, entity_id, format!("{name} arrives.")).await;
+    }
+
+    /// Take the client's active player out of the game: drop it from any engagement, remove its
+    /// entity and tell everyone in the room it left. Returns the removed player.
+    pub async fn remove_player(&self, client_id: &str) -> Option<Player> {
+        let player = self.active_players.write().await.remove(client_id)?;
+        self.player_statuses.write().await.remove(&player.id);
+        if let Some(engagement_id) = self
+            .engagements
+            .find_for_entity(player.entity_id, EngagementType::Battle)
+            .await
+        {
+            self.engagements
+                .remove_entity(engagement_id, player.entity_id)
+                .await;
+        }
+        if let Some(engagement_id) = self
+            .engagements
+            .find_for_entity(player.entity_id, EngagementType::Conversation)
+            .await
+        {
+            self.engagements.remove(engagement_id).await;
+        }
+        let entity = self.active_entities.write().await.remove(&player.entity_id);
+        if let Some(entity) = entity {
+            messaging::message_location(
+                self,
+                &entity.location,
+                player.entity_id,
+                format!("{} leaves.", player.name),
+            )
+            .await;
+        }
+        Some(player)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::{EntityType, Location};
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -89,6 +270,49 @@ mod tests {
         assert_eq!(state.attribute_config.attributes.len(), 10);
     }
 
+    #[tokio::test]
+    async fn seeded_games_roll_the_same_checks() {
+        let check = SkillCheck::new("strength", 12, crate::game::component::RollMode::Normal);
+        let mut rolls = Vec::new();
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("mud.toml"),
+            r#"
+rng_seed = 99
+
+[game_loop]
+tick_rate_ms = 500
+max_engage_ms = 15000
+world_update_ms = 300000
+
+[spawn]
+world_id = "w"
+dungeon_id = "d"
+room_id = "r"
+"#,
+        )
+        .unwrap();
+        for _ in 0..2 {
+            let state = GameState::load(Some(dir.path())).unwrap();
+            let location = Location {
+                world_id: "w".to_string(),
+                dungeon_id: "d".to_string(),
+                room_id: "r".to_string(),
+            };
+            state
+                .active_entities
+                .write()
+                .await
+                .insert(1, Entity::new(1, EntityType::Player, location));
+            let mut naturals = Vec::new();
+            for _ in 0..5 {
+                naturals.push(state.roll_check(1, &check).await.unwrap().natural);
+            }
+            rolls.push(naturals);
+        }
+        assert_eq!(rolls[0], rolls[1]);
+    }
+
     #[test]
     fn load_with_dir_missing_file_uses_defaults() {
         let dir = TempDir::new().unwrap();
@@ -161,6 +385,67 @@ room_id = "default"
         assert!(entities.is_empty());
     }
 
+    fn player(id: i64, name: &str) -> Player {
+        Player {
+            id,
+            client_id: format!("client{id}"),
+            name: name.to_string(),
+            entity_id: id * 10,
+        }
+    }
+
+    fn square() -> Location {
+        Location {
+            world_id: "w1".to_string(),
+            dungeon_id: "d1".to_string(),
+            room_id: "square".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn players_joining_and_leaving_are_announced() {
+        let state = GameState::load(None).unwrap();
+        let mut rx = state.message_tx.subscribe();
+        let alice = player(1, "Alice");
+        let bob = player(2, "Bob");
+        state
+            .add_player(
+                "client1",
+                alice.clone(),
+                Entity::new(alice.entity_id, EntityType::Player, square()),
+            )
+            .await;
+        state
+            .add_player(
+                "client2",
+                bob.clone(),
+                Entity::new(bob.entity_id, EntityType::Player, square()),
+            )
+            .await;
+
+        let arrival = rx.recv().await.unwrap();
+        assert_eq!(arrival.player_id, alice.id);
+        assert!(
+            matches!(arrival.message, messaging::Message::Complete(ref c) if c == "Bob arrives.")
+        );
+
+        let removed = state.remove_player("client2").await.unwrap();
+        assert_eq!(removed.name, "Bob");
+        assert!(
+            !state
+                .active_entities
+                .read()
+                .await
+                .contains_key(&bob.entity_id)
+        );
+        let departure = rx.recv().await.unwrap();
+        assert_eq!(departure.player_id, alice.id);
+        assert!(
+            matches!(departure.message, messaging::Message::Complete(ref c) if c == "Bob leaves.")
+        );
+        assert!(state.remove_player("client2").await.is_none());
+    }
+
     #[tokio::test]
     async fn load_initializes_empty_dungeons() {
         let state = GameState::load(None).unwrap();
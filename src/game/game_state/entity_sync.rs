@@ -4,8 +4,10 @@ use sqlx::SqlitePool;
 
 use crate::game::GameState;
 use crate::game::entity::Entity;
-use crate::persistence::{PersistenceError, entity_repo};
+use crate::persistence::{PersistenceError, entity_effect_repo, entity_repo, interaction_repo};
 
+/// Reload config entities for the dungeons players are in. Carried entities always share their
+/// holder's location, so they're loaded along with the dungeon their holder is in.
 pub async fn sync(game_state: &GameState, pool: &SqlitePool) -> Result<(), PersistenceError> {
     let new_active_dungeons = compute_active_dungeons(game_state).await;
 
@@ -45,5 +47,11 @@ async fn load_dungeon_entities(
     world_id: &str,
     dungeon_id: &str,
 ) -> Result<Vec<Entity>, PersistenceError> {
-    entity_repo::find_config_entities_by_dungeon(pool, world_id, dungeon_id).await
+    let mut entities =
+        entity_repo::find_config_entities_by_dungeon(pool, world_id, dungeon_id).await?;
+    for entity in &mut entities {
+        entity.effects = entity_effect_repo::find_active_by_entity(pool, entity.id).await?;
+        entity.interactions = interaction_repo::find_by_entity(pool, entity.id).await?;
+    }
+    Ok(entities)
 }
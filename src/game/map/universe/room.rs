@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::Navigation;
-use crate::game::Description;
+use crate::game::{Description, Direction};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Room {
@@ -12,6 +14,21 @@ pub struct Room {
     pub east: Option<Navigation>,
     pub west: Option<Navigation>,
     #[serde(default)]
+    pub up: Option<Navigation>,
+    #[serde(default)]
+    pub down: Option<Navigation>,
+    #[serde(default)]
+    pub northeast: Option<Navigation>,
+    #[serde(default)]
+    pub northwest: Option<Navigation>,
+    #[serde(default)]
+    pub southeast: Option<Navigation>,
+    #[serde(default)]
+    pub southwest: Option<Navigation>,
+    /// Exits reached by name rather than direction, e.g. `enter tavern`.
+    #[serde(default)]
+    pub exits: BTreeMap<String, Navigation>,
+    #[serde(default)]
     pub entities: Vec<String>,
 }
 
@@ -24,7 +41,111 @@ impl Room {
             south: None,
             east: None,
             west: None,
+            up: None,
+            down: None,
+            northeast: None,
+            northwest: None,
+            southeast: None,
+            southwest: None,
+            exits: BTreeMap::new(),
             entities: Vec::new(),
         }
     }
+
+    /// The navigation for the exit in `direction`, if the room has one.
+    pub fn exit(&self, direction: &Direction) -> Option<&Navigation> {
+        match direction {
+            Direction::North => self.north.as_ref(),
+            Direction::South => self.south.as_ref(),
+            Direction::East => self.east.as_ref(),
+            Direction::West => self.west.as_ref(),
+            Direction::Up => self.up.as_ref(),
+            Direction::Down => self.down.as_ref(),
+            Direction::Northeast => self.northeast.as_ref(),
+            Direction::Northwest => self.northwest.as_ref(),
+            Direction::Southeast => self.southeast.as_ref(),
+            Direction::Southwest => self.southwest.as_ref(),
+        }
+    }
+
+    /// Add, replace or, with `None`, remove an exit. Direction words change that direction's
+    /// exit; anything else is a named exit.
+    pub fn set_exit(&mut self, exit: &str, navigation: Option<Navigation>) {
+        let slot = match Direction::parse(exit) {
+            Some(Direction::North) => &mut self.north,
+            Some(Direction::South) => &mut self.south,
+            Some(Direction::East) => &mut self.east,
+            Some(Direction::West) => &mut self.west,
+            Some(Direction::Up) => &mut self.up,
+            Some(Direction::Down) => &mut self.down,
+            Some(Direction::Northeast) => &mut self.northeast,
+            Some(Direction::Northwest) => &mut self.northwest,
+            Some(Direction::Southeast) => &mut self.southeast,
+            Some(Direction::Southwest) => &mut self.southwest,
+            None => {
+                match navigation {
+                    Some(nav) => self.exits.insert(exit.to_lowercase(), nav),
+                    None => self.exits.remove(&exit.to_lowercase()),
+                };
+                return;
+            }
+        };
+        *slot = navigation;
+    }
+
+    /// The named exit matching `name`, ignoring case.
+    pub fn named_exit(&self, name: &str) -> Option<(&str, &Navigation)> {
+        let name = name.trim();
+        self.exits
+            .iter()
+            .find(|(exit, _)| exit.eq_ignore_ascii_case(name))
+            .map(|(exit, nav)| (exit.as_str(), nav))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nav(room_id: &str) -> Navigation {
+        Navigation {
+            world_id: None,
+            dungeon_id: None,
+            room_id: Some(room_id.to_string()),
+        }
+    }
+
+    #[test]
+    fn exit_looks_up_direction() {
+        let mut room = Room::new("r1".to_string(), Description::default());
+        room.up = Some(nav("attic"));
+        assert_eq!(
+            room.exit(&Direction::Up).and_then(|n| n.room_id.as_deref()),
+            Some("attic")
+        );
+        assert!(room.exit(&Direction::Down).is_none());
+    }
+
+    #[test]
+    fn named_exit_ignores_case() {
+        let mut room = Room::new("r1".to_string(), Description::default());
+        room.exits.insert("tavern".to_string(), nav("tavern"));
+        let (name, _) = room.named_exit(" Tavern ").unwrap();
+        assert_eq!(name, "tavern");
+        assert!(room.named_exit("stable").is_none());
+    }
+
+    #[test]
+    fn set_exit_opens_and_closes_exits() {
+        let mut room = Room::new("r1".to_string(), Description::default());
+        room.set_exit("e", Some(nav("vault")));
+        room.set_exit("Trapdoor", Some(nav("cellar")));
+        assert_eq!(room.exit(&Direction::East), Some(&nav("vault")));
+        assert!(room.named_exit("trapdoor").is_some());
+
+        room.set_exit("east", None);
+        room.set_exit("trapdoor", None);
+        assert!(room.exit(&Direction::East).is_none());
+        assert!(room.exits.is_empty());
+    }
 }
@@ -1,9 +1,17 @@
+pub mod character_sheet;
+pub mod room_description;
+pub mod speech;
+pub mod status;
 pub mod stream;
 
 use tokio::sync::broadcast;
 
-use crate::game::map::universe::room::Room;
+use crate::game::{GameState, Location};
 
+pub use character_sheet::{AttributeGroup, CharacterSheet, SheetAttribute, SheetEffect};
+pub use room_description::{RoomDescription, VisibleEntity};
+pub use speech::{Speech, SpeechKind};
+pub use status::{PlayerStatus, StatusAttribute};
 pub use stream::stream_message;
 
 #[derive(Debug, Clone)]
@@ -19,6 +27,19 @@ pub enum Message {
         chunk: String,
         state: StreamingState,
     },
+    Room(Box<RoomDescription>),
+    Speech(Speech),
+    Channel(ChannelPost),
+    Status(Box<PlayerStatus>),
+    CharacterSheet(Box<CharacterSheet>),
+}
+
+/// A post to a chat channel.
+#[derive(Debug, Clone)]
+pub struct ChannelPost {
+    pub channel: String,
+    pub speaker: String,
+    pub content: String,
 }
 
 #[derive(Debug, Clone)]
@@ -34,16 +55,100 @@ pub fn message(tx: &broadcast::Sender<PlayerMessage>, player_id: i64, content: i
     });
 }
 
-pub fn message_room_description(
+/// Send a message to every active player whose entity is at `location`, except `except_entity_id`
+/// (usually whoever caused the message).
+pub async fn message_location(
+    game_state: &GameState,
+    location: &Location,
+    except_entity_id: i64,
+    content: impl Into<String>,
+) {
+    let content = content.into();
+    for player_id in players_where(game_state, except_entity_id, |l| l == location).await {
+        message(&game_state.message_tx, player_id, content.clone());
+    }
+}
+
+/// Ids of active players whose entity's location satisfies `filter`, except `except_entity_id`.
+pub async fn players_where(
+    game_state: &GameState,
+    except_entity_id: i64,
+    filter: impl Fn(&Location) -> bool,
+) -> Vec<i64> {
+    let entities = game_state.active_entities.read().await;
+    let players = game_state.active_players.read().await;
+    players
+        .values()
+        .filter(|p| p.entity_id != except_entity_id)
+        .filter(|p| {
+            entities
+                .get(&p.entity_id)
+                .is_some_and(|e| filter(&e.location))
+        })
+        .map(|p| p.id)
+        .collect()
+}
+
+/// Send a line of player speech.
+pub fn speech(tx: &broadcast::Sender<PlayerMessage>, player_id: i64, speech: Speech) {
+    let _ = tx.send(PlayerMessage {
+        player_id,
+        message: Message::Speech(speech),
+    });
+}
+
+/// Deliver a channel post to a player.
+pub fn channel_post(tx: &broadcast::Sender<PlayerMessage>, player_id: i64, post: ChannelPost) {
+    let _ = tx.send(PlayerMessage {
+        player_id,
+        message: Message::Channel(post),
+    });
+}
+
+/// Send one chunk of a streamed message. The final chunk must use [`StreamingState::Complete`].
+pub fn chunk(
+    tx: &broadcast::Sender<PlayerMessage>,
+    player_id: i64,
+    chunk: impl Into<String>,
+    state: StreamingState,
+) {
+    let _ = tx.send(PlayerMessage {
+        player_id,
+        message: Message::Streaming {
+            chunk: chunk.into(),
+            state,
+        },
+    });
+}
+
+/// Describe a room to a player as a structured [`RoomDescription`].
+pub fn room_description(
     tx: &broadcast::Sender<PlayerMessage>,
     player_id: i64,
-    room: &Room,
+    description: RoomDescription,
 ) {
-    let content = room
-        .description
-        .standard
-        .as_deref()
-        .unwrap_or("You look around but see nothing remarkable.")
-        .to_string();
-    message(tx, player_id, content);
+    let _ = tx.send(PlayerMessage {
+        player_id,
+        message: Message::Room(Box::new(description)),
+    });
+}
+
+/// Update a player's status bar.
+pub fn status(tx: &broadcast::Sender<PlayerMessage>, player_id: i64, status: PlayerStatus) {
+    let _ = tx.send(PlayerMessage {
+        player_id,
+        message: Message::Status(Box::new(status)),
+    });
+}
+
+/// Show a player their character sheet.
+pub fn character_sheet(
+    tx: &broadcast::Sender<PlayerMessage>,
+    player_id: i64,
+    sheet: CharacterSheet,
+) {
+    let _ = tx.send(PlayerMessage {
+        player_id,
+        message: Message::CharacterSheet(Box::new(sheet)),
+    });
 }
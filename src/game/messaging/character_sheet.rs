@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::component::{ActiveEffect, AttributeCategory, TriggerInfo};
+use crate::game::config::AttributeConfig;
+use crate::game::entity::Entity;
+
+/// One attribute on a character sheet. `value` includes modifiers from equipment; `base` is
+/// the value without them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SheetAttribute {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub value: i64,
+    pub base: i64,
+    pub max: i64,
+}
+
+/// The attributes of one [`AttributeCategory`], in `attributes.toml` order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeGroup {
+    pub category: AttributeCategory,
+    pub attributes: Vec<SheetAttribute>,
+}
+
+/// An effect currently acting on the character. `remaining_ticks` is `None` for effects that
+/// last until removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SheetEffect {
+    pub name: String,
+    pub description: Option<String>,
+    pub remaining_ticks: Option<u64>,
+}
+
+/// Everything a player can learn about their own character with `score`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharacterSheet {
+    pub name: String,
+    pub groups: Vec<AttributeGroup>,
+    pub effects: Vec<SheetEffect>,
+}
+
+impl CharacterSheet {
+    /// Sheet for `entity`. Attributes without a definition are left out, as are empty groups.
+    pub fn new(name: &str, entity: &Entity, config: &AttributeConfig) -> Self {
+        let categories = [
+            AttributeCategory::Life,
+            AttributeCategory::Speed,
+            AttributeCategory::General,
+        ];
+        let groups = categories
+            .into_iter()
+            .filter_map(|category| {
+                let attributes: Vec<SheetAttribute> = config
+                    .attributes
+                    .iter()
+                    .filter(|d| d.attribute_category == category)
+                    .filter_map(|definition| {
+                        let attribute = entity.attributes.get(&definition.id)?;
+                        Some(SheetAttribute {
+                            id: definition.id.clone(),
+                            title: definition.title.clone(),
+                            description: definition.description.clone(),
+                            value: attribute.effective_value(),
+                            base: attribute.current_value,
+                            max: attribute.max_value,
+                        })
+                    })
+                    .collect();
+                (!attributes.is_empty()).then_some(AttributeGroup {
+                    category,
+                    attributes,
+                })
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            groups,
+            effects: entity.effects.iter().map(sheet_effect).collect(),
+        }
+    }
+}
+
+/// Heading for a group of attributes.
+pub fn category_title(category: &AttributeCategory) -> &'static str {
+    match category {
+        AttributeCategory::Life => "Life",
+        AttributeCategory::Speed => "Speed",
+        AttributeCategory::General => "General",
+    }
+}
+
+fn sheet_effect(active: &ActiveEffect) -> SheetEffect {
+    let remaining_ticks = match active.effect.trigger_info {
+        TriggerInfo::Once => Some(0),
+        TriggerInfo::OverTime { end, .. } => {
+            end.map(|end| end.saturating_sub(active.elapsed_ticks))
+        }
+    };
+    SheetEffect {
+        name: active.effect.name.clone(),
+        description: active.effect.description.start_description.clone(),
+        remaining_ticks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::{Attribute, Effect, EffectDescription, EffectType};
+    use crate::game::{EntityType, Location};
+
+    fn test_entity() -> Entity {
+        let location = Location {
+            world_id: "w".to_string(),
+            dungeon_id: "d".to_string(),
+            room_id: "r".to_string(),
+        };
+        let mut entity = Entity::new(1, EntityType::Player, location);
+        let mut strength = Attribute::new("strength".to_string(), 1, 20, 10);
+        strength.set_modifier(7, 2);
+        entity.attributes.insert("strength".to_string(), strength);
+        entity.attributes.insert(
+            "hp".to_string(),
+            Attribute::new("hp".to_string(), 0, 50, 30),
+        );
+        entity
+    }
+
+    #[test]
+    fn new_groups_attributes_by_category() {
+        let sheet =
+            CharacterSheet::new("Alice", &test_entity(), &AttributeConfig::default_config());
+
+        let categories: Vec<_> = sheet.groups.iter().map(|g| g.category.clone()).collect();
+        assert_eq!(
+            categories,
+            [AttributeCategory::Life, AttributeCategory::General]
+        );
+        assert_eq!(sheet.groups[0].attributes[0].id, "hp");
+        let strength = &sheet.groups[1].attributes[0];
+        assert_eq!(strength.title, "Strength");
+        assert_eq!((strength.value, strength.base, strength.max), (12, 10, 20));
+    }
+
+    #[test]
+    fn new_lists_effects_with_remaining_ticks() {
+        let mut entity = test_entity();
+        entity.effects.push(ActiveEffect {
+            id: 1,
+            effect: Effect {
+                name: "regen".to_string(),
+                effect_type: EffectType::AttributeUpdate {
+                    attribute_id: "hp".to_string(),
+                    value: 1,
+                },
+                trigger_info: TriggerInfo::OverTime {
+                    start: 0,
+                    end: Some(10),
+                    rate: 1,
+                },
+                description: EffectDescription::default(),
+            },
+            elapsed_ticks: 4,
+        });
+
+        let sheet = CharacterSheet::new("Alice", &entity, &AttributeConfig::default_config());
+
+        assert_eq!(sheet.effects[0].name, "regen");
+        assert_eq!(sheet.effects[0].remaining_ticks, Some(6));
+    }
+}
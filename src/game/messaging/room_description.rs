@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::component::Attribute;
+use crate::game::entity::EntityType;
+use crate::game::map::universe::room::Room;
+use crate::game::{Direction, Location};
+
+/// Everything a player sees when looking around a room, kept in parts so clients can render
+/// each one differently.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoomDescription {
+    pub title: String,
+    pub dungeon: String,
+    pub world: String,
+    pub description: String,
+    /// Directions and named exits that lead somewhere, e.g. `north`, `up`, `tavern`.
+    pub exits: Vec<String>,
+    /// One line per group of entities in the room: players first, then characters and objects.
+    pub entities: Vec<String>,
+}
+
+/// An entity the viewer can see and the line describing it.
+pub struct VisibleEntity {
+    pub entity_type: EntityType,
+    pub line: String,
+}
+
+impl RoomDescription {
+    pub fn new(
+        room: &Room,
+        location: &Location,
+        attributes: &HashMap<String, Attribute>,
+        visible: Vec<VisibleEntity>,
+    ) -> Self {
+        Self {
+            title: room.id.clone(),
+            dungeon: location.dungeon_id.clone(),
+            world: location.world_id.clone(),
+            description: room
+                .description
+                .render(attributes)
+                .unwrap_or_else(|| "You look around but see nothing remarkable.".to_string()),
+            exits: exit_names(room),
+            entities: group_entities(visible),
+        }
+    }
+
+    /// Title line naming the room, its dungeon and world.
+    pub fn heading(&self) -> String {
+        format!("{} ({}, {})", self.title, self.dungeon, self.world)
+    }
+
+    pub fn exits_line(&self) -> String {
+        if self.exits.is_empty() {
+            "Exits: none".to_string()
+        } else {
+            format!("Exits: {}", self.exits.join(", "))
+        }
+    }
+}
+
+fn exit_names(room: &Room) -> Vec<String> {
+    let directions = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+        Direction::Northeast,
+        Direction::Northwest,
+        Direction::Southeast,
+        Direction::Southwest,
+        Direction::Up,
+        Direction::Down,
+    ];
+    directions
+        .iter()
+        .filter(|d| room.exit(d).is_some_and(|nav| nav.room_id.is_some()))
+        .map(Direction::to_string)
+        .chain(room.exits.keys().cloned())
+        .collect()
+}
+
+/// Order entity lines by group and collapse identical lines into one with a count.
+fn group_entities(mut visible: Vec<VisibleEntity>) -> Vec<String> {
+    visible.sort_by_key(|v| match v.entity_type {
+        EntityType::Player => 0,
+        EntityType::Character => 1,
+        EntityType::Object => 2,
+    });
+    let mut grouped: Vec<(String, usize)> = Vec::new();
+    for entity in visible {
+        match grouped.iter_mut().find(|(line, _)| *line == entity.line) {
+            Some((_, count)) => *count += 1,
+            None => grouped.push((entity.line, 1)),
+        }
+    }
+    grouped
+        .into_iter()
+        .map(|(line, count)| match count {
+            1 => line,
+            n => format!("{line} (x{n})"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Description, Navigation};
+
+    fn location() -> Location {
+        Location {
+            world_id: "overworld".to_string(),
+            dungeon_id: "town".to_string(),
+            room_id: "square".to_string(),
+        }
+    }
+
+    fn visible(entity_type: EntityType, line: &str) -> VisibleEntity {
+        VisibleEntity {
+            entity_type,
+            line: line.to_string(),
+        }
+    }
+
+    #[test]
+    fn lists_populated_exits_in_order() {
+        let mut room = Room::new(
+            "square".to_string(),
+            Description::new(Some("A square.".to_string())),
+        );
+        let nav = Navigation {
+            world_id: None,
+            dungeon_id: None,
+            room_id: Some("elsewhere".to_string()),
+        };
+        room.up = Some(nav.clone());
+        room.south = Some(nav.clone());
+        room.west = Some(Navigation::new());
+        room.exits.insert("tavern".to_string(), nav);
+
+        let description = RoomDescription::new(&room, &location(), &HashMap::new(), Vec::new());
+        assert_eq!(description.heading(), "square (town, overworld)");
+        assert_eq!(description.dungeon, "town");
+        assert_eq!(description.exits, vec!["south", "up", "tavern"]);
+        assert_eq!(description.exits_line(), "Exits: south, up, tavern");
+    }
+
+    #[test]
+    fn groups_entity_lines() {
+        let room = Room::new("square".to_string(), Description::default());
+        let description = RoomDescription::new(
+            &room,
+            &location(),
+            &HashMap::new(),
+            vec![
+                visible(EntityType::Object, "A rat is here."),
+                visible(EntityType::Character, "An innkeeper is here."),
+                visible(EntityType::Object, "A rat is here."),
+                visible(EntityType::Player, "Alice is here."),
+            ],
+        );
+        assert_eq!(
+            description.entities,
+            vec![
+                "Alice is here.",
+                "An innkeeper is here.",
+                "A rat is here. (x2)"
+            ]
+        );
+        assert_eq!(
+            description.description,
+            "You look around but see nothing remarkable."
+        );
+        assert_eq!(description.exits_line(), "Exits: none");
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// How something was said, so clients can colour speech differently from narration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeechKind {
+    Say,
+    Emote,
+    Whisper,
+    Shout,
+    Tell,
+}
+
+/// A line of player speech, already phrased for the player receiving it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Speech {
+    pub kind: SpeechKind,
+    /// Name of the player who spoke.
+    pub speaker: String,
+    pub text: String,
+}
+
+impl Speech {
+    pub fn new(kind: SpeechKind, speaker: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            speaker: speaker.into(),
+            text: text.into(),
+        }
+    }
+}
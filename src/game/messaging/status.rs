@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::component::AttributeCategory;
+use crate::game::config::AttributeConfig;
+use crate::game::entity::Entity;
+
+/// One attribute shown on a player's status bar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusAttribute {
+    pub id: String,
+    pub title: String,
+    pub current: i64,
+    pub max: i64,
+    pub category: AttributeCategory,
+}
+
+/// What a player's status bar shows: their status attributes and where they are.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStatus {
+    pub attributes: Vec<StatusAttribute>,
+    pub room: String,
+    pub dungeon: String,
+}
+
+impl PlayerStatus {
+    /// Status for `entity`, listing the attributes in `attribute_ids` it has, in that order.
+    pub fn new(entity: &Entity, config: &AttributeConfig, attribute_ids: &[String]) -> Self {
+        let attributes = attribute_ids
+            .iter()
+            .filter_map(|id| {
+                let attribute = entity.attributes.get(id)?;
+                let definition = config.find(id)?;
+                Some(StatusAttribute {
+                    id: id.clone(),
+                    title: definition.title.clone(),
+                    current: attribute.effective_value(),
+                    max: attribute.max_value,
+                    category: definition.attribute_category.clone(),
+                })
+            })
+            .collect();
+        Self {
+            attributes,
+            room: entity.location.room_id.clone(),
+            dungeon: entity.location.dungeon_id.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::Attribute;
+    use crate::game::{EntityType, Location};
+
+    #[test]
+    fn new_lists_configured_attributes_in_order() {
+        let location = Location {
+            world_id: "overworld".to_string(),
+            dungeon_id: "town".to_string(),
+            room_id: "tavern".to_string(),
+        };
+        let mut entity = Entity::new(1, EntityType::Player, location);
+        for (id, current) in [("hp", 40), ("mp", 10)] {
+            entity.attributes.insert(
+                id.to_string(),
+                Attribute::new(id.to_string(), 0, 50, current),
+            );
+        }
+        let ids = ["mp", "hp", "missing"].map(String::from);
+
+        let status = PlayerStatus::new(&entity, &AttributeConfig::default_config(), &ids);
+
+        let listed: Vec<_> = status
+            .attributes
+            .iter()
+            .map(|a| (a.id.as_str(), a.current, a.max))
+            .collect();
+        assert_eq!(listed, [("mp", 10, 50), ("hp", 40, 50)]);
+        assert_eq!(status.attributes[1].category, AttributeCategory::Life);
+        assert_eq!(status.room, "tavern");
+        assert_eq!(status.dungeon, "town");
+    }
+}
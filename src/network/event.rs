@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::game::messaging::{CharacterSheet, PlayerStatus, RoomDescription, SpeechKind};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerInfoResponse {
     pub server_id: String,
@@ -47,11 +49,39 @@ pub enum NetworkEvent {
         chunk: String,
         is_final: bool,
     },
+    RoomDescription {
+        player_id: i64,
+        room: Box<RoomDescription>,
+    },
+    Speech {
+        player_id: i64,
+        kind: SpeechKind,
+        speaker: String,
+        text: String,
+    },
+    ChannelMessage {
+        player_id: i64,
+        channel: String,
+        speaker: String,
+        content: String,
+    },
+    /// The player's status attributes or location changed.
+    PlayerStatus {
+        player_id: i64,
+        status: Box<PlayerStatus>,
+    },
+    /// The player asked to see their character sheet.
+    CharacterSheet {
+        player_id: i64,
+        sheet: Box<CharacterSheet>,
+    },
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::AttributeCategory;
+    use crate::game::messaging::StatusAttribute;
 
     #[test]
     fn serialize_ping() {
@@ -100,4 +130,74 @@ mod tests {
         let decoded: NetworkEvent = serde_json::from_str(&json).unwrap();
         assert_eq!(event, decoded);
     }
+
+    #[test]
+    fn round_trip_room_description() {
+        let event = NetworkEvent::RoomDescription {
+            player_id: 1,
+            room: Box::new(RoomDescription {
+                title: "tavern".to_string(),
+                dungeon: "town".to_string(),
+                world: "overworld".to_string(),
+                description: "A warm tavern.".to_string(),
+                exits: vec!["south".to_string()],
+                entities: vec!["An innkeeper is here.".to_string()],
+            }),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.starts_with(r#"{"type":"room_description""#));
+        let decoded: NetworkEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(event, decoded);
+    }
+
+    #[test]
+    fn serialize_speech() {
+        let json = serde_json::to_string(&NetworkEvent::Speech {
+            player_id: 1,
+            kind: SpeechKind::Shout,
+            speaker: "Alice".to_string(),
+            text: "Alice shouts, \"Hi\"".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"speech","player_id":1,"kind":"shout","speaker":"Alice","text":"Alice shouts, \"Hi\""}"#
+        );
+    }
+
+    #[test]
+    fn round_trip_channel_message() {
+        let event = NetworkEvent::ChannelMessage {
+            player_id: 1,
+            channel: "ooc".to_string(),
+            speaker: "Alice".to_string(),
+            content: "brb".to_string(),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.starts_with(r#"{"type":"channel_message""#));
+        let decoded: NetworkEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(event, decoded);
+    }
+
+    #[test]
+    fn round_trip_player_status() {
+        let event = NetworkEvent::PlayerStatus {
+            player_id: 1,
+            status: Box::new(PlayerStatus {
+                attributes: vec![StatusAttribute {
+                    id: "hp".to_string(),
+                    title: "Hit Points".to_string(),
+                    current: 40,
+                    max: 50,
+                    category: AttributeCategory::Life,
+                }],
+                room: "tavern".to_string(),
+                dungeon: "town".to_string(),
+            }),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.starts_with(r#"{"type":"player_status""#));
+        let decoded: NetworkEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(event, decoded);
+    }
 }
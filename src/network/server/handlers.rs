@@ -4,7 +4,66 @@ pub use player::{player_create_handler, player_list_handler, player_select_handl
 
 use serde::Deserialize;
 
-use crate::game::Interaction;
+use crate::game::{Interaction, Movement, Navigation};
+
+#[derive(Deserialize)]
+pub struct WarpRequest {
+    pub player_id: i64,
+    #[serde(flatten)]
+    pub destination: Navigation,
+}
+
+/// Whether the request carries the `admin_token` from `mud.toml` as a bearer token. Always
+/// false when no token is configured.
+fn is_admin(state: &AppState, headers: &HeaderMap) -> bool {
+    let Some(token) = state.game_state.mud_config.admin_token.as_deref() else {
+        return false;
+    };
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes()))
+}
+
+/// Compare without stopping at the first differing byte, so response times don't reveal how
+/// much of the token a guess got right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Admin endpoint that warps an active player. The warp is queued like any other interaction
+/// so it is validated and applied by the game loop.
+pub async fn warp_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(req): Json<WarpRequest>,
+) -> StatusCode {
+    tracing::info!(player_id = req.player_id, "POST /admin/warp");
+    if !is_admin(&state, &headers) {
+        return StatusCode::UNAUTHORIZED;
+    }
+    let entity_id = state
+        .game_state
+        .active_players
+        .read()
+        .await
+        .values()
+        .find(|p| p.id == req.player_id)
+        .map(|p| p.entity_id);
+    let Some(entity_id) = entity_id else {
+        return StatusCode::NOT_FOUND;
+    };
+    state
+        .game_state
+        .mailboxes
+        .push(
+            entity_id,
+            Interaction::Movement(Movement::Warp(req.destination)),
+        )
+        .await;
+    StatusCode::OK
+}
 
 #[derive(Deserialize)]
 pub struct SendInteractionRequest {
@@ -17,6 +76,14 @@ pub async fn send_interaction_handler(
     Json(req): Json<SendInteractionRequest>,
 ) -> StatusCode {
     tracing::info!(client_id = %req.client_id, "POST /interactions");
+    // Warps teleport anywhere, so players only get them through portals, effects and the
+    // admin endpoint. Verbs are declared by entities and run through `Use`.
+    if matches!(
+        req.interaction,
+        Interaction::Movement(Movement::Warp(_)) | Interaction::Verb(_)
+    ) {
+        return StatusCode::FORBIDDEN;
+    }
     let players = state.game_state.active_players.read().await;
     let player = match players.get(&req.client_id) {
         Some(p) => p.clone(),
@@ -36,7 +103,8 @@ use std::time::Instant;
 
 use axum::Json;
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::sse::{Event, KeepAlive, Sse};
 use futures_util::stream::Stream;
 use tokio_stream::StreamExt;
@@ -78,6 +146,8 @@ pub async fn sse_handler(
     let guard = SseCleanupGuard {
         client_id: query.client_id,
         connections: state.connections.clone(),
+        game_state: state.game_state.clone(),
+        db: state.db.clone(),
     };
     let stream = GuardedStream {
         inner,
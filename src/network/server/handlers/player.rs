@@ -5,10 +5,11 @@ use axum::extract::State;
 use axum::http::StatusCode;
 use tracing::info;
 
-use crate::game::{Entity, EntityType, Location};
+use crate::game::game_loop::interactions::mail;
+use crate::game::{Entity, EntityType};
 use crate::network::event::{NetworkEvent, PlayerInfo, PlayerListResponse};
 use crate::network::server::state::{AppState, PlayerCreateBody, PlayerListBody, PlayerSelectBody};
-use crate::persistence::{entity_repo, player_repo};
+use crate::persistence::{entity_effect_repo, entity_repo, player_repo};
 
 pub async fn player_list_handler(
     State(state): State<Arc<AppState>>,
@@ -36,13 +37,17 @@ pub async fn player_create_handler(
 ) -> Result<Json<PlayerInfo>, StatusCode> {
     info!(client_id = %body.client_id, name = %body.name, "POST /players/create");
     let pool = state.db.pool();
-    let spawn = &state.game_state.mud_config.spawn;
-    let location = Location {
-        world_id: spawn.world_id.clone(),
-        dungeon_id: spawn.dungeon_id.clone(),
-        room_id: spawn.room_id.clone(),
-    };
-    let entity = Entity::new(0, EntityType::Player, location);
+    let game_state = &state.game_state;
+    let location = game_state.mud_config.spawn.location();
+    let mut entity = Entity::new(0, EntityType::Player, location);
+    entity.attributes = game_state
+        .mud_config
+        .player
+        .starting_attributes(&game_state.attribute_config, &body.allocation)
+        .map_err(|e| {
+            tracing::warn!(error = %e, "Rejected stat allocation");
+            StatusCode::BAD_REQUEST
+        })?;
     let entity_id = entity_repo::insert(pool, &entity)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -70,33 +75,40 @@ pub async fn player_select_handler(
         return Err(StatusCode::FORBIDDEN);
     }
 
-    let entity = entity_repo::find_by_id(pool, player.entity_id)
+    let mut entity = entity_repo::find_by_id(pool, player.entity_id)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
-
-    state
-        .game_state
-        .active_entities
-        .write()
+    entity.effects = entity_effect_repo::find_active_by_entity(pool, entity.id)
         .await
-        .insert(entity.id, entity);
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let game_state = &state.game_state;
+    if game_state
+        .mud_config
+        .player
+        .backfill(&game_state.attribute_config, &mut entity.attributes)
+        && let Err(e) = entity_repo::update_attributes(pool, entity.id, &entity.attributes).await
+    {
+        tracing::error!(error = %e, "Failed to back-fill player attributes");
+    }
 
     state
         .game_state
-        .active_players
-        .write()
-        .await
-        .insert(body.client_id.clone(), player.clone());
+        .add_player(&body.client_id, player.clone(), entity)
+        .await;
 
     if let Err(e) = state.game_state.sync_active_entities(state.db.pool()).await {
         tracing::error!(error = %e, "Failed to sync active entities on player select");
     }
 
-    let conns = state.connections.read().await;
-    if let Some(client) = conns.get(&body.client_id) {
-        let _ = client
-            .personal_tx
+    let personal_tx = state
+        .connections
+        .read()
+        .await
+        .get(&body.client_id)
+        .map(|client| client.personal_tx.clone());
+    if let Some(tx) = personal_tx {
+        let _ = tx
             .send(NetworkEvent::PlayerSelected {
                 client_id: body.client_id,
                 player_id: player.id,
@@ -105,6 +117,9 @@ pub async fn player_select_handler(
             .await;
     }
 
+    // Sent after PlayerSelected so the client knows which player the notice is for.
+    mail::notify_unread(&state.game_state, &state.db, &player).await;
+
     Ok(Json(PlayerInfo {
         id: player.id,
         name: player.name,
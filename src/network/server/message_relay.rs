@@ -14,8 +14,11 @@ use super::state::ConnectedClient;
 ///
 /// Subscribes to the game's broadcast channel and forwards each PlayerMessage
 /// to the correct SSE client. Complete messages become NetworkEvent::Message;
-/// streaming chunks become NetworkEvent::MessageChunk. The player's client_id
-/// is resolved via active_players to find the right SSE channel.
+/// streaming chunks become NetworkEvent::MessageChunk; room descriptions become
+/// NetworkEvent::RoomDescription, speech becomes NetworkEvent::Speech, channel posts become
+/// NetworkEvent::ChannelMessage, status updates become NetworkEvent::PlayerStatus and character
+/// sheets become NetworkEvent::CharacterSheet. The player's client_id is resolved via
+/// active_players to find the right SSE channel.
 pub fn spawn(
     mut msg_rx: broadcast::Receiver<PlayerMessage>,
     connections: Arc<RwLock<HashMap<String, ConnectedClient>>>,
@@ -44,6 +47,45 @@ pub fn spawn(
                         },
                     )
                 }
+                Message::Room(room) => (
+                    pm.player_id,
+                    NetworkEvent::RoomDescription {
+                        player_id: pm.player_id,
+                        room,
+                    },
+                ),
+                Message::Speech(speech) => (
+                    pm.player_id,
+                    NetworkEvent::Speech {
+                        player_id: pm.player_id,
+                        kind: speech.kind,
+                        speaker: speech.speaker,
+                        text: speech.text,
+                    },
+                ),
+                Message::Channel(post) => (
+                    pm.player_id,
+                    NetworkEvent::ChannelMessage {
+                        player_id: pm.player_id,
+                        channel: post.channel,
+                        speaker: post.speaker,
+                        content: post.content,
+                    },
+                ),
+                Message::Status(status) => (
+                    pm.player_id,
+                    NetworkEvent::PlayerStatus {
+                        player_id: pm.player_id,
+                        status,
+                    },
+                ),
+                Message::CharacterSheet(sheet) => (
+                    pm.player_id,
+                    NetworkEvent::CharacterSheet {
+                        player_id: pm.player_id,
+                        sheet,
+                    },
+                ),
             };
             // Look up the client_id for this player to find their SSE channel.
             let players = game_state.active_players.read().await;
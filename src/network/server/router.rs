@@ -6,7 +6,7 @@ use axum::routing::{get, post};
 use super::handlers::{
     maps_reload_handler, ping_handler, player_create_handler, player_list_handler,
     player_select_handler, send_interaction_handler, server_info_handler, session_end_handler,
-    session_start_handler, sse_handler,
+    session_start_handler, sse_handler, warp_handler,
 };
 use super::state::AppState;
 
@@ -21,6 +21,7 @@ pub fn build_router(state: Arc<AppState>) -> Router {
         .route("/players/create", post(player_create_handler))
         .route("/players/select", post(player_select_handler))
         .route("/maps/reload", post(maps_reload_handler))
+        .route("/admin/warp", post(warp_handler))
         .route("/interactions", post(send_interaction_handler))
         .with_state(state)
 }
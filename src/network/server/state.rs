@@ -35,14 +35,23 @@ pub struct AppState {
 pub struct SseCleanupGuard {
     pub client_id: String,
     pub connections: Arc<RwLock<HashMap<String, ConnectedClient>>>,
+    pub game_state: Arc<GameState>,
+    pub db: Database,
 }
 
 impl Drop for SseCleanupGuard {
     fn drop(&mut self) {
         let client_id = self.client_id.clone();
         let connections = self.connections.clone();
+        let game_state = self.game_state.clone();
+        let db = self.db.clone();
         tokio::spawn(async move {
             connections.write().await.remove(&client_id);
+            if game_state.remove_player(&client_id).await.is_some()
+                && let Err(e) = game_state.sync_active_entities(db.pool()).await
+            {
+                tracing::error!(error = %e, "Failed to sync active entities on disconnect");
+            }
             info!(client_id = %client_id, "SSE disconnected — session ended");
         });
     }
@@ -93,6 +102,9 @@ pub struct PlayerListBody {
 pub struct PlayerCreateBody {
     pub client_id: String,
     pub name: String,
+    /// Stat points to add to each stat, up to the template's `stat_points`.
+    #[serde(default)]
+    pub allocation: HashMap<String, i64>,
 }
 
 #[derive(Deserialize)]
@@ -1,10 +1,15 @@
 pub mod attribute_repo;
+pub mod channel_repo;
 pub mod database;
+pub mod dialog_reward_repo;
 pub mod dungeon_repo;
 pub mod entity_effect_repo;
 pub mod entity_repo;
 pub mod error;
+#[cfg(test)]
+pub mod fixtures;
 pub mod interaction_repo;
+pub mod mail_repo;
 pub mod player_repo;
 pub mod room_repo;
 pub mod server_state_repo;
@@ -0,0 +1,107 @@
+use sqlx::SqlitePool;
+
+use crate::persistence::error::PersistenceError;
+
+/// Record whether a player joined or left a channel. Players without a record for a channel
+/// follow the channel's `auto_join` setting.
+pub async fn set_joined(
+    pool: &SqlitePool,
+    player_id: i64,
+    channel: &str,
+    joined: bool,
+) -> Result<(), PersistenceError> {
+    sqlx::query(
+        "INSERT OR REPLACE INTO player_channels (player_id, channel, joined) VALUES (?, ?, ?)",
+    )
+    .bind(player_id)
+    .bind(channel)
+    .bind(joined)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Every player's recorded choice for `channel`, as `(player_id, joined)` pairs.
+pub async fn find_by_channel(
+    pool: &SqlitePool,
+    channel: &str,
+) -> Result<Vec<(i64, bool)>, PersistenceError> {
+    let rows: Vec<(i64, bool)> =
+        sqlx::query_as("SELECT player_id, joined FROM player_channels WHERE channel = ?")
+            .bind(channel)
+            .fetch_all(pool)
+            .await?;
+    Ok(rows)
+}
+
+/// A player's recorded choices for every channel, as `(channel, joined)` pairs.
+pub async fn find_by_player(
+    pool: &SqlitePool,
+    player_id: i64,
+) -> Result<Vec<(String, bool)>, PersistenceError> {
+    let rows: Vec<(String, bool)> =
+        sqlx::query_as("SELECT channel, joined FROM player_channels WHERE player_id = ?")
+            .bind(player_id)
+            .fetch_all(pool)
+            .await?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Description, Dungeon, Entity, EntityType, Location, Room, World};
+    use crate::persistence::database::Database;
+    use crate::persistence::{dungeon_repo, entity_repo, player_repo, room_repo, world_repo};
+
+    async fn setup(db: &Database) {
+        world_repo::insert(db.pool(), &World::new("w1".to_string()))
+            .await
+            .unwrap();
+        dungeon_repo::insert(db.pool(), &Dungeon::new("d1".to_string()), "w1")
+            .await
+            .unwrap();
+        room_repo::insert(
+            db.pool(),
+            &Room::new("r1".to_string(), Description::new(None)),
+            "d1",
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn insert_player(db: &Database, name: &str) -> i64 {
+        let location = Location {
+            world_id: "w1".to_string(),
+            dungeon_id: "d1".to_string(),
+            room_id: "r1".to_string(),
+        };
+        let entity_id =
+            entity_repo::insert(db.pool(), &Entity::new(0, EntityType::Player, location))
+                .await
+                .unwrap();
+        player_repo::insert(db.pool(), "client", name, entity_id)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn set_joined_replaces_previous_choice() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let alice = insert_player(&db, "Alice").await;
+        let bob = insert_player(&db, "Bob").await;
+        set_joined(db.pool(), alice, "trade", true).await.unwrap();
+        set_joined(db.pool(), bob, "trade", true).await.unwrap();
+        set_joined(db.pool(), bob, "trade", false).await.unwrap();
+
+        let mut rows = find_by_channel(db.pool(), "trade").await.unwrap();
+        rows.sort();
+        assert_eq!(rows, vec![(alice, true), (bob, false)]);
+        assert_eq!(
+            find_by_player(db.pool(), bob).await.unwrap(),
+            vec![("trade".to_string(), false)]
+        );
+        assert!(find_by_channel(db.pool(), "ooc").await.unwrap().is_empty());
+    }
+}
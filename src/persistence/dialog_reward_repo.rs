@@ -0,0 +1,104 @@
+use sqlx::SqlitePool;
+
+use crate::persistence::error::PersistenceError;
+
+/// Record that a player was paid for the dialog response at `path` in `npc`'s dialog tree.
+/// Returns false when they already were, so each reward is only paid once.
+pub async fn claim(
+    pool: &SqlitePool,
+    player_id: i64,
+    npc: &str,
+    path: &str,
+) -> Result<bool, PersistenceError> {
+    let result =
+        sqlx::query("INSERT OR IGNORE INTO dialog_rewards (player_id, npc, path) VALUES (?, ?, ?)")
+            .bind(player_id)
+            .bind(npc)
+            .bind(path)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Record that a player failed the roll for the dialog response at `path` in `npc`'s dialog tree.
+pub async fn record_failed_roll(
+    pool: &SqlitePool,
+    player_id: i64,
+    npc: &str,
+    path: &str,
+) -> Result<(), PersistenceError> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO dialog_roll_failures (player_id, npc, path) VALUES (?, ?, ?)",
+    )
+    .bind(player_id)
+    .bind(npc)
+    .bind(path)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Whether a player already failed the roll for the dialog response at `path`.
+pub async fn has_failed_roll(
+    pool: &SqlitePool,
+    player_id: i64,
+    npc: &str,
+    path: &str,
+) -> Result<bool, PersistenceError> {
+    let row: Option<(i64,)> = sqlx::query_as(
+        "SELECT 1 FROM dialog_roll_failures WHERE player_id = ? AND npc = ? AND path = ?",
+    )
+    .bind(player_id)
+    .bind(npc)
+    .bind(path)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::database::Database;
+    use crate::persistence::fixtures::{insert_player, insert_room};
+
+    #[tokio::test]
+    async fn claim_succeeds_once_per_response() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let location = insert_room(&db).await;
+        let alice = insert_player(&db, &location, "Alice").await.id;
+        assert!(claim(db.pool(), alice, "innkeeper", "2.1").await.unwrap());
+        assert!(!claim(db.pool(), alice, "innkeeper", "2.1").await.unwrap());
+        assert!(claim(db.pool(), alice, "innkeeper", "2.2").await.unwrap());
+        assert!(claim(db.pool(), alice, "guard", "2.1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn failed_rolls_are_remembered_per_player_and_response() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let location = insert_room(&db).await;
+        let alice = insert_player(&db, &location, "Alice").await.id;
+        let bob = insert_player(&db, &location, "Bob").await.id;
+        record_failed_roll(db.pool(), alice, "innkeeper", "2")
+            .await
+            .unwrap();
+        record_failed_roll(db.pool(), alice, "innkeeper", "2")
+            .await
+            .unwrap();
+        assert!(
+            has_failed_roll(db.pool(), alice, "innkeeper", "2")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !has_failed_roll(db.pool(), alice, "innkeeper", "3")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !has_failed_roll(db.pool(), bob, "innkeeper", "2")
+                .await
+                .unwrap()
+        );
+    }
+}
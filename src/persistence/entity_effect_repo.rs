@@ -1,15 +1,18 @@
 use sqlx::SqlitePool;
 
-use crate::game::component::effect::{Effect, EffectDescription, EffectType, TriggerInfo};
+use crate::game::component::effect::{
+    ActiveEffect, Effect, EffectDescription, EffectType, TriggerInfo,
+};
 use crate::persistence::error::PersistenceError;
 
 type EffectRow = (i64, String, String, String, String);
+type ActiveEffectRow = (i64, String, String, String, String, i64);
 
 pub async fn insert(
     pool: &SqlitePool,
     entity_id: i64,
     effect: &Effect,
-) -> Result<(), PersistenceError> {
+) -> Result<i64, PersistenceError> {
     let effect_type_json =
         serde_json::to_string(&effect.effect_type).map_err(PersistenceError::Json)?;
     let trigger_info_json =
@@ -17,7 +20,7 @@ pub async fn insert(
     let description_json =
         serde_json::to_string(&effect.description).map_err(PersistenceError::Json)?;
 
-    sqlx::query(
+    let result = sqlx::query(
         "INSERT INTO entity_effects (entity_id, name, effect_type_json, trigger_info_json, description_json) VALUES (?, ?, ?, ?, ?)",
     )
     .bind(entity_id)
@@ -27,7 +30,7 @@ pub async fn insert(
     .bind(description_json)
     .execute(pool)
     .await?;
-    Ok(())
+    Ok(result.last_insert_rowid())
 }
 
 pub async fn find_by_entity(
@@ -61,6 +64,82 @@ pub async fn find_by_entity(
         .collect()
 }
 
+pub async fn find_active_by_entity(
+    pool: &SqlitePool,
+    entity_id: i64,
+) -> Result<Vec<ActiveEffect>, PersistenceError> {
+    let rows: Vec<ActiveEffectRow> = sqlx::query_as(
+        "SELECT id, name, effect_type_json, trigger_info_json, description_json, elapsed_ticks FROM entity_effects WHERE entity_id = ?",
+    )
+    .bind(entity_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(
+            |(id, name, effect_type_json, trigger_info_json, description_json, elapsed_ticks)| {
+                let effect_type: EffectType =
+                    serde_json::from_str(&effect_type_json).map_err(PersistenceError::Json)?;
+                let trigger_info: TriggerInfo =
+                    serde_json::from_str(&trigger_info_json).map_err(PersistenceError::Json)?;
+                let description: EffectDescription =
+                    serde_json::from_str(&description_json).map_err(PersistenceError::Json)?;
+                Ok(ActiveEffect {
+                    id,
+                    effect: Effect {
+                        name,
+                        effect_type,
+                        trigger_info,
+                        description,
+                    },
+                    elapsed_ticks: elapsed_ticks as u64,
+                })
+            },
+        )
+        .collect()
+}
+
+pub async fn update_elapsed_ticks(
+    pool: &SqlitePool,
+    id: i64,
+    elapsed_ticks: u64,
+) -> Result<(), PersistenceError> {
+    sqlx::query("UPDATE entity_effects SET elapsed_ticks = ? WHERE id = ?")
+        .bind(elapsed_ticks as i64)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Save the progress of many effects at once, as `(id, elapsed_ticks)` pairs.
+pub async fn update_elapsed_ticks_many(
+    pool: &SqlitePool,
+    progress: &[(i64, u64)],
+) -> Result<(), PersistenceError> {
+    if progress.is_empty() {
+        return Ok(());
+    }
+    let mut tx = pool.begin().await?;
+    for (id, elapsed_ticks) in progress {
+        sqlx::query("UPDATE entity_effects SET elapsed_ticks = ? WHERE id = ?")
+            .bind(*elapsed_ticks as i64)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn delete(pool: &SqlitePool, id: i64) -> Result<(), PersistenceError> {
+    sqlx::query("DELETE FROM entity_effects WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
 pub async fn delete_by_entity(pool: &SqlitePool, entity_id: i64) -> Result<(), PersistenceError> {
     sqlx::query("DELETE FROM entity_effects WHERE entity_id = ?")
         .bind(entity_id)
@@ -129,6 +208,49 @@ mod tests {
         assert!(found.is_empty());
     }
 
+    #[tokio::test]
+    async fn find_active_by_entity_tracks_elapsed_ticks() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let entity_id = setup(&db).await;
+        let id = insert(db.pool(), entity_id, &make_effect()).await.unwrap();
+        update_elapsed_ticks(db.pool(), id, 7).await.unwrap();
+
+        let found = find_active_by_entity(db.pool(), entity_id).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, id);
+        assert_eq!(found[0].effect, make_effect());
+        assert_eq!(found[0].elapsed_ticks, 7);
+    }
+
+    #[tokio::test]
+    async fn update_elapsed_ticks_many_saves_each_effect() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let entity_id = setup(&db).await;
+        let first = insert(db.pool(), entity_id, &make_effect()).await.unwrap();
+        let second = insert(db.pool(), entity_id, &make_effect()).await.unwrap();
+        update_elapsed_ticks_many(db.pool(), &[(first, 3), (second, 9)])
+            .await
+            .unwrap();
+
+        let found = find_active_by_entity(db.pool(), entity_id).await.unwrap();
+        let mut ticks: Vec<_> = found.iter().map(|a| (a.id, a.elapsed_ticks)).collect();
+        ticks.sort();
+        assert_eq!(ticks, vec![(first, 3), (second, 9)]);
+    }
+
+    #[tokio::test]
+    async fn delete_removes_single_effect() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let entity_id = setup(&db).await;
+        let id = insert(db.pool(), entity_id, &make_effect()).await.unwrap();
+        insert(db.pool(), entity_id, &make_effect()).await.unwrap();
+        delete(db.pool(), id).await.unwrap();
+
+        let found = find_active_by_entity(db.pool(), entity_id).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_ne!(found[0].id, id);
+    }
+
     #[tokio::test]
     async fn delete_by_entity_removes_effects() {
         let db = Database::connect_in_memory().await.unwrap();
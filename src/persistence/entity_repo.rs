@@ -15,13 +15,20 @@ type EntityRow = (
     Option<String>,
     Option<String>,
     Option<String>,
+    Option<i64>,
+    bool,
+    Option<i64>,
+    bool,
+    Option<i64>,
 );
 
+const COLUMNS: &str = "id, entity_type, world_id, dungeon_id, room_id, config_id, attributes, description, holder_id, equipped, charges, loot_rolled, despawn_at";
+
 pub async fn insert(pool: &SqlitePool, entity: &Entity) -> Result<i64, PersistenceError> {
     let entity_type = entity_type_to_str(&entity.entity_type);
     let attributes_json = serde_json::to_string(&entity.attributes)?;
     let result = sqlx::query(
-        "INSERT INTO entities (entity_type, world_id, dungeon_id, room_id, config_id, attributes, description) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO entities (entity_type, world_id, dungeon_id, room_id, config_id, attributes, description, holder_id, despawn_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(entity_type)
     .bind(&entity.location.world_id)
@@ -30,6 +37,8 @@ pub async fn insert(pool: &SqlitePool, entity: &Entity) -> Result<i64, Persisten
     .bind(&entity.config_id)
     .bind(attributes_json)
     .bind(&entity.description)
+    .bind(entity.holder_id)
+    .bind(entity.despawn_at)
     .execute(pool)
     .await?;
     Ok(result.last_insert_rowid())
@@ -90,83 +99,25 @@ pub async fn insert_config_entity_if_missing(
 }
 
 pub async fn find_by_id(pool: &SqlitePool, id: i64) -> Result<Option<Entity>, PersistenceError> {
-    let row: Option<EntityRow> = sqlx::query_as(
-        "SELECT id, entity_type, world_id, dungeon_id, room_id, config_id, attributes, description FROM entities WHERE id = ?",
-    )
-        .bind(id)
-        .fetch_optional(pool)
-        .await?;
-
-    Ok(row.map(
-        |(id, et, world_id, dungeon_id, room_id, config_id, attrs_json, description)| {
-            let attributes = attrs_json
-                .and_then(|json| match serde_json::from_str(&json) {
-                    Ok(v) => Some(v),
-                    Err(e) => {
-                        tracing::warn!("Failed to deserialize attributes for entity {id}: {e}");
-                        None
-                    }
-                })
-                .unwrap_or_default();
-            let mut entity = Entity::new(
-                id,
-                entity_type_from_str(&et),
-                Location {
-                    world_id,
-                    dungeon_id,
-                    room_id,
-                },
-            );
-            entity.config_id = config_id;
-            entity.attributes = attributes;
-            entity.description = description;
-            entity
-        },
-    ))
+    let sql = format!("SELECT {COLUMNS} FROM entities WHERE id = ?");
+    let row: Option<EntityRow> = sqlx::query_as(&sql).bind(id).fetch_optional(pool).await?;
+    Ok(row.map(parse_entity))
 }
 
 pub async fn find_by_location(
     pool: &SqlitePool,
     location: &Location,
 ) -> Result<Vec<Entity>, PersistenceError> {
-    let rows: Vec<EntityRow> = sqlx::query_as(
-        "SELECT id, entity_type, world_id, dungeon_id, room_id, config_id, attributes, description FROM entities WHERE world_id = ? AND dungeon_id = ? AND room_id = ?",
-    )
+    let sql = format!(
+        "SELECT {COLUMNS} FROM entities WHERE world_id = ? AND dungeon_id = ? AND room_id = ?"
+    );
+    let rows: Vec<EntityRow> = sqlx::query_as(&sql)
         .bind(&location.world_id)
         .bind(&location.dungeon_id)
         .bind(&location.room_id)
         .fetch_all(pool)
         .await?;
-
-    Ok(rows
-        .into_iter()
-        .map(
-            |(id, et, world_id, dungeon_id, room_id, config_id, attrs_json, description)| {
-                let attributes = attrs_json
-                    .and_then(|json| match serde_json::from_str(&json) {
-                        Ok(v) => Some(v),
-                        Err(e) => {
-                            tracing::warn!("Failed to deserialize attributes for entity {id}: {e}");
-                            None
-                        }
-                    })
-                    .unwrap_or_default();
-                let mut entity = Entity::new(
-                    id,
-                    entity_type_from_str(&et),
-                    Location {
-                        world_id,
-                        dungeon_id,
-                        room_id,
-                    },
-                );
-                entity.config_id = config_id;
-                entity.attributes = attributes;
-                entity.description = description;
-                entity
-            },
-        )
-        .collect())
+    Ok(rows.into_iter().map(parse_entity).collect())
 }
 
 pub async fn find_config_entities_by_dungeon(
@@ -174,45 +125,134 @@ pub async fn find_config_entities_by_dungeon(
     world_id: &str,
     dungeon_id: &str,
 ) -> Result<Vec<Entity>, PersistenceError> {
-    let rows: Vec<EntityRow> = sqlx::query_as(
-        "SELECT id, entity_type, world_id, dungeon_id, room_id, config_id, attributes, description FROM entities WHERE config_id IS NOT NULL AND world_id = ? AND dungeon_id = ?",
+    // Used-up consumables stay out of the world until a world update resets them.
+    let sql = format!(
+        "SELECT {COLUMNS} FROM entities
+         WHERE config_id IS NOT NULL AND world_id = ? AND dungeon_id = ?
+           AND (charges IS NULL OR charges > 0)"
+    );
+    let rows: Vec<EntityRow> = sqlx::query_as(&sql)
+        .bind(world_id)
+        .bind(dungeon_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(parse_entity).collect())
+}
+
+/// Whether the map placed the entity, so it comes back on every map load unless kept.
+pub async fn is_map_placed(pool: &SqlitePool, id: i64) -> Result<bool, PersistenceError> {
+    let row: Option<(bool,)> =
+        sqlx::query_as("SELECT original_room_id IS NOT NULL FROM entities WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+    Ok(row.is_some_and(|(placed,)| placed))
+}
+
+/// Ids of config entities placed by the map, with the room they were placed in.
+pub async fn find_original_locations(
+    pool: &SqlitePool,
+) -> Result<Vec<(i64, Location)>, PersistenceError> {
+    let rows: Vec<(i64, String, String, String)> = sqlx::query_as(
+        "SELECT id, original_world_id, original_dungeon_id, original_room_id FROM entities
+         WHERE config_id IS NOT NULL AND original_room_id IS NOT NULL",
     )
-    .bind(world_id)
-    .bind(dungeon_id)
     .fetch_all(pool)
     .await?;
-
     Ok(rows
         .into_iter()
-        .map(
-            |(id, et, world_id, dungeon_id, room_id, config_id, attrs_json, description)| {
-                let attributes = attrs_json
-                    .and_then(|json| match serde_json::from_str(&json) {
-                        Ok(v) => Some(v),
-                        Err(e) => {
-                            tracing::warn!("Failed to deserialize attributes for entity {id}: {e}");
-                            None
-                        }
-                    })
-                    .unwrap_or_default();
-                let mut entity = Entity::new(
-                    id,
-                    entity_type_from_str(&et),
-                    Location {
-                        world_id,
-                        dungeon_id,
-                        room_id,
-                    },
-                );
-                entity.config_id = config_id;
-                entity.attributes = attributes;
-                entity.description = description;
-                entity
-            },
-        )
+        .map(|(id, world_id, dungeon_id, room_id)| {
+            (
+                id,
+                Location {
+                    world_id,
+                    dungeon_id,
+                    room_id,
+                },
+            )
+        })
         .collect())
 }
 
+/// Config entities placed by the map whose stored state shows they were disturbed: moved out of
+/// their original room, carried, worn, looted or used.
+pub async fn find_disturbed_placed(pool: &SqlitePool) -> Result<Vec<Entity>, PersistenceError> {
+    let sql = format!(
+        "SELECT {COLUMNS} FROM entities
+         WHERE config_id IS NOT NULL AND original_room_id IS NOT NULL
+           AND (world_id != original_world_id OR dungeon_id != original_dungeon_id
+                OR room_id != original_room_id OR holder_id IS NOT NULL OR equipped
+                OR loot_rolled OR charges IS NOT NULL)"
+    );
+    let rows: Vec<EntityRow> = sqlx::query_as(&sql).fetch_all(pool).await?;
+    Ok(rows.into_iter().map(parse_entity).collect())
+}
+
+/// Ids of entities whose lifetime ran out at or before `now_ms`.
+pub async fn find_expired(pool: &SqlitePool, now_ms: i64) -> Result<Vec<i64>, PersistenceError> {
+    let rows: Vec<(i64,)> =
+        sqlx::query_as("SELECT id FROM entities WHERE despawn_at IS NOT NULL AND despawn_at <= ?")
+            .bind(now_ms)
+            .fetch_all(pool)
+            .await?;
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+/// Entities held by `holder_id`.
+pub async fn find_held_by(
+    pool: &SqlitePool,
+    holder_id: i64,
+) -> Result<Vec<Entity>, PersistenceError> {
+    let sql = format!("SELECT {COLUMNS} FROM entities WHERE holder_id = ?");
+    let rows: Vec<EntityRow> = sqlx::query_as(&sql).bind(holder_id).fetch_all(pool).await?;
+    Ok(rows.into_iter().map(parse_entity).collect())
+}
+
+fn parse_entity(row: EntityRow) -> Entity {
+    let (
+        id,
+        et,
+        world_id,
+        dungeon_id,
+        room_id,
+        config_id,
+        attrs_json,
+        description,
+        holder_id,
+        equipped,
+        charges,
+        loot_rolled,
+        despawn_at,
+    ) = row;
+    let attributes = attrs_json
+        .and_then(|json| match serde_json::from_str(&json) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                tracing::warn!("Failed to deserialize attributes for entity {id}: {e}");
+                None
+            }
+        })
+        .unwrap_or_default();
+    let mut entity = Entity::new(
+        id,
+        entity_type_from_str(&et),
+        Location {
+            world_id,
+            dungeon_id,
+            room_id,
+        },
+    );
+    entity.config_id = config_id;
+    entity.attributes = attributes;
+    entity.description = description;
+    entity.holder_id = holder_id;
+    entity.equipped = equipped;
+    entity.charges = charges;
+    entity.loot_rolled = loot_rolled;
+    entity.despawn_at = despawn_at;
+    entity
+}
+
 pub async fn update_attributes(
     pool: &SqlitePool,
     entity_id: i64,
@@ -242,6 +282,102 @@ pub async fn update_location(
     Ok(())
 }
 
+/// Move everything `holder_id` carries, and everything those carry, to `location`.
+pub async fn update_carried_location(
+    pool: &SqlitePool,
+    holder_id: i64,
+    location: &Location,
+) -> Result<(), PersistenceError> {
+    sqlx::query(
+        "WITH RECURSIVE carried(id) AS (
+             SELECT id FROM entities WHERE holder_id = ?
+             UNION SELECT e.id FROM entities e JOIN carried c ON e.holder_id = c.id
+         )
+         UPDATE entities SET world_id = ?, dungeon_id = ?, room_id = ?
+         WHERE id IN (SELECT id FROM carried)",
+    )
+    .bind(holder_id)
+    .bind(&location.world_id)
+    .bind(&location.dungeon_id)
+    .bind(&location.room_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Set or clear the entity holding this one. Held entities travel with their holder.
+pub async fn update_holder(
+    pool: &SqlitePool,
+    entity_id: i64,
+    holder_id: Option<i64>,
+) -> Result<(), PersistenceError> {
+    sqlx::query("UPDATE entities SET holder_id = ? WHERE id = ?")
+        .bind(holder_id)
+        .bind(entity_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn update_equipped(
+    pool: &SqlitePool,
+    entity_id: i64,
+    equipped: bool,
+) -> Result<(), PersistenceError> {
+    sqlx::query("UPDATE entities SET equipped = ? WHERE id = ?")
+        .bind(equipped)
+        .bind(entity_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Write back an entity's location, attributes and item state.
+pub async fn update(pool: &SqlitePool, entity: &Entity) -> Result<(), PersistenceError> {
+    let attributes_json = serde_json::to_string(&entity.attributes)?;
+    sqlx::query(
+        "UPDATE entities SET world_id = ?, dungeon_id = ?, room_id = ?, attributes = ?, holder_id = ?, equipped = ?, charges = ?, loot_rolled = ? WHERE id = ?",
+    )
+    .bind(&entity.location.world_id)
+    .bind(&entity.location.dungeon_id)
+    .bind(&entity.location.room_id)
+    .bind(attributes_json)
+    .bind(entity.holder_id)
+    .bind(entity.equipped)
+    .bind(entity.charges)
+    .bind(entity.loot_rolled)
+    .bind(entity.id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn update_charges(
+    pool: &SqlitePool,
+    entity_id: i64,
+    charges: i64,
+) -> Result<(), PersistenceError> {
+    sqlx::query("UPDATE entities SET charges = ? WHERE id = ?")
+        .bind(charges)
+        .bind(entity_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn update_loot_rolled(
+    pool: &SqlitePool,
+    entity_id: i64,
+    loot_rolled: bool,
+) -> Result<(), PersistenceError> {
+    sqlx::query("UPDATE entities SET loot_rolled = ? WHERE id = ?")
+        .bind(loot_rolled)
+        .bind(entity_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
 pub async fn delete(pool: &SqlitePool, id: i64) -> Result<(), PersistenceError> {
     sqlx::query("DELETE FROM entities WHERE id = ?")
         .bind(id)
@@ -595,4 +731,157 @@ mod tests {
         assert_eq!(found.attributes.len(), 1);
         assert_eq!(found.attributes["str"].current_value, 15);
     }
+
+    #[tokio::test]
+    async fn update_holder_sets_and_clears_holder() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let player = insert(
+            db.pool(),
+            &Entity::new(0, EntityType::Player, test_location()),
+        )
+        .await
+        .unwrap();
+        let item = insert(
+            db.pool(),
+            &Entity::new(0, EntityType::Object, test_location()),
+        )
+        .await
+        .unwrap();
+
+        update_holder(db.pool(), item, Some(player)).await.unwrap();
+        let found = find_by_id(db.pool(), item).await.unwrap().unwrap();
+        assert_eq!(found.holder_id, Some(player));
+
+        update_equipped(db.pool(), item, true).await.unwrap();
+        let found = find_by_id(db.pool(), item).await.unwrap().unwrap();
+        assert!(found.equipped);
+
+        update_holder(db.pool(), item, None).await.unwrap();
+        let found = find_by_id(db.pool(), item).await.unwrap().unwrap();
+        assert_eq!(found.holder_id, None);
+    }
+
+    #[tokio::test]
+    async fn find_original_locations_skips_spawned_entities() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let (placed, _) = insert_config_entity_if_missing(
+            db.pool(),
+            &EntityType::Object,
+            &test_location(),
+            "entities/chest",
+            None,
+        )
+        .await
+        .unwrap();
+        let mut spawned = Entity::new(0, EntityType::Object, test_location());
+        spawned.config_id = Some("entities/potion".to_string());
+        spawned.holder_id = Some(placed);
+        let spawned = insert(db.pool(), &spawned).await.unwrap();
+
+        let originals = find_original_locations(db.pool()).await.unwrap();
+        assert_eq!(originals, vec![(placed, test_location())]);
+        let held = find_held_by(db.pool(), placed).await.unwrap();
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].id, spawned);
+    }
+
+    #[tokio::test]
+    async fn find_disturbed_placed_skips_untouched_and_spawned_entities() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let mut placed = Vec::new();
+        for config_id in ["entities/chest", "entities/potion"] {
+            let (id, _) = insert_config_entity_if_missing(
+                db.pool(),
+                &EntityType::Object,
+                &test_location(),
+                config_id,
+                None,
+            )
+            .await
+            .unwrap();
+            placed.push(id);
+        }
+        update_charges(db.pool(), placed[1], 0).await.unwrap();
+        let mut spawned = Entity::new(0, EntityType::Object, test_location());
+        spawned.config_id = Some("entities/potion".to_string());
+        spawned.holder_id = Some(placed[0]);
+        insert(db.pool(), &spawned).await.unwrap();
+
+        let disturbed = find_disturbed_placed(db.pool()).await.unwrap();
+        let ids: Vec<i64> = disturbed.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![placed[1]]);
+    }
+
+    #[tokio::test]
+    async fn update_carried_location_moves_nested_items() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let mut elsewhere = test_location();
+        elsewhere.room_id = "r2".to_string();
+        room_repo::insert(
+            db.pool(),
+            &Room::new("r2".to_string(), Description::new(None)),
+            "d1",
+        )
+        .await
+        .unwrap();
+        let holder = insert(
+            db.pool(),
+            &Entity::new(0, EntityType::Character, elsewhere.clone()),
+        )
+        .await
+        .unwrap();
+        let mut bag = Entity::new(0, EntityType::Object, elsewhere.clone());
+        bag.holder_id = Some(holder);
+        let bag = insert(db.pool(), &bag).await.unwrap();
+        let mut coin = Entity::new(0, EntityType::Object, elsewhere.clone());
+        coin.holder_id = Some(bag);
+        let coin = insert(db.pool(), &coin).await.unwrap();
+
+        update_carried_location(db.pool(), holder, &test_location())
+            .await
+            .unwrap();
+        for id in [bag, coin] {
+            let entity = find_by_id(db.pool(), id).await.unwrap().unwrap();
+            assert_eq!(entity.location, test_location());
+        }
+        let holder = find_by_id(db.pool(), holder).await.unwrap().unwrap();
+        assert_eq!(holder.location, elsewhere);
+    }
+
+    #[tokio::test]
+    async fn update_writes_entity_state() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let mut entity = Entity::new(0, EntityType::Object, test_location());
+        entity.id = insert(db.pool(), &entity).await.unwrap();
+        entity.loot_rolled = true;
+        entity.charges = Some(2);
+        update(db.pool(), &entity).await.unwrap();
+
+        let found = find_by_id(db.pool(), entity.id).await.unwrap().unwrap();
+        assert!(found.loot_rolled);
+        assert_eq!(found.charges, Some(2));
+    }
+
+    #[tokio::test]
+    async fn find_expired_returns_entities_past_their_lifetime() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let mut summon = Entity::new(0, EntityType::Character, test_location());
+        summon.despawn_at = Some(1_000);
+        let summon = insert(db.pool(), &summon).await.unwrap();
+        insert(
+            db.pool(),
+            &Entity::new(0, EntityType::Object, test_location()),
+        )
+        .await
+        .unwrap();
+
+        assert!(find_expired(db.pool(), 999).await.unwrap().is_empty());
+        assert_eq!(find_expired(db.pool(), 1_000).await.unwrap(), vec![summon]);
+    }
 }
@@ -0,0 +1,44 @@
+//! Database rows shared by tests: a room to put entities in and players to act in it.
+
+use crate::game::{Description, Dungeon, Entity, EntityType, Location, Player, Room, World};
+use crate::persistence::database::Database;
+use crate::persistence::{dungeon_repo, entity_repo, player_repo, room_repo, world_repo};
+
+/// Insert world `w1`, dungeon `d1` and room `r1`, returning the room's location.
+pub async fn insert_room(db: &Database) -> Location {
+    let pool = db.pool();
+    world_repo::insert(pool, &World::new("w1".to_string()))
+        .await
+        .unwrap();
+    dungeon_repo::insert(pool, &Dungeon::new("d1".to_string()), "w1")
+        .await
+        .unwrap();
+    room_repo::insert(
+        pool,
+        &Room::new("r1".to_string(), Description::new(None)),
+        "d1",
+    )
+    .await
+    .unwrap();
+    Location {
+        world_id: "w1".to_string(),
+        dungeon_id: "d1".to_string(),
+        room_id: "r1".to_string(),
+    }
+}
+
+/// Insert a player called `name` with an entity at `location`.
+pub async fn insert_player(db: &Database, location: &Location, name: &str) -> Player {
+    let entity = Entity::new(0, EntityType::Player, location.clone());
+    let entity_id = entity_repo::insert(db.pool(), &entity).await.unwrap();
+    let client_id = format!("client-{name}-{entity_id}");
+    let id = player_repo::insert(db.pool(), &client_id, name, entity_id)
+        .await
+        .unwrap();
+    Player {
+        id,
+        client_id,
+        name: name.to_string(),
+        entity_id,
+    }
+}
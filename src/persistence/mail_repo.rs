@@ -0,0 +1,130 @@
+use sqlx::SqlitePool;
+
+use crate::persistence::error::PersistenceError;
+
+/// A letter left for a player, with the sender's name resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mail {
+    pub id: i64,
+    pub sender_name: String,
+    pub content: String,
+    pub sent_at: String,
+}
+
+pub async fn insert(
+    pool: &SqlitePool,
+    sender_id: i64,
+    recipient_id: i64,
+    content: &str,
+) -> Result<i64, PersistenceError> {
+    let result =
+        sqlx::query("INSERT INTO mail (sender_id, recipient_id, content) VALUES (?, ?, ?)")
+            .bind(sender_id)
+            .bind(recipient_id)
+            .bind(content)
+            .execute(pool)
+            .await?;
+    Ok(result.last_insert_rowid())
+}
+
+/// Unread mail for a player, oldest first.
+pub async fn find_unread(
+    pool: &SqlitePool,
+    recipient_id: i64,
+) -> Result<Vec<Mail>, PersistenceError> {
+    let rows: Vec<(i64, String, String, String)> = sqlx::query_as(
+        "SELECT mail.id, players.name, mail.content, mail.sent_at FROM mail JOIN players ON players.id = mail.sender_id WHERE mail.recipient_id = ? AND mail.read = 0 ORDER BY mail.id",
+    )
+    .bind(recipient_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, sender_name, content, sent_at)| Mail {
+            id,
+            sender_name,
+            content,
+            sent_at,
+        })
+        .collect())
+}
+
+pub async fn count_unread(pool: &SqlitePool, recipient_id: i64) -> Result<i64, PersistenceError> {
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM mail WHERE recipient_id = ? AND read = 0")
+            .bind(recipient_id)
+            .fetch_one(pool)
+            .await?;
+    Ok(count)
+}
+
+pub async fn mark_read(pool: &SqlitePool, id: i64) -> Result<(), PersistenceError> {
+    sqlx::query("UPDATE mail SET read = 1 WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Description, Dungeon, Entity, EntityType, Location, Room, World};
+    use crate::persistence::database::Database;
+    use crate::persistence::{dungeon_repo, entity_repo, player_repo, room_repo, world_repo};
+
+    async fn setup(db: &Database) -> (i64, i64) {
+        world_repo::insert(db.pool(), &World::new("w1".to_string()))
+            .await
+            .unwrap();
+        dungeon_repo::insert(db.pool(), &Dungeon::new("d1".to_string()), "w1")
+            .await
+            .unwrap();
+        room_repo::insert(
+            db.pool(),
+            &Room::new("r1".to_string(), Description::new(None)),
+            "d1",
+        )
+        .await
+        .unwrap();
+        let mut ids = Vec::new();
+        for name in ["Alice", "Bob"] {
+            let location = Location {
+                world_id: "w1".to_string(),
+                dungeon_id: "d1".to_string(),
+                room_id: "r1".to_string(),
+            };
+            let entity_id =
+                entity_repo::insert(db.pool(), &Entity::new(0, EntityType::Player, location))
+                    .await
+                    .unwrap();
+            ids.push(
+                player_repo::insert(db.pool(), "client", name, entity_id)
+                    .await
+                    .unwrap(),
+            );
+        }
+        (ids[0], ids[1])
+    }
+
+    #[tokio::test]
+    async fn unread_mail_until_marked_read() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let (alice, bob) = setup(&db).await;
+        let first = insert(db.pool(), alice, bob, "Meet me at the tavern.")
+            .await
+            .unwrap();
+        insert(db.pool(), alice, bob, "Bring coin.").await.unwrap();
+
+        assert_eq!(count_unread(db.pool(), bob).await.unwrap(), 2);
+        assert_eq!(count_unread(db.pool(), alice).await.unwrap(), 0);
+        let unread = find_unread(db.pool(), bob).await.unwrap();
+        assert_eq!(unread[0].sender_name, "Alice");
+        assert_eq!(unread[0].content, "Meet me at the tavern.");
+
+        mark_read(db.pool(), first).await.unwrap();
+        let unread = find_unread(db.pool(), bob).await.unwrap();
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].content, "Bring coin.");
+    }
+}
@@ -69,6 +69,25 @@ pub async fn find_by_entity_id(
     }))
 }
 
+/// Find a player by name, ignoring case. Names aren't unique, so the oldest player wins.
+pub async fn find_by_name(
+    pool: &SqlitePool,
+    name: &str,
+) -> Result<Option<Player>, PersistenceError> {
+    let row: Option<(i64, String, String, i64)> = sqlx::query_as(
+        "SELECT id, client_id, name, entity_id FROM players WHERE name = ? COLLATE NOCASE ORDER BY id LIMIT 1",
+    )
+    .bind(name.trim())
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|(id, client_id, name, entity_id)| Player {
+        id,
+        client_id,
+        name,
+        entity_id,
+    }))
+}
+
 pub async fn delete(pool: &SqlitePool, id: i64) -> Result<(), PersistenceError> {
     sqlx::query("DELETE FROM players WHERE id = ?")
         .bind(id)
@@ -179,4 +198,17 @@ mod tests {
         let found = find_by_id(db.pool(), player_id).await.unwrap();
         assert!(found.is_none());
     }
+
+    #[tokio::test]
+    async fn find_by_name_ignores_case() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let entity_id = setup(&db).await;
+        let player_id = insert(db.pool(), "client1", "Alice", entity_id)
+            .await
+            .unwrap();
+
+        let found = find_by_name(db.pool(), "alice").await.unwrap().unwrap();
+        assert_eq!(found.id, player_id);
+        assert!(find_by_name(db.pool(), "bob").await.unwrap().is_none());
+    }
 }
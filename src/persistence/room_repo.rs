@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use sqlx::SqlitePool;
 
 use crate::game::{Description, Navigation, Room};
@@ -10,95 +12,120 @@ type RoomRow = (
     Option<String>,
     Option<String>,
     Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
 );
 
+const COLUMNS: &str = "id, description_json, north_json, south_json, east_json, west_json, up_json, down_json, northeast_json, northwest_json, southeast_json, southwest_json, exits_json";
+
 pub async fn insert(
     pool: &SqlitePool,
     room: &Room,
     dungeon_id: &str,
 ) -> Result<(), PersistenceError> {
-    let description_json = serde_json::to_string(&room.description)?;
-    let north_json = room.north.as_ref().map(serde_json::to_string).transpose()?;
-    let south_json = room.south.as_ref().map(serde_json::to_string).transpose()?;
-    let east_json = room.east.as_ref().map(serde_json::to_string).transpose()?;
-    let west_json = room.west.as_ref().map(serde_json::to_string).transpose()?;
-
-    sqlx::query(
-        "INSERT OR REPLACE INTO rooms (id, dungeon_id, description_json, north_json, south_json, east_json, west_json) VALUES (?, ?, ?, ?, ?, ?, ?)",
-    )
-    .bind(&room.id)
-    .bind(dungeon_id)
-    .bind(&description_json)
-    .bind(&north_json)
-    .bind(&south_json)
-    .bind(&east_json)
-    .bind(&west_json)
-    .execute(pool)
-    .await?;
-    Ok(())
+    write(pool, room, dungeon_id, "INSERT OR REPLACE").await
 }
 
 pub async fn insert_if_missing(
     pool: &SqlitePool,
     room: &Room,
     dungeon_id: &str,
+) -> Result<(), PersistenceError> {
+    write(pool, room, dungeon_id, "INSERT OR IGNORE").await
+}
+
+/// Replace a stored room's exits with the room's current ones.
+pub async fn update_exits(
+    pool: &SqlitePool,
+    room: &Room,
+    dungeon_id: &str,
+) -> Result<(), PersistenceError> {
+    let mut query = sqlx::query(
+        "UPDATE rooms SET north_json = ?, south_json = ?, east_json = ?, west_json = ?, up_json = ?, down_json = ?, northeast_json = ?, northwest_json = ?, southeast_json = ?, southwest_json = ?, exits_json = ? WHERE dungeon_id = ? AND id = ?",
+    );
+    for nav_json in navigation_json(room) {
+        query = query.bind(nav_json?);
+    }
+    query
+        .bind(serde_json::to_string(&room.exits)?)
+        .bind(dungeon_id)
+        .bind(&room.id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn write(
+    pool: &SqlitePool,
+    room: &Room,
+    dungeon_id: &str,
+    insert: &str,
 ) -> Result<(), PersistenceError> {
     let description_json = serde_json::to_string(&room.description)?;
-    let north_json = room.north.as_ref().map(serde_json::to_string).transpose()?;
-    let south_json = room.south.as_ref().map(serde_json::to_string).transpose()?;
-    let east_json = room.east.as_ref().map(serde_json::to_string).transpose()?;
-    let west_json = room.west.as_ref().map(serde_json::to_string).transpose()?;
-
-    sqlx::query(
-        "INSERT OR IGNORE INTO rooms (id, dungeon_id, description_json, north_json, south_json, east_json, west_json) VALUES (?, ?, ?, ?, ?, ?, ?)",
-    )
-    .bind(&room.id)
-    .bind(dungeon_id)
-    .bind(&description_json)
-    .bind(&north_json)
-    .bind(&south_json)
-    .bind(&east_json)
-    .bind(&west_json)
-    .execute(pool)
-    .await?;
+    let exits_json = serde_json::to_string(&room.exits)?;
+
+    let sql = format!(
+        "{insert} INTO rooms (dungeon_id, {COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    );
+    let mut query = sqlx::query(&sql)
+        .bind(dungeon_id)
+        .bind(&room.id)
+        .bind(description_json);
+    for nav_json in navigation_json(room) {
+        query = query.bind(nav_json?);
+    }
+    query.bind(exits_json).execute(pool).await?;
     Ok(())
 }
 
+/// The direction exits in column order.
+fn navigation_json(room: &Room) -> [Result<Option<String>, serde_json::Error>; 10] {
+    [
+        &room.north,
+        &room.south,
+        &room.east,
+        &room.west,
+        &room.up,
+        &room.down,
+        &room.northeast,
+        &room.northwest,
+        &room.southeast,
+        &room.southwest,
+    ]
+    .map(|nav| nav.as_ref().map(serde_json::to_string).transpose())
+}
+
 pub async fn find_by_id(
     pool: &SqlitePool,
     dungeon_id: &str,
     id: &str,
 ) -> Result<Option<Room>, PersistenceError> {
-    let row: Option<RoomRow> = sqlx::query_as(
-            "SELECT id, description_json, north_json, south_json, east_json, west_json FROM rooms WHERE dungeon_id = ? AND id = ?",
-        )
+    let sql = format!("SELECT {COLUMNS} FROM rooms WHERE dungeon_id = ? AND id = ?");
+    let row: Option<RoomRow> = sqlx::query_as(&sql)
         .bind(dungeon_id)
         .bind(id)
         .fetch_optional(pool)
         .await?;
 
-    row.map(|(id, desc_json, north, south, east, west)| {
-        parse_room(id, desc_json, north, south, east, west)
-    })
-    .transpose()
+    row.map(parse_room).transpose()
 }
 
 pub async fn find_by_dungeon(
     pool: &SqlitePool,
     dungeon_id: &str,
 ) -> Result<Vec<Room>, PersistenceError> {
-    let rows: Vec<RoomRow> = sqlx::query_as(
-            "SELECT id, description_json, north_json, south_json, east_json, west_json FROM rooms WHERE dungeon_id = ?",
-        )
+    let sql = format!("SELECT {COLUMNS} FROM rooms WHERE dungeon_id = ?");
+    let rows: Vec<RoomRow> = sqlx::query_as(&sql)
         .bind(dungeon_id)
         .fetch_all(pool)
         .await?;
 
-    rows.into_iter()
-        .map(|(id, desc_json, north, south, east, west)| {
-            parse_room(id, desc_json, north, south, east, west)
-        })
-        .collect()
+    rows.into_iter().map(parse_room).collect()
 }
 
 pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), PersistenceError> {
@@ -109,26 +136,46 @@ pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), PersistenceError>
     Ok(())
 }
 
-fn parse_room(
-    id: String,
-    desc_json: String,
-    north: Option<String>,
-    south: Option<String>,
-    east: Option<String>,
-    west: Option<String>,
-) -> Result<Room, PersistenceError> {
-    let description: Description = serde_json::from_str(&desc_json)?;
-    let north: Option<Navigation> = north.as_deref().map(serde_json::from_str).transpose()?;
-    let south: Option<Navigation> = south.as_deref().map(serde_json::from_str).transpose()?;
-    let east: Option<Navigation> = east.as_deref().map(serde_json::from_str).transpose()?;
-    let west: Option<Navigation> = west.as_deref().map(serde_json::from_str).transpose()?;
-    Ok(Room {
+fn parse_navigation(json: Option<String>) -> Result<Option<Navigation>, PersistenceError> {
+    Ok(json.as_deref().map(serde_json::from_str).transpose()?)
+}
+
+fn parse_room(row: RoomRow) -> Result<Room, PersistenceError> {
+    let (
         id,
-        description,
+        desc_json,
         north,
         south,
         east,
         west,
+        up,
+        down,
+        northeast,
+        northwest,
+        southeast,
+        southwest,
+        exits,
+    ) = row;
+    let description: Description = serde_json::from_str(&desc_json)?;
+    let exits: BTreeMap<String, Navigation> = exits
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()?
+        .unwrap_or_default();
+    Ok(Room {
+        id,
+        description,
+        north: parse_navigation(north)?,
+        south: parse_navigation(south)?,
+        east: parse_navigation(east)?,
+        west: parse_navigation(west)?,
+        up: parse_navigation(up)?,
+        down: parse_navigation(down)?,
+        northeast: parse_navigation(northeast)?,
+        northwest: parse_navigation(northwest)?,
+        southeast: parse_navigation(southeast)?,
+        southwest: parse_navigation(southwest)?,
+        exits,
         entities: Vec::new(),
     })
 }
@@ -214,4 +261,48 @@ mod tests {
         assert_eq!(north.world_id.as_deref(), Some("w1"));
         assert_eq!(north.room_id.as_deref(), Some("r2"));
     }
+
+    #[tokio::test]
+    async fn extended_and_named_exits_survive_round_trip() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let mut room = make_room("r1");
+        let nav = |room_id: &str| Navigation {
+            world_id: None,
+            dungeon_id: None,
+            room_id: Some(room_id.to_string()),
+        };
+        room.down = Some(nav("cellar"));
+        room.northeast = Some(nav("garden"));
+        room.exits.insert("tavern".to_string(), nav("tavern"));
+        insert(db.pool(), &room, "d1").await.unwrap();
+
+        let found = find_by_id(db.pool(), "d1", "r1").await.unwrap().unwrap();
+        assert_eq!(found.down.unwrap().room_id.as_deref(), Some("cellar"));
+        assert!(found.northeast.is_some());
+        assert!(found.up.is_none());
+        assert_eq!(found.exits["tavern"].room_id.as_deref(), Some("tavern"));
+    }
+
+    #[tokio::test]
+    async fn update_exits_changes_only_exits() {
+        let db = Database::connect_in_memory().await.unwrap();
+        setup(&db).await;
+        let mut room = make_room("r1");
+        insert(db.pool(), &room, "d1").await.unwrap();
+
+        room.set_exit(
+            "east",
+            Some(Navigation {
+                world_id: None,
+                dungeon_id: None,
+                room_id: Some("vault".to_string()),
+            }),
+        );
+        update_exits(db.pool(), &room, "d1").await.unwrap();
+
+        let found = find_by_id(db.pool(), "d1", "r1").await.unwrap().unwrap();
+        assert_eq!(found.east.unwrap().room_id.as_deref(), Some("vault"));
+        assert_eq!(found.description.standard.as_deref(), Some("A room."));
+    }
 }
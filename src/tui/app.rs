@@ -1,25 +1,36 @@
 mod network_event_handler;
 
+use crate::game::messaging::{CharacterSheet, PlayerStatus, SpeechKind};
 use crate::network::event::PlayerInfo;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageKind {
+    Normal,
+    Debug,
+    RoomTitle,
+    Exits,
+    Speech(SpeechKind),
+}
+
 #[derive(Debug, Clone)]
 pub struct AppMessage {
     pub text: String,
-    pub debug: bool,
+    pub kind: MessageKind,
 }
 
 impl AppMessage {
     pub fn normal(text: impl Into<String>) -> Self {
-        Self {
-            text: text.into(),
-            debug: false,
-        }
+        Self::with_kind(text, MessageKind::Normal)
     }
 
     pub fn debug(text: impl Into<String>) -> Self {
+        Self::with_kind(text, MessageKind::Debug)
+    }
+
+    pub fn with_kind(text: impl Into<String>, kind: MessageKind) -> Self {
         Self {
             text: text.into(),
-            debug: true,
+            kind,
         }
     }
 }
@@ -47,6 +58,8 @@ pub struct PlayerSelectState {
 pub struct App {
     pub should_quit: bool,
     pub messages: Vec<AppMessage>,
+    /// Channel chat, shown in its own pane.
+    pub channel_messages: Vec<AppMessage>,
     pub input: String,
     pub scroll_offset: usize,
     pub mode: AppMode,
@@ -54,6 +67,11 @@ pub struct App {
     pub player_select: PlayerSelectState,
     pub current_player_id: Option<i64>,
     pub streaming_message_index: Option<usize>,
+    /// Latest status pushed for the current player, shown in the status bar.
+    pub status: Option<PlayerStatus>,
+    /// Latest character sheet, shown in its own panel while `show_character_sheet` is set.
+    pub character_sheet: Option<CharacterSheet>,
+    pub show_character_sheet: bool,
     pub debug: bool,
 }
 
@@ -65,6 +83,7 @@ impl App {
                 AppMessage::normal("Welcome to mudroom."),
                 AppMessage::normal("Type commands and press Enter."),
             ],
+            channel_messages: Vec::new(),
             input: String::new(),
             scroll_offset: 0,
             mode: AppMode::Game,
@@ -72,6 +91,9 @@ impl App {
             player_select: PlayerSelectState::default(),
             current_player_id: None,
             streaming_message_index: None,
+            status: None,
+            character_sheet: None,
+            show_character_sheet: false,
             debug,
         }
     }
@@ -80,6 +102,7 @@ impl App {
         Self {
             should_quit: false,
             messages: Vec::<AppMessage>::new(),
+            channel_messages: Vec::new(),
             input: String::new(),
             scroll_offset: 0,
             mode: AppMode::PlayerSelect,
@@ -90,6 +113,9 @@ impl App {
             player_select: PlayerSelectState::default(),
             current_player_id: None,
             streaming_message_index: None,
+            status: None,
+            character_sheet: None,
+            show_character_sheet: false,
             debug,
         }
     }
@@ -1,6 +1,6 @@
 use crate::network::NetworkEvent;
 
-use super::{App, AppMessage};
+use super::{App, AppMessage, MessageKind};
 
 impl App {
     pub fn handle_network_event(&mut self, event: NetworkEvent) {
@@ -28,6 +28,8 @@ impl App {
             } => {
                 self.current_player_id = Some(player_id);
                 self.streaming_message_index = None;
+                self.status = None;
+                self.character_sheet = None;
                 self.messages
                     .push(AppMessage::normal(format!("Playing as: {player_name}")));
             }
@@ -61,6 +63,55 @@ impl App {
                     }
                 }
             }
+            NetworkEvent::RoomDescription { player_id, room } => {
+                if Some(player_id) == self.current_player_id {
+                    self.messages.push(AppMessage::with_kind(
+                        room.heading(),
+                        MessageKind::RoomTitle,
+                    ));
+                    self.messages
+                        .push(AppMessage::normal(room.description.clone()));
+                    self.messages
+                        .push(AppMessage::with_kind(room.exits_line(), MessageKind::Exits));
+                    for line in &room.entities {
+                        self.messages.push(AppMessage::normal(line.clone()));
+                    }
+                }
+            }
+            NetworkEvent::Speech {
+                player_id,
+                kind,
+                text,
+                ..
+            } => {
+                if Some(player_id) == self.current_player_id {
+                    self.messages
+                        .push(AppMessage::with_kind(text, MessageKind::Speech(kind)));
+                }
+            }
+            NetworkEvent::ChannelMessage {
+                player_id,
+                channel,
+                speaker,
+                content,
+            } => {
+                if Some(player_id) == self.current_player_id {
+                    self.channel_messages.push(AppMessage::normal(format!(
+                        "[{channel}] {speaker}: {content}"
+                    )));
+                }
+            }
+            NetworkEvent::PlayerStatus { player_id, status } => {
+                if Some(player_id) == self.current_player_id {
+                    self.status = Some(*status);
+                }
+            }
+            NetworkEvent::CharacterSheet { player_id, sheet } => {
+                if Some(player_id) == self.current_player_id {
+                    self.character_sheet = Some(*sheet);
+                    self.show_character_sheet = true;
+                }
+            }
         }
     }
 }
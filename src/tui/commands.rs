@@ -5,9 +5,59 @@ pub enum Command {
     Look,
     Help,
     Talk,
+    Attack(String),
+    Flee,
     Choose(String),
-    #[allow(dead_code)]
+    /// Anything that isn't a command, said to whoever the player is talking to.
+    Respond(String),
     Enter(String),
+    Say(String),
+    Emote(String),
+    Whisper {
+        target: String,
+        content: String,
+    },
+    Shout(String),
+    ChannelPost {
+        channel: String,
+        content: String,
+    },
+    ChannelJoin(String),
+    ChannelLeave(String),
+    Channels,
+    Tell {
+        target: String,
+        content: String,
+    },
+    Reply(String),
+    Mail {
+        target: String,
+        content: String,
+    },
+    ReadMail,
+    Get(String),
+    Drop(String),
+    Give {
+        item: String,
+        target: String,
+    },
+    Inventory,
+    Wear(String),
+    Remove(String),
+    Equipment,
+    Stats,
+    Search,
+    Use {
+        verb: String,
+        item: String,
+        target: Option<String>,
+    },
+    Open(String),
+    LookIn(String),
+    GetFrom {
+        item: String,
+        container: String,
+    },
     Unknown,
 }
 
@@ -15,26 +65,158 @@ pub fn parse(input: &str) -> Command {
     let trimmed = input.trim();
     let lower = trimmed.to_lowercase();
 
+    if let Some(direction) = Direction::parse(&lower) {
+        return Command::Move(direction);
+    }
+    if let Some(command) = parse_chat(trimmed) {
+        return command;
+    }
+
     match lower.as_str() {
-        "n" | "north" => Command::Move(Direction::North),
-        "s" | "south" => Command::Move(Direction::South),
-        "e" | "east" => Command::Move(Direction::East),
-        "w" | "west" => Command::Move(Direction::West),
+        "climb" => Command::Move(Direction::Up),
+        "descend" => Command::Move(Direction::Down),
         "l" | "look" => Command::Look,
         "h" | "help" => Command::Help,
         "talk" => Command::Talk,
+        "flee" => Command::Flee,
+        "channels" => Command::Channels,
+        "mail" => Command::ReadMail,
+        "i" | "inv" | "inventory" => Command::Inventory,
+        "eq" | "equipment" => Command::Equipment,
+        "score" | "stats" => Command::Stats,
+        "search" => Command::Search,
         _ => {
             if lower.chars().all(|c| c.is_ascii_digit()) && !lower.is_empty() {
                 Command::Choose(lower)
-            } else if let Some(target) = lower.strip_prefix("enter ") {
-                Command::Enter(target.to_string())
-            } else {
+            } else if let Some(target) = lower.strip_prefix("attack ") {
+                Command::Attack(target.trim().to_string())
+            } else if let Some(container) = ["look in ", "l in "]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix))
+            {
+                Command::LookIn(container.trim().to_string())
+            } else if let Some(container) = lower.strip_prefix("open ") {
+                Command::Open(container.trim().to_string())
+            } else if let Some((item, container)) = ["get ", "take "]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix))
+                .and_then(|rest| rest.split_once(" from "))
+            {
+                Command::GetFrom {
+                    item: item.trim().to_string(),
+                    container: container.trim().to_string(),
+                }
+            } else if let Some(item) = ["get ", "take ", "pick up "]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix))
+            {
+                Command::Get(item.trim().to_string())
+            } else if let Some(item) = ["wear ", "wield ", "equip "]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix))
+            {
+                Command::Wear(item.trim().to_string())
+            } else if let Some(item) = ["remove ", "unequip "]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix))
+            {
+                Command::Remove(item.trim().to_string())
+            } else if let Some(rest) = lower.strip_prefix("use ") {
+                match rest.split_once(" on ") {
+                    Some((item, target)) => Command::Use {
+                        verb: "use".to_string(),
+                        item: item.trim().to_string(),
+                        target: Some(target.trim().to_string()),
+                    },
+                    None => Command::Use {
+                        verb: "use".to_string(),
+                        item: rest.trim().to_string(),
+                        target: None,
+                    },
+                }
+            } else if let Some((verb, item)) = ["drink", "quaff", "eat", "read"]
+                .iter()
+                .find_map(|verb| Some((*verb, lower.strip_prefix(verb)?.strip_prefix(' ')?)))
+            {
+                Command::Use {
+                    verb: verb.to_string(),
+                    item: item.trim().to_string(),
+                    target: None,
+                }
+            } else if let Some(item) = lower.strip_prefix("drop ") {
+                Command::Drop(item.trim().to_string())
+            } else if let Some((item, target)) = lower
+                .strip_prefix("give ")
+                .and_then(|rest| rest.split_once(" to "))
+            {
+                Command::Give {
+                    item: item.trim().to_string(),
+                    target: target.trim().to_string(),
+                }
+            } else if let Some(target) = ["enter ", "climb ", "go "]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix))
+            {
+                let target = target.trim();
+                match Direction::parse(target) {
+                    Some(direction) => Command::Move(direction),
+                    None => Command::Enter(target.to_string()),
+                }
+            } else if trimmed.is_empty() {
                 Command::Unknown
+            } else {
+                Command::Respond(trimmed.to_string())
             }
         }
     }
 }
 
+/// Chat commands keep the player's original casing, so they're matched on the trimmed input.
+fn parse_chat(input: &str) -> Option<Command> {
+    let (word, rest) = input.split_once(char::is_whitespace)?;
+    let rest = rest.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    match word.to_lowercase().as_str() {
+        "say" => Some(Command::Say(rest.to_string())),
+        "emote" | "me" => Some(Command::Emote(rest.to_string())),
+        "shout" => Some(Command::Shout(rest.to_string())),
+        "join" => Some(Command::ChannelJoin(rest.to_lowercase())),
+        "leave" => Some(Command::ChannelLeave(rest.to_lowercase())),
+        "reply" => Some(Command::Reply(rest.to_string())),
+        "tell" => {
+            let (target, content) = rest.split_once(char::is_whitespace)?;
+            Some(Command::Tell {
+                target: target.to_string(),
+                content: content.trim().to_string(),
+            })
+        }
+        "mail" => {
+            let (target, content) = rest.split_once(char::is_whitespace)?;
+            Some(Command::Mail {
+                target: target.to_string(),
+                content: content.trim().to_string(),
+            })
+        }
+        "chat" => {
+            let (channel, content) = rest.split_once(char::is_whitespace)?;
+            Some(Command::ChannelPost {
+                channel: channel.to_lowercase(),
+                content: content.trim().to_string(),
+            })
+        }
+        "whisper" => {
+            let (target, content) = rest.split_once(char::is_whitespace)?;
+            Some(Command::Whisper {
+                target: target.to_string(),
+                content: content.trim().to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,6 +255,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_vertical_and_diagonal_directions() {
+        assert!(matches!(parse("u"), Command::Move(Direction::Up)));
+        assert!(matches!(parse("climb"), Command::Move(Direction::Up)));
+        assert!(matches!(parse("descend"), Command::Move(Direction::Down)));
+        assert!(matches!(parse("NE"), Command::Move(Direction::Northeast)));
+        assert!(matches!(
+            parse("southwest"),
+            Command::Move(Direction::Southwest)
+        ));
+    }
+
+    #[test]
+    fn parse_go_and_climb() {
+        assert!(matches!(parse("go north"), Command::Move(Direction::North)));
+        assert!(matches!(parse("climb up"), Command::Move(Direction::Up)));
+        if let Command::Enter(target) = parse("climb  Ladder") {
+            assert_eq!(target, "ladder");
+        } else {
+            panic!("expected Enter");
+        }
+        assert!(matches!(parse("go tavern"), Command::Enter(_)));
+    }
+
     #[test]
     fn parse_help_variants() {
         assert!(matches!(parse("h"), Command::Help));
@@ -81,10 +287,133 @@ mod tests {
         assert!(matches!(parse("Help"), Command::Help));
     }
 
+    #[test]
+    fn parse_attack_and_flee() {
+        if let Command::Attack(target) = parse("Attack  Goblin") {
+            assert_eq!(target, "goblin");
+        } else {
+            panic!("expected Attack");
+        }
+        assert!(matches!(parse("flee"), Command::Flee));
+        assert!(matches!(parse("attack"), Command::Respond(_)));
+    }
+
     #[test]
     fn parse_unknown() {
-        assert!(matches!(parse("foo"), Command::Unknown));
         assert!(matches!(parse(""), Command::Unknown));
-        assert!(matches!(parse("go north"), Command::Unknown));
+        assert!(matches!(parse("   "), Command::Unknown));
+    }
+
+    #[test]
+    fn parse_free_text_as_respond() {
+        if let Command::Respond(content) = parse("  Got a Room? ") {
+            assert_eq!(content, "Got a Room?");
+        } else {
+            panic!("expected Respond");
+        }
+        assert!(matches!(parse("bye"), Command::Respond(_)));
+        assert!(matches!(parse("go"), Command::Respond(_)));
+    }
+
+    #[test]
+    fn parse_chat_commands() {
+        if let Command::Say(content) = parse("Say Hello there!") {
+            assert_eq!(content, "Hello there!");
+        } else {
+            panic!("expected Say");
+        }
+        assert!(matches!(parse("me waves"), Command::Emote(_)));
+        assert!(matches!(parse("shout HELP"), Command::Shout(c) if c == "HELP"));
+        if let Command::Whisper { target, content } = parse("whisper Bob  Meet me upstairs") {
+            assert_eq!(target, "Bob");
+            assert_eq!(content, "Meet me upstairs");
+        } else {
+            panic!("expected Whisper");
+        }
+        assert!(matches!(parse("whisper Bob"), Command::Respond(_)));
+        assert!(matches!(parse("say"), Command::Respond(_)));
+    }
+
+    #[test]
+    fn parse_channel_commands() {
+        if let Command::ChannelPost { channel, content } = parse("chat OOC Anyone around?") {
+            assert_eq!(channel, "ooc");
+            assert_eq!(content, "Anyone around?");
+        } else {
+            panic!("expected ChannelPost");
+        }
+        assert!(matches!(parse("join trade"), Command::ChannelJoin(c) if c == "trade"));
+        assert!(matches!(parse("leave newbie"), Command::ChannelLeave(_)));
+        assert!(matches!(parse("channels"), Command::Channels));
+    }
+
+    #[test]
+    fn parse_tell_reply_and_mail() {
+        if let Command::Tell { target, content } = parse("tell Bob Where are you?") {
+            assert_eq!(target, "Bob");
+            assert_eq!(content, "Where are you?");
+        } else {
+            panic!("expected Tell");
+        }
+        assert!(matches!(parse("reply On my way"), Command::Reply(c) if c == "On my way"));
+        assert!(matches!(
+            parse("mail Bob See you tomorrow"),
+            Command::Mail { .. }
+        ));
+        assert!(matches!(parse("mail"), Command::ReadMail));
+    }
+
+    #[test]
+    fn parse_inventory_commands() {
+        assert!(matches!(parse("get Lantern"), Command::Get(i) if i == "lantern"));
+        assert!(matches!(parse("pick up lantern"), Command::Get(_)));
+        assert!(matches!(parse("drop lantern"), Command::Drop(_)));
+        if let Command::Give { item, target } = parse("give lantern to Innkeeper") {
+            assert_eq!(item, "lantern");
+            assert_eq!(target, "innkeeper");
+        } else {
+            panic!("expected Give");
+        }
+        assert!(matches!(parse("give lantern"), Command::Respond(_)));
+        assert!(matches!(parse("i"), Command::Inventory));
+    }
+
+    #[test]
+    fn parse_equipment_commands() {
+        assert!(matches!(parse("wield Sword"), Command::Wear(i) if i == "sword"));
+        assert!(matches!(parse("wear helmet"), Command::Wear(_)));
+        assert!(matches!(parse("remove helmet"), Command::Remove(_)));
+        assert!(matches!(parse("eq"), Command::Equipment));
+        assert!(matches!(parse("score"), Command::Stats));
+        assert!(matches!(parse("stats"), Command::Stats));
+        assert!(matches!(parse("search"), Command::Search));
+    }
+
+    #[test]
+    fn parse_use_commands() {
+        assert!(matches!(
+            parse("use potion on Bob"),
+            Command::Use { item, target: Some(target), .. } if item == "potion" && target == "bob"
+        ));
+        assert!(matches!(
+            parse("drink potion"),
+            Command::Use { target: None, .. }
+        ));
+        assert!(matches!(
+            parse("read scroll"),
+            Command::Use { verb, item, .. } if verb == "read" && item == "scroll"
+        ));
+        assert!(matches!(parse("pull lever"), Command::Respond(_)));
+    }
+
+    #[test]
+    fn parse_container_commands() {
+        assert!(matches!(parse("open chest"), Command::Open(c) if c == "chest"));
+        assert!(matches!(parse("look in chest"), Command::LookIn(c) if c == "chest"));
+        assert!(matches!(
+            parse("get potion from chest"),
+            Command::GetFrom { item, container } if item == "potion" && container == "chest"
+        ));
+        assert!(matches!(parse("get potion"), Command::Get(_)));
     }
 }
@@ -166,6 +166,17 @@ async fn handle_game_key(app: &mut App, modifiers: KeyModifiers, code: KeyCode)
                             send_interaction(url, client_id, &Interaction::StartConversation).await;
                     }
                 }
+                commands::Command::Attack(target) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ =
+                            send_interaction(url, client_id, &Interaction::Attack(target)).await;
+                    }
+                }
+                commands::Command::Flee => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Flee).await;
+                    }
+                }
                 commands::Command::Choose(choice) => {
                     if let (Some(url), Some(client_id)) = (url, client_id) {
                         let action =
@@ -175,11 +186,174 @@ async fn handle_game_key(app: &mut App, modifiers: KeyModifiers, code: KeyCode)
                         let _ = send_interaction(url, client_id, &action).await;
                     }
                 }
+                commands::Command::Enter(target) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::Movement(Movement::Enter(target));
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::Say(content) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Say(content)).await;
+                    }
+                }
+                commands::Command::Emote(content) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ =
+                            send_interaction(url, client_id, &Interaction::Emote(content)).await;
+                    }
+                }
+                commands::Command::Whisper { target, content } => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::Whisper { target, content };
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::Shout(content) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ =
+                            send_interaction(url, client_id, &Interaction::Shout(content)).await;
+                    }
+                }
+                commands::Command::ChannelPost { channel, content } => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::ChannelPost { channel, content };
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::ChannelJoin(channel) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::ChannelJoin(channel);
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::ChannelLeave(channel) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::ChannelLeave(channel);
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::Channels => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Channels).await;
+                    }
+                }
+                commands::Command::Tell { target, content } => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::Tell { target, content };
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::Reply(content) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ =
+                            send_interaction(url, client_id, &Interaction::Reply(content)).await;
+                    }
+                }
+                commands::Command::Mail { target, content } => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::Mail { target, content };
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::ReadMail => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::ReadMail).await;
+                    }
+                }
+                commands::Command::Get(item) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Get(item)).await;
+                    }
+                }
+                commands::Command::Drop(item) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Drop(item)).await;
+                    }
+                }
+                commands::Command::Give { item, target } => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::Give { item, target };
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::Inventory => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Inventory).await;
+                    }
+                }
+                commands::Command::Wear(item) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Wear(item)).await;
+                    }
+                }
+                commands::Command::Remove(item) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Remove(item)).await;
+                    }
+                }
+                commands::Command::Equipment => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Equipment).await;
+                    }
+                }
+                commands::Command::Stats => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Stats).await;
+                    }
+                }
+                commands::Command::Search => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let _ = send_interaction(url, client_id, &Interaction::Search).await;
+                    }
+                }
+                commands::Command::Use { verb, item, target } => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::Use { verb, item, target };
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::Open(container) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::Open(container);
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::LookIn(container) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::LookIn(container);
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::GetFrom { item, container } => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let interaction = Interaction::GetFrom { item, container };
+                        let _ = send_interaction(url, client_id, &interaction).await;
+                    }
+                }
+                commands::Command::Respond(content) => {
+                    if let (Some(url), Some(client_id)) = (url, client_id) {
+                        let action = Interaction::EngagementAction(TurnAction::Respond { content });
+                        let _ = send_interaction(url, client_id, &action).await;
+                    }
+                }
                 _ => {}
             }
             app.messages.push(AppMessage::normal(input));
             app.scroll_offset = 0;
         }
+        (_, KeyCode::F(2)) => {
+            app.show_character_sheet = !app.show_character_sheet;
+            // Fetch a fresh sheet whenever the panel opens.
+            if app.show_character_sheet
+                && let (Some(url), Some(client_id)) = (
+                    app.connection.server_url.as_deref(),
+                    app.connection.client_id.as_deref(),
+                )
+            {
+                let _ = send_interaction(url, client_id, &Interaction::Stats).await;
+            }
+        }
         (_, KeyCode::PageUp) => app.scroll_up(),
         (_, KeyCode::PageDown) => app.scroll_down(),
         _ => {}
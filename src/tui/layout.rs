@@ -1,13 +1,16 @@
 use ratatui::{
     Frame,
-    layout::{Constraint, Layout},
-    style::{Color, Style},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, LineGauge, Paragraph, Wrap},
 };
 
-use super::app::{App, AppMode};
+use super::app::{App, AppMode, MessageKind};
 use super::player_select;
+use crate::game::AttributeCategory;
+use crate::game::messaging::character_sheet::category_title;
+use crate::game::messaging::{CharacterSheet, PlayerStatus, SpeechKind};
 
 pub fn render(frame: &mut Frame, app: &App) {
     if app.mode == AppMode::PlayerSelect {
@@ -17,13 +20,23 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     let areas = Layout::vertical([
         Constraint::Fill(1),
+        Constraint::Length(7),
         Constraint::Length(3),
         Constraint::Length(3),
     ])
     .split(frame.area());
 
-    // Message log
-    let visible_lines = areas[0].height.saturating_sub(2) as usize;
+    // Message log, with the character sheet beside it when open
+    let log_area = match (&app.character_sheet, app.show_character_sheet) {
+        (Some(sheet), true) => {
+            let columns =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(42)]).split(areas[0]);
+            render_character_sheet(frame, sheet, columns[1]);
+            columns[0]
+        }
+        _ => areas[0],
+    };
+    let visible_lines = log_area.height.saturating_sub(2) as usize;
     let total = app.messages.len();
     let max_offset = total.saturating_sub(visible_lines);
     let effective_offset = app.scroll_offset.min(max_offset);
@@ -32,10 +45,22 @@ pub fn render(frame: &mut Frame, app: &App) {
     let log_lines: Vec<Line> = app.messages[start..end]
         .iter()
         .flat_map(|msg| {
-            let style = if msg.debug {
-                Style::default().fg(Color::DarkGray)
-            } else {
-                Style::default()
+            let style = match msg.kind {
+                MessageKind::Normal => Style::default(),
+                MessageKind::Debug => Style::default().fg(Color::DarkGray),
+                MessageKind::RoomTitle => Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+                MessageKind::Exits => Style::default().fg(Color::Magenta),
+                MessageKind::Speech(SpeechKind::Say) => Style::default().fg(Color::LightCyan),
+                MessageKind::Speech(SpeechKind::Emote) => Style::default().fg(Color::LightGreen),
+                MessageKind::Speech(SpeechKind::Whisper) => Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::ITALIC),
+                MessageKind::Speech(SpeechKind::Tell) => Style::default().fg(Color::LightMagenta),
+                MessageKind::Speech(SpeechKind::Shout) => Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
             };
             msg.text
                 .split('\n')
@@ -45,18 +70,142 @@ pub fn render(frame: &mut Frame, app: &App) {
         .collect();
     let log = Paragraph::new(Text::from(log_lines))
         .block(Block::default().title("Messages").borders(Borders::ALL));
-    frame.render_widget(log, areas[0]);
+    frame.render_widget(log, log_area);
+
+    // Channel chat, newest at the bottom
+    let channel_lines = areas[1].height.saturating_sub(2) as usize;
+    let channel_start = app.channel_messages.len().saturating_sub(channel_lines);
+    let channel_text: Vec<Line> = app.channel_messages[channel_start..]
+        .iter()
+        .map(|msg| Line::from(msg.text.clone()))
+        .collect();
+    let channels = Paragraph::new(Text::from(channel_text))
+        .style(Style::default().fg(Color::LightYellow))
+        .block(Block::default().title("Channels").borders(Borders::ALL));
+    frame.render_widget(channels, areas[1]);
 
     // Status bar
-    let status = Paragraph::new("HP: 100 | MP: 50 | Location: Town Square")
-        .style(Style::default().fg(Color::Green))
-        .block(Block::default().title("Status").borders(Borders::ALL));
-    frame.render_widget(status, areas[1]);
+    let status_block = Block::default().title("Status").borders(Borders::ALL);
+    let status_area = status_block.inner(areas[2]);
+    frame.render_widget(status_block, areas[2]);
+    match &app.status {
+        Some(status) => render_status(frame, status, status_area),
+        None => frame.render_widget(
+            Paragraph::new("Waiting for status...").style(Style::default().fg(Color::DarkGray)),
+            status_area,
+        ),
+    }
 
     // Input line
     let input_text = format!("> {}", app.input);
     let input = Paragraph::new(Text::from(input_text))
         .style(Style::default().fg(Color::Yellow))
         .block(Block::default().title("Input").borders(Borders::ALL));
-    frame.render_widget(input, areas[2]);
+    frame.render_widget(input, areas[3]);
+}
+
+/// One gauge per status attribute, followed by the player's location.
+fn render_status(frame: &mut Frame, status: &PlayerStatus, area: Rect) {
+    let location = format!("{} ({})", status.room, status.dungeon);
+    let constraints = status
+        .attributes
+        .iter()
+        .map(|_| Constraint::Fill(1))
+        .chain([Constraint::Length(location.len() as u16 + 1)]);
+    let areas = Layout::horizontal(constraints).spacing(2).split(area);
+
+    for (attribute, area) in status.attributes.iter().zip(areas.iter()) {
+        let ratio = if attribute.max > 0 {
+            (attribute.current as f64 / attribute.max as f64).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let color = category_color(&attribute.category, ratio);
+        let gauge = LineGauge::default()
+            .ratio(ratio)
+            .label(Span::styled(
+                format!(
+                    "{} {}/{}",
+                    attribute.id.to_uppercase(),
+                    attribute.current,
+                    attribute.max
+                ),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ))
+            .filled_style(Style::default().fg(color))
+            .unfilled_style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(gauge, *area);
+    }
+
+    if let Some(location_area) = areas.last() {
+        let location = Paragraph::new(location).style(Style::default().fg(Color::Cyan));
+        frame.render_widget(location, *location_area);
+    }
+}
+
+/// Gauge colour for an attribute. Life turns from green to yellow to red as it runs low.
+fn category_color(category: &AttributeCategory, ratio: f64) -> Color {
+    match category {
+        AttributeCategory::Life if ratio <= 0.25 => Color::Red,
+        AttributeCategory::Life if ratio <= 0.5 => Color::Yellow,
+        AttributeCategory::Life => Color::Green,
+        AttributeCategory::Speed => Color::LightYellow,
+        AttributeCategory::General => Color::LightBlue,
+    }
+}
+
+/// The character sheet: attributes under a heading per category, then active effects.
+fn render_character_sheet(frame: &mut Frame, sheet: &CharacterSheet, area: Rect) {
+    let heading = Style::default().add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    for group in &sheet.groups {
+        let color = category_color(&group.category, 1.0);
+        lines.push(Line::from(Span::styled(
+            category_title(&group.category),
+            heading.fg(color),
+        )));
+        for attribute in &group.attributes {
+            let mut spans = vec![
+                Span::raw(format!("  {:<16}", attribute.title)),
+                Span::styled(
+                    format!("{}/{}", attribute.value, attribute.max),
+                    Style::default().fg(color),
+                ),
+            ];
+            let modifier = attribute.value - attribute.base;
+            if modifier != 0 {
+                spans.push(Span::styled(format!(" ({modifier:+})"), dim));
+            }
+            lines.push(Line::from(spans));
+            lines.push(Line::from(Span::styled(
+                format!("    {}", attribute.description),
+                dim,
+            )));
+        }
+        lines.push(Line::default());
+    }
+    lines.push(Line::from(Span::styled("Effects", heading)));
+    if sheet.effects.is_empty() {
+        lines.push(Line::from(Span::styled("  none", dim)));
+    }
+    for effect in &sheet.effects {
+        let mut spans = vec![Span::raw(format!("  {}", effect.name))];
+        if let Some(ticks) = effect.remaining_ticks {
+            spans.push(Span::styled(format!(" ({ticks} ticks left)"), dim));
+        }
+        lines.push(Line::from(spans));
+        if let Some(description) = &effect.description {
+            lines.push(Line::from(Span::styled(format!("    {description}"), dim)));
+        }
+    }
+
+    let panel = Paragraph::new(Text::from(lines))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(format!("{} (F2 to close)", sheet.name))
+                .borders(Borders::ALL),
+        );
+    frame.render_widget(panel, area);
 }